@@ -0,0 +1,140 @@
+; ModuleID = 'fib.c'
+source_filename = "fib.c"
+target datalayout = "e-m:o-i64:64-i128:128-n32:64-S128"
+target triple = "arm64-apple-macosx14.0.0"
+
+@.str = private unnamed_addr constant [28 x i8] c"Enter the number of terms: \00", align 1
+@.str.1 = private unnamed_addr constant [3 x i8] c"%d\00", align 1
+@.str.2 = private unnamed_addr constant [27 x i8] c"Fibonacci Series: %d, %d, \00", align 1
+@.str.3 = private unnamed_addr constant [5 x i8] c"%d, \00", align 1
+
+; Function Attrs: noinline nounwind optnone ssp uwtable(sync)
+define i32 @main() #0 !dbg !9 {
+  %1 = alloca i32, align 4
+  %2 = alloca i32, align 4
+  %3 = alloca i32, align 4
+  %4 = alloca i32, align 4
+  %5 = alloca i32, align 4
+  %6 = alloca i32, align 4
+  store i32 0, ptr %1, align 4
+  call void @llvm.dbg.declare(metadata ptr %2, metadata !14, metadata !DIExpression()), !dbg !15
+  call void @llvm.dbg.declare(metadata ptr %3, metadata !16, metadata !DIExpression()), !dbg !17
+  call void @llvm.dbg.declare(metadata ptr %4, metadata !18, metadata !DIExpression()), !dbg !19
+  store i32 0, ptr %4, align 4, !dbg !19
+  call void @llvm.dbg.declare(metadata ptr %5, metadata !20, metadata !DIExpression()), !dbg !21
+  store i32 1, ptr %5, align 4, !dbg !21
+  call void @llvm.dbg.declare(metadata ptr %6, metadata !22, metadata !DIExpression()), !dbg !23
+  %7 = load i32, ptr %4, align 4, !dbg !24
+  %8 = load i32, ptr %5, align 4, !dbg !25
+  %9 = add nsw i32 %7, %8, !dbg !26
+  store i32 %9, ptr %6, align 4, !dbg !23
+  %10 = call i32 (ptr, ...) @printf(ptr noundef @.str), !dbg !27
+  %11 = call i32 (ptr, ...) @scanf(ptr noundef @.str.1, ptr noundef %3), !dbg !28
+  %12 = load i32, ptr %4, align 4, !dbg !29
+  %13 = load i32, ptr %5, align 4, !dbg !30
+  %14 = call i32 (ptr, ...) @printf(ptr noundef @.str.2, i32 noundef %12, i32 noundef %13), !dbg !31
+  store i32 3, ptr %2, align 4, !dbg !32
+  br label %15, !dbg !34
+
+15:                                               ; preds = %27, %0
+  %16 = load i32, ptr %2, align 4, !dbg !35
+  %17 = load i32, ptr %3, align 4, !dbg !37
+  %18 = icmp sle i32 %16, %17, !dbg !38
+  br i1 %18, label %19, label %30, !dbg !39
+
+19:                                               ; preds = %15
+  %20 = load i32, ptr %6, align 4, !dbg !40
+  %21 = call i32 (ptr, ...) @printf(ptr noundef @.str.3, i32 noundef %20), !dbg !42
+  %22 = load i32, ptr %5, align 4, !dbg !43
+  store i32 %22, ptr %4, align 4, !dbg !44
+  %23 = load i32, ptr %6, align 4, !dbg !45
+  store i32 %23, ptr %5, align 4, !dbg !46
+  %24 = load i32, ptr %4, align 4, !dbg !47
+  %25 = load i32, ptr %5, align 4, !dbg !48
+  %26 = add nsw i32 %24, %25, !dbg !49
+  store i32 %26, ptr %6, align 4, !dbg !50
+  br label %27, !dbg !51
+
+27:                                               ; preds = %19
+  %28 = load i32, ptr %2, align 4, !dbg !52
+  %29 = add nsw i32 %28, 1, !dbg !52
+  store i32 %29, ptr %2, align 4, !dbg !52
+  br label %15, !dbg !53, !llvm.loop !54
+
+30:                                               ; preds = %15
+  ret i32 0, !dbg !57
+}
+
+; Function Attrs: nocallback nofree nosync nounwind speculatable willreturn memory(none)
+declare void @llvm.dbg.declare(metadata, metadata, metadata) #1
+
+declare i32 @printf(ptr noundef, ...) #2
+
+declare i32 @scanf(ptr noundef, ...) #2
+
+attributes #0 = { noinline nounwind optnone ssp uwtable(sync) "frame-pointer"="non-leaf" "no-trapping-math"="true" "stack-protector-buffer-size"="8" "target-cpu"="apple-m1" "target-features"="+aes,+crc,+dotprod,+fp-armv8,+fp16fml,+fullfp16,+lse,+neon,+ras,+rcpc,+rdm,+sha2,+sha3,+v8.1a,+v8.2a,+v8.3a,+v8.4a,+v8.5a,+v8a,+zcm,+zcz" }
+attributes #1 = { nocallback nofree nosync nounwind speculatable willreturn memory(none) }
+attributes #2 = { "frame-pointer"="non-leaf" "no-trapping-math"="true" "stack-protector-buffer-size"="8" "target-cpu"="apple-m1" "target-features"="+aes,+crc,+dotprod,+fp-armv8,+fp16fml,+fullfp16,+lse,+neon,+ras,+rcpc,+rdm,+sha2,+sha3,+v8.1a,+v8.2a,+v8.3a,+v8.4a,+v8.5a,+v8a,+zcm,+zcz" }
+
+!llvm.dbg.cu = !{!0}
+!llvm.module.flags = !{!2, !3, !4, !5, !6, !7}
+!llvm.ident = !{!8}
+
+!0 = distinct !DICompileUnit(language: DW_LANG_C11, file: !1, producer: "Homebrew clang version 17.0.6", isOptimized: false, runtimeVersion: 0, emissionKind: FullDebug, splitDebugInlining: false, nameTableKind: None)
+!1 = !DIFile(filename: "fib.c", directory: "/tmp")
+!2 = !{i32 7, !"Dwarf Version", i32 4}
+!3 = !{i32 2, !"Debug Info Version", i32 3}
+!4 = !{i32 1, !"wchar_size", i32 4}
+!5 = !{i32 8, !"PIC Level", i32 2}
+!6 = !{i32 7, !"uwtable", i32 1}
+!7 = !{i32 7, !"frame-pointer", i32 1}
+!8 = !{!"Homebrew clang version 17.0.6"}
+!9 = distinct !DISubprogram(name: "main", scope: !1, file: !1, line: 4, type: !10, scopeLine: 4, spFlags: DISPFlagDefinition, unit: !0, retainedNodes: !13)
+!10 = !DISubroutineType(types: !11)
+!11 = !{!12}
+!12 = !DIBasicType(name: "int", size: 32, encoding: DW_ATE_signed)
+!13 = !{}
+!14 = !DILocalVariable(name: "i", scope: !9, file: !1, line: 6, type: !12)
+!15 = !DILocation(line: 6, column: 7, scope: !9)
+!16 = !DILocalVariable(name: "n", scope: !9, file: !1, line: 6, type: !12)
+!17 = !DILocation(line: 6, column: 10, scope: !9)
+!18 = !DILocalVariable(name: "t1", scope: !9, file: !1, line: 9, type: !12)
+!19 = !DILocation(line: 9, column: 7, scope: !9)
+!20 = !DILocalVariable(name: "t2", scope: !9, file: !1, line: 9, type: !12)
+!21 = !DILocation(line: 9, column: 15, scope: !9)
+!22 = !DILocalVariable(name: "nextTerm", scope: !9, file: !1, line: 12, type: !12)
+!23 = !DILocation(line: 12, column: 7, scope: !9)
+!24 = !DILocation(line: 12, column: 18, scope: !9)
+!25 = !DILocation(line: 12, column: 23, scope: !9)
+!26 = !DILocation(line: 12, column: 21, scope: !9)
+!27 = !DILocation(line: 15, column: 3, scope: !9)
+!28 = !DILocation(line: 16, column: 3, scope: !9)
+!29 = !DILocation(line: 19, column: 40, scope: !9)
+!30 = !DILocation(line: 19, column: 44, scope: !9)
+!31 = !DILocation(line: 19, column: 3, scope: !9)
+!32 = !DILocation(line: 22, column: 10, scope: !33)
+!33 = distinct !DILexicalBlock(scope: !9, file: !1, line: 22, column: 3)
+!34 = !DILocation(line: 22, column: 8, scope: !33)
+!35 = !DILocation(line: 22, column: 15, scope: !36)
+!36 = distinct !DILexicalBlock(scope: !33, file: !1, line: 22, column: 3)
+!37 = !DILocation(line: 22, column: 20, scope: !36)
+!38 = !DILocation(line: 22, column: 17, scope: !36)
+!39 = !DILocation(line: 22, column: 3, scope: !33)
+!40 = !DILocation(line: 23, column: 20, scope: !41)
+!41 = distinct !DILexicalBlock(scope: !36, file: !1, line: 22, column: 28)
+!42 = !DILocation(line: 23, column: 5, scope: !41)
+!43 = !DILocation(line: 24, column: 10, scope: !41)
+!44 = !DILocation(line: 24, column: 8, scope: !41)
+!45 = !DILocation(line: 25, column: 10, scope: !41)
+!46 = !DILocation(line: 25, column: 8, scope: !41)
+!47 = !DILocation(line: 26, column: 16, scope: !41)
+!48 = !DILocation(line: 26, column: 21, scope: !41)
+!49 = !DILocation(line: 26, column: 19, scope: !41)
+!50 = !DILocation(line: 26, column: 14, scope: !41)
+!51 = !DILocation(line: 27, column: 3, scope: !41)
+!52 = !DILocation(line: 22, column: 23, scope: !36)
+!53 = !DILocation(line: 22, column: 3, scope: !36)
+!54 = distinct !{!54, !39, !55, !56}
+!55 = !DILocation(line: 27, column: 3, scope: !33)
+!56 = !{!"llvm.loop.mustprogress"}
+!57 = !DILocation(line: 29, column: 3, scope: !9)
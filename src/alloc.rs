@@ -0,0 +1,246 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fmt;
+
+use crate::ir::{self, Function, Name};
+
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Register(pub usize);
+
+impl fmt::Display for Register {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "r{}", self.0)
+    }
+}
+
+impl std::str::FromStr for Register {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.strip_prefix('r')
+            .and_then(|n| n.parse().ok())
+            .map(Register)
+            .ok_or_else(|| format!("invalid register: {s:?}"))
+    }
+}
+
+pub type Assignment = HashMap<Name, Register>;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Violation {
+    /// `a` and `b` are live at the same time but share `register`.
+    Conflict {
+        index: usize,
+        a: Name,
+        b: Name,
+        register: Register,
+    },
+    /// `name` is used or defined without having been assigned a register.
+    Unassigned { index: usize, name: Name },
+    /// `by` is defined into `register` while the precolored `name` is live in it.
+    Clobbered {
+        index: usize,
+        name: Name,
+        by: Name,
+        register: Register,
+    },
+}
+
+impl Violation {
+    /// Index of the instruction in `ir::lva` order where the violation occurs.
+    pub fn index(&self) -> usize {
+        match self {
+            Violation::Conflict { index, .. }
+            | Violation::Unassigned { index, .. }
+            | Violation::Clobbered { index, .. } => *index,
+        }
+    }
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Violation::Conflict { a, b, register, .. } => {
+                write!(f, "{a:?} and {b:?} are both live in {register}")
+            }
+            Violation::Unassigned { name, .. } => write!(f, "{name:?} has no register"),
+            Violation::Clobbered {
+                name, by, register, ..
+            } => write!(f, "{by:?} clobbers {register} holding {name:?}"),
+        }
+    }
+}
+
+/// Parses assignments of the form `%1=r0 %2=r1`, separated by whitespace or
+/// commas. A trailing `!` (e.g. `%0=r0!`) marks the value as precolored.
+pub fn parse(s: &str) -> Result<(Assignment, Assignment), String> {
+    let mut assignment = Assignment::new();
+    let mut precolored = Assignment::new();
+    for entry in s
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|e| !e.is_empty())
+    {
+        let (name, register) = entry
+            .split_once('=')
+            .ok_or_else(|| format!("expected `%name=rN`, found {entry:?}"))?;
+        let name: Name = name.parse()?;
+        if let Some(register) = register.strip_suffix('!') {
+            precolored.insert(name, register.parse()?);
+        } else {
+            assignment.insert(name, register.parse()?);
+        }
+    }
+    Ok((assignment, precolored))
+}
+
+/// Checks `assignment` against the liveness `lives` computed by `ir::lva`.
+///
+/// Precolored values take their register from `precolored`, regardless of
+/// what `assignment` says about them.
+pub fn check(
+    f: &Function,
+    lives: &[(HashSet<&Name>, HashSet<&Name>, &str)],
+    assignment: &Assignment,
+    precolored: &Assignment,
+) -> Vec<Violation> {
+    tracing::trace!("check {}", f.name);
+
+    let register = |n: &Name| precolored.get(n).or_else(|| assignment.get(n)).copied();
+    let defs = ir::def(f);
+    let uses = ir::r#use(f);
+
+    let mut violations = vec![];
+    for (j, (r#in, out, _)) in lives.iter().enumerate() {
+        let accessed: BTreeSet<&Name> = defs[j].iter().chain(uses[j].iter()).copied().collect();
+        for name in accessed {
+            if register(name).is_none() {
+                violations.push(Violation::Unassigned {
+                    index: j,
+                    name: name.clone(),
+                });
+            }
+        }
+
+        // values live on entry interfere, and so do values live on exit along
+        // with whatever this instruction writes
+        let entry: BTreeSet<&Name> = r#in.iter().copied().collect();
+        let exit: BTreeSet<&Name> = out.iter().chain(defs[j].iter()).copied().collect();
+        let mut seen = HashSet::new();
+        for live in [entry, exit] {
+            for (k, &a) in live.iter().enumerate() {
+                for &b in live.iter().skip(k + 1) {
+                    let (Some(ra), Some(rb)) = (register(a), register(b)) else {
+                        continue;
+                    };
+                    if ra != rb || !seen.insert((a, b)) {
+                        continue;
+                    }
+                    let clobber = if precolored.get(a) == Some(&ra) && defs[j].contains(b) {
+                        Some((a, b))
+                    } else if precolored.get(b) == Some(&rb) && defs[j].contains(a) {
+                        Some((b, a))
+                    } else {
+                        None
+                    };
+                    violations.push(match clobber {
+                        Some((name, by)) => Violation::Clobbered {
+                            index: j,
+                            name: name.clone(),
+                            by: by.clone(),
+                            register: ra,
+                        },
+                        None => Violation::Conflict {
+                            index: j,
+                            a: a.clone(),
+                            b: b.clone(),
+                            register: ra,
+                        },
+                    });
+                }
+            }
+        }
+    }
+    violations
+}
+
+#[test]
+fn test_parse() {
+    assert_eq!(
+        parse("%1=r0, %argc=r1  %0=r2!"),
+        Ok((
+            HashMap::from([
+                (Name::Number(1), Register(0)),
+                (Name::Name("argc".to_string()), Register(1)),
+            ]),
+            HashMap::from([(Name::Number(0), Register(2))]),
+        )),
+    );
+    assert!(parse("%1").is_err());
+    assert!(parse("%1=x0").is_err());
+}
+
+#[test]
+fn test_check() {
+    use crate::builder::Builder;
+
+    // define i32 @main(i32 %0) {
+    //   %2 = add i32 %0, %0
+    //   %3 = add i32 %2, %0
+    //   ret i32 %3
+    // }
+    let mut b = Builder::new("main");
+    let n = b.param("i32");
+    let twice = b.binary("add", &n, &n);
+    let thrice = b.binary("add", &twice, &n);
+    b.ret(Some(&thrice));
+    let f = b.build();
+    let lives = ir::lva(&f);
+
+    let (assignment, precolored) = parse("%0=r0 %2=r1 %3=r0").unwrap();
+    assert_eq!(check(&f, &lives, &assignment, &precolored), vec![]);
+
+    let (assignment, precolored) = parse("%0=r0 %2=r0").unwrap();
+    assert_eq!(
+        check(&f, &lives, &assignment, &precolored),
+        vec![
+            Violation::Conflict {
+                index: 0,
+                a: Name::Number(0),
+                b: Name::Number(2),
+                register: Register(0),
+            },
+            Violation::Unassigned {
+                index: 1,
+                name: Name::Number(3),
+            },
+            Violation::Conflict {
+                index: 1,
+                a: Name::Number(0),
+                b: Name::Number(2),
+                register: Register(0),
+            },
+            Violation::Unassigned {
+                index: 2,
+                name: Name::Number(3),
+            },
+        ],
+    );
+
+    let (assignment, precolored) = parse("%0=r0! %2=r0 %3=r1").unwrap();
+    assert_eq!(
+        check(&f, &lives, &assignment, &precolored),
+        vec![
+            Violation::Clobbered {
+                index: 0,
+                name: Name::Number(0),
+                by: Name::Number(2),
+                register: Register(0),
+            },
+            Violation::Conflict {
+                index: 1,
+                a: Name::Number(0),
+                b: Name::Number(2),
+                register: Register(0),
+            },
+        ],
+    );
+}
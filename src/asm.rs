@@ -0,0 +1,536 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::alloc::{Assignment, Register};
+use crate::ir::{Function, Instruction, Name, Operand};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Location {
+    Register(Register),
+    Slot(usize),
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Location::Register(r) => write!(f, "{r}"),
+            Location::Slot(s) => write!(f, "[s{s}]"),
+        }
+    }
+}
+
+/// A line of assembly, linked to the instruction (in `ir::lva` order) it was
+/// selected from.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Line {
+    pub index: Option<usize>,
+    pub text: String,
+}
+
+/// Assigns every value a location: its register if it has one, otherwise a
+/// spill slot numbered in order of definition.
+pub fn locations(f: &Function, assignment: &Assignment) -> HashMap<Name, Location> {
+    let names = f.params.iter().map(|p| &p.name).chain(
+        f.basic_blocks
+            .iter()
+            .flat_map(|b| b.insts.iter().filter_map(|i| i.def.as_ref())),
+    );
+    let mut slots = 0;
+    names
+        .map(|n| match assignment.get(n) {
+            Some(&r) => (n.clone(), Location::Register(r)),
+            None => {
+                slots += 1;
+                (n.clone(), Location::Slot(slots - 1))
+            }
+        })
+        .collect()
+}
+
+/// Splits `s` at commas that are not nested within brackets.
+fn split_top(s: &str) -> Vec<&str> {
+    let mut depth = 0;
+    let mut start = 0;
+    let mut pieces = vec![];
+    for (i, c) in s.char_indices() {
+        match c {
+            '(' | '[' | '{' | '<' => depth += 1,
+            ')' | ']' | '}' | '>' => depth -= 1,
+            ',' if depth == 0 => {
+                pieces.push(s[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    pieces.push(s[start..].trim());
+    pieces
+}
+
+fn last_token(s: &str) -> String {
+    s.split_whitespace().last().unwrap_or_default().to_string()
+}
+
+/// Strips the `%x = ` prefix and any trailing `align` or metadata operands.
+fn body(s: &str) -> Vec<&str> {
+    let s = s.trim();
+    let s = match s.split_once(" = ") {
+        Some((def, rest)) if def.starts_with('%') => rest,
+        _ => s,
+    };
+    split_top(s)
+        .into_iter()
+        .filter(|p| !p.starts_with("align ") && !p.starts_with('!'))
+        .collect()
+}
+
+/// The callee and textual arguments of a call, e.g. `@printf` and `[@.str, %12]`.
+fn call(s: &str) -> Option<(String, Vec<String>)> {
+    let at = s.find('@')?;
+    let open = at + s[at..].find('(')?;
+    let mut depth = 0;
+    let close = s[open..].char_indices().find_map(|(i, c)| {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            _ => {}
+        }
+        (depth == 0).then_some(open + i)
+    })?;
+    let args = &s[open + 1..close];
+    let args = if args.trim().is_empty() {
+        vec![]
+    } else {
+        split_top(args).into_iter().map(last_token).collect()
+    };
+    Some((s[at..open].to_string(), args))
+}
+
+/// The text of each operand of `inst`, in the same order as `Instruction::uses`.
+fn operand_texts(opcode: usize, s: &str) -> Vec<String> {
+    let pieces = body(s);
+    match opcode {
+        // load
+        32 => pieces.iter().skip(1).map(|p| last_token(p)).collect(),
+        // casts
+        38..=50 => vec![last_token(
+            pieces[0].split(" to ").next().unwrap_or_default(),
+        )],
+        // phi
+        55 => pieces
+            .iter()
+            .filter_map(|p| p.find('[').map(|i| p[i + 1..].trim_end_matches(']')))
+            .map(|p| split_top(p)[0].to_string())
+            .collect(),
+        // call
+        56 => call(s)
+            .map(|(callee, mut args)| {
+                args.push(callee);
+                args
+            })
+            .unwrap_or_default(),
+        _ => pieces.iter().map(|p| last_token(p)).collect(),
+    }
+}
+
+fn mnemonic(opcode: usize) -> Option<&'static str> {
+    Some(match opcode {
+        13 => "add",
+        15 => "sub",
+        17 => "mul",
+        19 => "udiv",
+        20 => "sdiv",
+        22 => "urem",
+        23 => "srem",
+        25 => "shl",
+        26 => "lshr",
+        27 => "ashr",
+        28 => "and",
+        29 => "or",
+        30 => "xor",
+        _ => return None,
+    })
+}
+
+struct Selector<'a> {
+    locations: &'a HashMap<Name, Location>,
+    lines: Vec<Line>,
+}
+
+impl Selector<'_> {
+    fn location(&self, n: &Name) -> String {
+        match self.locations.get(n) {
+            Some(l) => l.to_string(),
+            None => format!("{n:?}"),
+        }
+    }
+
+    fn value(&self, o: &Operand, text: Option<&String>) -> String {
+        match (&o.name, o.constant) {
+            (Some(n), false) => self.location(n),
+            _ => text.cloned().unwrap_or_else(|| "?".to_string()),
+        }
+    }
+
+    fn emit(&mut self, index: usize, text: String) {
+        self.lines.push(Line {
+            index: Some(index),
+            text: format!("    {text}"),
+        });
+    }
+
+    /// The copies into the phis of `to` along the edge from `from`, with the
+    /// index of each phi in `ir::lva` order.
+    fn copies(
+        &self,
+        f: &Function,
+        indices: &[usize],
+        from: &Name,
+        to: &Name,
+    ) -> Vec<(usize, String)> {
+        let Some((s, &start)) = f
+            .basic_blocks
+            .iter()
+            .zip(indices)
+            .find(|(s, _)| s.name == *to)
+        else {
+            return vec![];
+        };
+        let mut copies = vec![];
+        for (k, phi) in s.insts.iter().take_while(|i| i.opcode == 55).enumerate() {
+            let texts = operand_texts(phi.opcode, &phi.string);
+            let incoming = phi
+                .blocks
+                .iter()
+                .flatten()
+                .zip(phi.uses.iter().enumerate())
+                .find(|(b, _)| *b == from);
+            if let (Some(def), Some((_, (u, o)))) = (&phi.def, incoming) {
+                copies.push((start + k, self.location(def), self.value(o, texts.get(u))));
+            }
+        }
+        sequence(copies)
+    }
+
+    fn inst(&mut self, index: usize, inst: &Instruction) {
+        let texts = operand_texts(inst.opcode, &inst.string);
+        let values: Vec<_> = inst
+            .uses
+            .iter()
+            .enumerate()
+            .map(|(k, o)| self.value(o, texts.get(k)))
+            .collect();
+        let def = inst.def.as_ref().map(|d| self.location(d));
+        let text = match (inst.opcode, def, &values[..]) {
+            (op, Some(d), [a, b]) if mnemonic(op).is_some() => {
+                format!("{} {d}, {a}, {b}", mnemonic(op).unwrap())
+            }
+            // alloca
+            (31, Some(d), _) => format!("frame {d}"),
+            // load
+            (32, Some(d), [a]) => format!("ld {d}, [{a}]"),
+            // store
+            (33, None, [v, a]) => format!("st [{a}], {v}"),
+            // casts
+            (38..=50, Some(d), [a]) => format!("mov {d}, {a}"),
+            // icmp
+            (53, Some(d), [a, b]) => {
+                let pred = inst.string.split_whitespace().nth(3).unwrap_or("?");
+                format!("cmp.{pred} {d}, {a}, {b}")
+            }
+            // phi, resolved by copies in each predecessor
+            (55, _, _) => return,
+            // call
+            (56, d, [args @ .., callee]) => {
+                let call = format!("call {callee}({})", args.join(", "));
+                match d {
+                    Some(d) => format!("{call} -> {d}"),
+                    None => call,
+                }
+            }
+            _ => format!("; {}", inst.string.trim()),
+        };
+        self.emit(index, text);
+    }
+}
+
+/// Orders the parallel copy `copies`, of `(index, destination, source)`, so
+/// that no destination is written before every copy from it has read it.
+/// Copies that form a cycle are resolved by swapping.
+fn sequence(copies: Vec<(usize, String, String)>) -> Vec<(usize, String)> {
+    let mut pending: Vec<_> = copies.into_iter().filter(|(_, d, s)| d != s).collect();
+    let mut lines = vec![];
+    while !pending.is_empty() {
+        let ready = pending
+            .iter()
+            .position(|(_, d, _)| pending.iter().all(|(_, _, s)| s != d));
+        match ready {
+            Some(k) => {
+                let (index, d, s) = pending.remove(k);
+                lines.push((index, format!("mov {d}, {s}")));
+            }
+            // every destination is still to be read, so they form cycles
+            None => {
+                let (index, d, s) = pending.remove(0);
+                lines.push((index, format!("xchg {d}, {s}")));
+                // the value of `d` is now in `s`
+                for (_, _, from) in &mut pending {
+                    if *from == d {
+                        *from = s.clone();
+                    }
+                }
+                pending.retain(|(_, d, s)| d != s);
+            }
+        }
+    }
+    lines
+}
+
+fn label(n: &Name) -> String {
+    match n {
+        Name::Name(n) => format!(".L{n}"),
+        Name::Number(n) => format!(".L{n}"),
+    }
+}
+
+/// The label of the block holding the copies for the edge from `from` to `to`.
+fn edge_label(from: &Name, to: &Name) -> String {
+    match to {
+        Name::Name(n) => format!("{}_{n}", label(from)),
+        Name::Number(n) => format!("{}_{n}", label(from)),
+    }
+}
+
+/// Selects toy three-address instructions for `f`, replacing values with the
+/// registers in `assignment`, or spill slots where there are none.
+pub fn select(f: &Function, assignment: &Assignment) -> Vec<Line> {
+    tracing::trace!("select {}", f.name);
+
+    let locations = locations(f, assignment);
+    let mut selector = Selector {
+        locations: &locations,
+        lines: vec![Line {
+            index: None,
+            text: format!("{}:", f.name),
+        }],
+    };
+
+    // index of each instruction in `ir::lva` order
+    let mut indices = vec![];
+    let mut j = 0;
+    for b in &f.basic_blocks {
+        indices.push(j);
+        j += b.insts.len() + 1;
+    }
+
+    for (b, &start) in f.basic_blocks.iter().zip(&indices) {
+        selector.lines.push(Line {
+            index: None,
+            text: format!("{}:", label(&b.name)),
+        });
+        for (k, inst) in b.insts.iter().enumerate() {
+            selector.inst(start + k, inst);
+        }
+
+        let end = start + b.insts.len();
+        let labels: Vec<_> = b
+            .term
+            .uses
+            .iter()
+            .filter(|o| o.ty.id == 8)
+            .filter_map(|o| o.name.as_ref())
+            .collect();
+
+        // a conditional branch can't copy for one edge before it, so the
+        // copies for each of its edges get a block of their own, even if both
+        // go to the same block, as a copy could overwrite the condition
+        let copies: Vec<_> = labels
+            .iter()
+            .map(|succ| selector.copies(f, &indices, &b.name, succ))
+            .collect();
+        let split = labels.len() > 1;
+        let targets: Vec<_> = labels
+            .iter()
+            .zip(&copies)
+            .map(|(succ, c)| {
+                if split && !c.is_empty() {
+                    edge_label(&b.name, succ)
+                } else {
+                    label(succ)
+                }
+            })
+            .collect();
+        if !split {
+            for (index, text) in copies.first().into_iter().flatten() {
+                selector.emit(*index, text.clone());
+            }
+        }
+
+        let texts = operand_texts(b.term.opcode, &b.term.string);
+        let text = match (b.term.opcode, &b.term.uses[..]) {
+            (1, []) => "ret".to_string(),
+            (1, [v]) => format!("ret {}", selector.value(v, texts.first())),
+            (2, [_]) => format!("jmp {}", targets[0]),
+            // operands of a conditional br are stored as [cond, false, true]
+            (2, [c, _, _]) => format!(
+                "bnz {}, {}, {}",
+                selector.value(c, texts.first()),
+                targets[1],
+                targets[0],
+            ),
+            _ => format!("; {}", b.term.string.trim()),
+        };
+        selector.emit(end, text);
+
+        if split {
+            let mut seen = vec![];
+            for ((succ, c), target) in labels.iter().zip(copies).zip(targets) {
+                if c.is_empty() || seen.contains(&target) {
+                    continue;
+                }
+                selector.lines.push(Line {
+                    index: None,
+                    text: format!("{target}:"),
+                });
+                for (index, text) in c {
+                    selector.emit(index, text);
+                }
+                selector.emit(end, format!("jmp {}", label(succ)));
+                seen.push(target);
+            }
+        }
+    }
+
+    selector.lines
+}
+
+#[test]
+fn test_operand_texts() {
+    assert_eq!(operand_texts(13, "  %9 = add nsw i32 %7, 1"), ["%7", "1"]);
+    assert_eq!(
+        operand_texts(32, "  %7 = load i32, ptr %4, align 4"),
+        ["%4"]
+    );
+    assert_eq!(
+        operand_texts(33, "  store i32 0, ptr %1, align 4"),
+        ["0", "%1"],
+    );
+    assert_eq!(
+        operand_texts(55, "  %6 = phi i32 [ %8, %5 ], [ 0, %2 ]"),
+        ["%8", "0"],
+    );
+    assert_eq!(
+        operand_texts(
+            56,
+            "  %7 = tail call i32 (ptr, ...) @printf(ptr noundef nonnull dereferenceable(1) @.str, i32 noundef %6)",
+        ),
+        ["@.str", "%6", "@printf"],
+    );
+    assert_eq!(operand_texts(1, "  ret i32 42"), ["42"]);
+}
+
+#[test]
+fn test_select() {
+    use crate::builder::{constant, Builder};
+
+    // for1.ll without the call to printf
+    let mut b = Builder::new("main");
+    let n = b.param("i32");
+    b.param("ptr");
+    let entry = b.current();
+    let (end, body) = (b.block(), b.block());
+    let gt = b.icmp("sgt", &n, &constant("i32", 0));
+    b.cond_br(&gt, body, end);
+
+    b.position(end);
+    b.ret(Some(&constant("i32", 0)));
+
+    b.position(body);
+    let i = b.phi("i32");
+    let next = b.binary("add", &i, &constant("i32", 1));
+    let eq = b.icmp("eq", &next, &n);
+    b.cond_br(&eq, end, body);
+    b.incoming(&i, &next, body);
+    b.incoming(&i, &constant("i32", 0), entry);
+    let f = b.build();
+
+    let (assignment, _) = crate::alloc::parse("%0=r0 %1=r2 %3=r1 %6=r1 %7=r1").unwrap();
+    assert_eq!(
+        select(&f, &assignment)
+            .into_iter()
+            .map(|l| (l.index, l.text))
+            .collect::<Vec<_>>(),
+        vec![
+            (None, "main:".to_string()),
+            (None, ".L2:".to_string()),
+            (Some(0), "    cmp.sgt r1, r0, 0".to_string()),
+            (Some(1), "    bnz r1, .L2_5, .L4".to_string()),
+            (None, ".L2_5:".to_string()),
+            (Some(3), "    mov r1, 0".to_string()),
+            (Some(1), "    jmp .L5".to_string()),
+            (None, ".L4:".to_string()),
+            (Some(2), "    ret 0".to_string()),
+            (None, ".L5:".to_string()),
+            (Some(4), "    add r1, r1, 1".to_string()),
+            (Some(5), "    cmp.eq [s0], r1, r0".to_string()),
+            (Some(6), "    bnz [s0], .L4, .L5".to_string()),
+        ],
+    );
+
+    // the copies of a branch with both edges to the same block are made
+    // after the condition is read, even into its register
+    let mut b = Builder::new("g");
+    let n = b.param("i32");
+    let entry = b.current();
+    let join = b.block();
+    let c = b.icmp("eq", &n, &constant("i32", 0));
+    b.cond_br(&c, join, join);
+
+    b.position(join);
+    let phi = b.phi("i32");
+    b.incoming(&phi, &constant("i32", 1), entry);
+    b.incoming(&phi, &constant("i32", 1), entry);
+    b.ret(Some(&phi));
+    let f = b.build();
+
+    let (assignment, _) = crate::alloc::parse("%0=r1 %2=r0 %4=r0").unwrap();
+    assert_eq!(
+        select(&f, &assignment)
+            .into_iter()
+            .map(|l| (l.index, l.text))
+            .collect::<Vec<_>>(),
+        vec![
+            (None, "g:".to_string()),
+            (None, ".L1:".to_string()),
+            (Some(0), "    cmp.eq r0, r1, 0".to_string()),
+            (Some(1), "    bnz r0, .L1_3, .L1_3".to_string()),
+            (None, ".L1_3:".to_string()),
+            (Some(2), "    mov r0, 1".to_string()),
+            (Some(1), "    jmp .L3".to_string()),
+            (None, ".L3:".to_string()),
+            (Some(3), "    ret r0".to_string()),
+        ],
+    );
+}
+
+#[test]
+fn test_sequence() {
+    let copies = |c: &[(&str, &str)]| {
+        let c = c
+            .iter()
+            .enumerate()
+            .map(|(i, (d, s))| (i, d.to_string(), s.to_string()))
+            .collect();
+        sequence(c).into_iter().map(|(_, t)| t).collect::<Vec<_>>()
+    };
+    assert_eq!(copies(&[("r0", "r0")]), Vec::<String>::new());
+    assert_eq!(
+        copies(&[("r0", "r1"), ("r1", "0")]),
+        ["mov r0, r1", "mov r1, 0"]
+    );
+    assert_eq!(copies(&[("r0", "r1"), ("r1", "r0")]), ["xchg r0, r1"]);
+    assert_eq!(
+        copies(&[("r0", "r1"), ("r1", "r2"), ("r2", "r0"), ("r3", "r0")]),
+        ["mov r3, r0", "xchg r0, r1", "xchg r1, r2"],
+    );
+}
@@ -0,0 +1,437 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::ir::{BasicBlock, Function, Instruction, Name, Operand, Param, Terminator, Type};
+
+/// The type named `name`, as far as the analyses care.
+pub fn type_of(name: &str) -> Type {
+    let id = match name {
+        "half" => 0,
+        "bfloat" => 1,
+        "float" => 2,
+        "double" => 3,
+        "void" => 7,
+        "label" => 8,
+        "metadata" => 9,
+        "ptr" => 15,
+        _ if name.starts_with('{') => 16,
+        _ if name.starts_with('[') => 17,
+        _ if name.starts_with('<') => 18,
+        _ => 13,
+    };
+    Type {
+        id,
+        name: name.to_string(),
+    }
+}
+
+/// A constant of type `ty`, like `constant("i32", 0)` or
+/// `constant("ptr", "@.str")`.
+pub fn constant(ty: &str, value: impl fmt::Display) -> Operand {
+    Operand {
+        constant: true,
+        name: None,
+        ty: type_of(ty),
+        value: Some(value.to_string()),
+    }
+}
+
+/// The function or global variable `@name`.
+#[allow(dead_code)]
+pub fn global(name: &str) -> Operand {
+    constant("ptr", format!("@{name}"))
+}
+
+fn opcode(keyword: &str) -> usize {
+    (1..=67)
+        .find(|&o| crate::text::opcode(o) == Some(keyword))
+        .unwrap_or_else(|| panic!("unknown opcode {keyword}"))
+}
+
+/// An instruction `keyword` whose `string` is yet to be printed.
+fn instruction(
+    keyword: &str,
+    def: Option<Name>,
+    uses: Vec<Operand>,
+    ty: Option<&str>,
+    predicate: Option<&str>,
+) -> Instruction {
+    Instruction {
+        opcode: opcode(keyword),
+        def,
+        uses,
+        blocks: (keyword == "phi").then(Vec::new),
+        string: String::new(),
+        ty: ty.map(str::to_string),
+        predicate: predicate.map(str::to_string),
+    }
+}
+
+/// The phi `def` of the values coming from the blocks in `incoming`, to add
+/// to a function that is already built, as transformations do.
+pub fn phi(def: Name, incoming: Vec<(Operand, Name)>) -> Instruction {
+    let (uses, blocks) = incoming.into_iter().unzip();
+    let mut phi = instruction("phi", Some(def), uses, None, None);
+    phi.blocks = Some(blocks);
+    phi.string = format!("  {phi}");
+    phi
+}
+
+/// A block of the function being built.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[allow(dead_code)]
+pub struct Block(usize);
+
+/// Builds a `Function` one block at a time. Values and blocks are numbered
+/// when the function is built, in the order they appear in it like LLVM
+/// does, and the `string` of every instruction is printed from its fields.
+///
+/// Until then they go by temporary numbers, so only operands made by the
+/// builder itself or by `constant` can be used.
+#[allow(dead_code)]
+pub struct Builder {
+    name: String,
+    params: Vec<Param>,
+    blocks: Vec<(Name, Vec<Instruction>, Option<Terminator>)>,
+    current: usize,
+    next: usize,
+    names: HashMap<usize, Name>,
+}
+
+#[allow(dead_code)]
+impl Builder {
+    /// A function named `name` with an empty entry block to append to.
+    pub fn new(name: &str) -> Self {
+        let mut builder = Builder {
+            name: name.to_string(),
+            params: vec![],
+            blocks: vec![],
+            current: 0,
+            next: 0,
+            names: HashMap::new(),
+        };
+        builder.block();
+        builder
+    }
+
+    fn temporary(&mut self) -> Name {
+        self.next += 1;
+        Name::Number(self.next - 1)
+    }
+
+    fn value(&mut self, ty: &str) -> Operand {
+        Operand {
+            constant: false,
+            name: Some(self.temporary()),
+            ty: type_of(ty),
+            value: None,
+        }
+    }
+
+    fn label(&self, block: Block) -> Operand {
+        Operand {
+            constant: false,
+            name: Some(self.blocks[block.0].0.clone()),
+            ty: type_of("label"),
+            value: None,
+        }
+    }
+
+    pub fn param(&mut self, ty: &str) -> Operand {
+        let value = self.value(ty);
+        self.params.push(Param {
+            name: value.name.clone().unwrap(),
+            ty: value.ty.clone(),
+        });
+        value
+    }
+
+    /// Names `value` instead of numbering it, e.g. `%argc`.
+    pub fn name(&mut self, value: &Operand, name: &str) {
+        let Some(Name::Number(t)) = value.name else {
+            panic!("{value:?} is not a value of the builder");
+        };
+        self.names.insert(t, Name::Name(name.to_string()));
+    }
+
+    /// Adds an empty block after the others.
+    pub fn block(&mut self) -> Block {
+        let name = self.temporary();
+        self.blocks.push((name, vec![], None));
+        Block(self.blocks.len() - 1)
+    }
+
+    /// The block that instructions are appended to.
+    pub fn current(&self) -> Block {
+        Block(self.current)
+    }
+
+    /// Appends the instructions that follow to `block`.
+    pub fn position(&mut self, block: Block) {
+        self.current = block.0;
+    }
+
+    fn push(
+        &mut self,
+        keyword: &str,
+        result: Option<&str>,
+        uses: Vec<Operand>,
+        ty: Option<&str>,
+        predicate: Option<&str>,
+    ) -> Option<Operand> {
+        let value = result.map(|ty| self.value(ty));
+        let (_, insts, term) = &mut self.blocks[self.current];
+        assert!(term.is_none(), "the block is already terminated");
+        let def = value.as_ref().and_then(|v| v.name.clone());
+        insts.push(instruction(keyword, def, uses, ty, predicate));
+        value
+    }
+
+    /// A binary operation like `add` or `xor`.
+    pub fn binary(&mut self, op: &str, a: &Operand, b: &Operand) -> Operand {
+        let ty = a.ty.name.clone();
+        self.push(op, Some(&ty), vec![a.clone(), b.clone()], None, None)
+            .unwrap()
+    }
+
+    pub fn icmp(&mut self, predicate: &str, a: &Operand, b: &Operand) -> Operand {
+        let uses = vec![a.clone(), b.clone()];
+        self.push("icmp", Some("i1"), uses, None, Some(predicate))
+            .unwrap()
+    }
+
+    pub fn alloca(&mut self, ty: &str) -> Operand {
+        self.array_alloca(ty, &constant("i32", 1))
+    }
+
+    /// An alloca of `count` elements of `ty`.
+    pub fn array_alloca(&mut self, ty: &str, count: &Operand) -> Operand {
+        self.push("alloca", Some("ptr"), vec![count.clone()], Some(ty), None)
+            .unwrap()
+    }
+
+    pub fn load(&mut self, ty: &str, ptr: &Operand) -> Operand {
+        self.push("load", Some(ty), vec![ptr.clone()], Some(ty), None)
+            .unwrap()
+    }
+
+    pub fn store(&mut self, value: &Operand, ptr: &Operand) {
+        self.push("store", None, vec![value.clone(), ptr.clone()], None, None);
+    }
+
+    pub fn gep(&mut self, ty: &str, ptr: &Operand, indices: &[&Operand]) -> Operand {
+        let uses = [ptr].iter().chain(indices).copied().cloned().collect();
+        self.push("getelementptr", Some("ptr"), uses, Some(ty), None)
+            .unwrap()
+    }
+
+    /// A cast like `sext` of `value` to `ty`.
+    pub fn cast(&mut self, op: &str, value: &Operand, ty: &str) -> Operand {
+        self.push(op, Some(ty), vec![value.clone()], Some(ty), None)
+            .unwrap()
+    }
+
+    pub fn select(&mut self, cond: &Operand, a: &Operand, b: &Operand) -> Operand {
+        let ty = a.ty.name.clone();
+        let uses = vec![cond.clone(), a.clone(), b.clone()];
+        self.push("select", Some(&ty), uses, None, None).unwrap()
+    }
+
+    /// A phi without incoming values, which are added by `incoming` as they
+    /// may well be defined after it.
+    pub fn phi(&mut self, ty: &str) -> Operand {
+        self.push("phi", Some(ty), vec![], None, None).unwrap()
+    }
+
+    /// Adds `value` coming from `block` to `phi`.
+    pub fn incoming(&mut self, phi: &Operand, value: &Operand, block: Block) {
+        let label = self.label(block).name.unwrap();
+        let phi = self
+            .blocks
+            .iter_mut()
+            .flat_map(|(_, insts, _)| insts)
+            .find(|i| i.opcode == 55 && i.def == phi.name)
+            .expect("not a phi of the builder");
+        phi.uses.push(value.clone());
+        phi.blocks.get_or_insert_with(Vec::new).push(label);
+    }
+
+    /// A call of `callee`, like `global("f")` or a pointer for an indirect
+    /// call, with `ty` as it is written in the call, e.g. `i32` or
+    /// `i32 (ptr, ...)`, or None if it returns void.
+    pub fn call(&mut self, ty: &str, callee: &Operand, args: &[&Operand]) -> Option<Operand> {
+        let result = ty.split(" (").next().filter(|&r| r != "void");
+        let uses = args.iter().chain([&callee]).copied().cloned().collect();
+        self.push("call", result, uses, Some(ty), None)
+    }
+
+    fn terminate(&mut self, keyword: &str, uses: Vec<Operand>) {
+        let (_, _, term) = &mut self.blocks[self.current];
+        assert!(term.is_none(), "the block is already terminated");
+        *term = Some(Terminator {
+            opcode: opcode(keyword),
+            def: None,
+            uses,
+            string: String::new(),
+        });
+    }
+
+    pub fn ret(&mut self, value: Option<&Operand>) {
+        self.terminate("ret", value.into_iter().cloned().collect());
+    }
+
+    pub fn br(&mut self, to: Block) {
+        let uses = vec![self.label(to)];
+        self.terminate("br", uses);
+    }
+
+    pub fn cond_br(&mut self, cond: &Operand, then: Block, otherwise: Block) {
+        // the operands of a conditional br are stored as [cond, false, true]
+        let uses = vec![cond.clone(), self.label(otherwise), self.label(then)];
+        self.terminate("br", uses);
+    }
+
+    pub fn switch(&mut self, value: &Operand, default: Block, cases: &[(&Operand, Block)]) {
+        let mut uses = vec![value.clone(), self.label(default)];
+        for &(case, to) in cases {
+            uses.extend([case.clone(), self.label(to)]);
+        }
+        self.terminate("switch", uses);
+    }
+
+    pub fn unreachable(&mut self) {
+        self.terminate("unreachable", vec![]);
+    }
+
+    /// Numbers the values and blocks that are not named, in order, and
+    /// prints every instruction.
+    ///
+    /// Panics if a block is not terminated.
+    pub fn build(mut self) -> Function {
+        let mut n = 0;
+        let mut number = |names: &mut HashMap<usize, Name>, name: &Name| {
+            if let Name::Number(t) = name {
+                names.entry(*t).or_insert_with(|| {
+                    n += 1;
+                    Name::Number(n - 1)
+                });
+            }
+        };
+        for p in &self.params {
+            number(&mut self.names, &p.name);
+        }
+        for (name, insts, _) in &self.blocks {
+            number(&mut self.names, name);
+            for def in insts.iter().filter_map(|i| i.def.as_ref()) {
+                number(&mut self.names, def);
+            }
+        }
+
+        let names = self.names;
+        let rename = |name: &mut Name| {
+            if let Name::Number(t) = name {
+                *name = names[&*t].clone();
+            }
+        };
+        let rename_uses = |uses: &mut Vec<Operand>| {
+            for name in uses.iter_mut().filter_map(|o| o.name.as_mut()) {
+                rename(name);
+            }
+        };
+        let mut params = self.params;
+        for p in &mut params {
+            rename(&mut p.name);
+        }
+        let basic_blocks = self
+            .blocks
+            .into_iter()
+            .map(|(mut name, mut insts, term)| {
+                rename(&mut name);
+                let Some(mut term) = term else {
+                    panic!("block {name:?} has no terminator");
+                };
+                for i in &mut insts {
+                    i.def.iter_mut().for_each(rename);
+                    rename_uses(&mut i.uses);
+                    i.blocks.iter_mut().flatten().for_each(rename);
+                    i.string = format!("  {i}");
+                }
+                rename_uses(&mut term.uses);
+                term.string = format!("  {term}");
+                BasicBlock { name, insts, term }
+            })
+            .collect();
+        Function {
+            name: self.name,
+            params,
+            basic_blocks,
+        }
+    }
+}
+
+#[test]
+fn test_build() {
+    let mut b = Builder::new("f");
+    let n = b.param("i32");
+    let p = b.param("ptr");
+    b.name(&p, "p");
+    let entry = b.current();
+    // blocks are numbered in order, not in the order they are filled in
+    let (loop_, exit, other) = (b.block(), b.block(), b.block());
+    let c = b.icmp("sgt", &n, &constant("i32", 0));
+    b.cond_br(&c, loop_, exit);
+
+    b.position(exit);
+    let ext = b.cast("sext", &n, "i64");
+    let elem = b.gep("i8", &p, &[&ext]);
+    assert_eq!(b.call("void", &global("use"), &[&elem]), None);
+    b.switch(&n, other, &[(&constant("i32", 1), exit)]);
+
+    b.position(loop_);
+    let i = b.phi("i32");
+    let next = b.binary("add", &i, &constant("i32", 1));
+    let done = b.icmp("eq", &next, &n);
+    b.incoming(&i, &constant("i32", 0), entry);
+    b.incoming(&i, &next, loop_);
+    b.cond_br(&done, exit, loop_);
+
+    b.position(other);
+    let max = b.select(&c, &n, &constant("i32", 0));
+    b.ret(Some(&max));
+
+    let f = b.build();
+    assert_eq!(
+        f.to_string(),
+        "define i32 @f(i32 %0, ptr %p) {
+  %2 = icmp sgt i32 %0, 0
+  br i1 %2, label %3, label %7
+
+3:
+  %4 = phi i32 [ 0, %1 ], [ %5, %3 ]
+  %5 = add i32 %4, 1
+  %6 = icmp eq i32 %5, %0
+  br i1 %6, label %7, label %3
+
+7:
+  %8 = sext i32 %0 to i64
+  %9 = getelementptr i8, ptr %p, i64 %8
+  call void @use(ptr %9)
+  switch i32 %0, label %10 [ i32 1, label %7 ]
+
+10:
+  %11 = select i1 %2, i32 %0, i32 0
+  ret i32 %11
+}
+",
+    );
+    assert_eq!(
+        f.basic_blocks[1].insts[0].string,
+        "  %4 = phi i32 [ 0, %1 ], [ %5, %3 ]"
+    );
+    assert_eq!(
+        f.basic_blocks[1].insts[0].blocks,
+        Some(vec![Name::Number(1), Name::Number(3)]),
+    );
+    assert_eq!(f.basic_blocks[0].term.uses[1].name, Some(Name::Number(7)));
+    assert_eq!(f.params[1].name, Name::Name("p".to_string()));
+}
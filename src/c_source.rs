@@ -0,0 +1,40 @@
+#![allow(non_snake_case)]
+
+use dioxus::prelude::*;
+
+/// A read-only view of the C source of the module. `highlight` holds the
+/// 1-based lines to highlight, and `onhover` gets the line under the mouse.
+#[component]
+pub fn CSource(
+    text: String,
+    highlight: Vec<usize>,
+    onhover: EventHandler<Option<usize>>,
+    onclose: EventHandler<()>,
+) -> Element {
+    rsx! {
+        div { class: "flex flex-col h-full",
+            div { class: "flex flex-none items-center",
+                span { class: "flex-1 ml-1", "C source (read-only)" }
+                button {
+                    class: "bg-zinc-100 dark:bg-zinc-800 h-12 px-2",
+                    onclick: move |_| onclose(()),
+                    "×"
+                }
+            }
+            div {
+                class: "flex-1 overflow-scroll bg-white dark:bg-zinc-900 font-mono text-sm whitespace-pre",
+                onmouseleave: move |_| onhover(None),
+                for (n , line) in text.lines().enumerate().map(|(n, l)| (n + 1, l.to_string())) {
+                    div {
+                        class: if highlight.contains(&n) { "flex bg-yellow-100 dark:bg-yellow-900" } else { "flex" },
+                        onmouseenter: move |_| onhover(Some(n)),
+                        span { class: "flex-none w-8 pr-2 text-right text-zinc-400 select-none",
+                            "{n}"
+                        }
+                        span { "{line}" }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,269 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+
+use petgraph::graph::DiGraph;
+
+use crate::dot::escape;
+use crate::ir::Function;
+
+/// The function called by the `call` or `invoke` instruction `inst`, or
+/// None if it is not a direct call.
+pub fn callee(inst: &str) -> Option<&str> {
+    let code = inst.split(';').next()?;
+    let (_, rest) = code
+        .split_once("call ")
+        .or_else(|| code.split_once("invoke "))?;
+    // the callee is the first global that is called, like @f(...), whereas
+    // the arguments, e.g. @.str, are followed by a comma or paren
+    let mut rest = rest;
+    while let Some(at) = rest.find('@') {
+        rest = &rest[at + 1..];
+        let end = if let Some(quoted) = rest.strip_prefix('"') {
+            quoted.find('"')? + 2
+        } else {
+            rest.find(|c: char| !(c.is_alphanumeric() || matches!(c, '.' | '_' | '$' | '-')))
+                .unwrap_or(rest.len())
+        };
+        if rest[end..].starts_with('(') {
+            return Some(rest[..end].trim_matches('"'));
+        }
+    }
+    None
+}
+
+/// The calls between the functions of a module.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CallGraph {
+    /// The defined functions, in module order, followed by the external
+    /// functions they call, in the order they are first called.
+    pub names: Vec<String>,
+    /// The number of defined functions, i.e. where the external ones start.
+    pub defined: usize,
+    /// The number of calls from one function to another.
+    pub calls: BTreeMap<(usize, usize), usize>,
+    /// The functions with calls through a pointer.
+    pub indirect: BTreeSet<usize>,
+    /// The strongly connected components of more than one function.
+    pub sccs: Vec<Vec<usize>>,
+    /// The functions that may call themselves, directly or not.
+    pub recursive: BTreeSet<usize>,
+}
+
+impl CallGraph {
+    pub fn external(&self, f: usize) -> bool {
+        f >= self.defined
+    }
+}
+
+/// Builds the call graph of `functions` from their `call` and `invoke`
+/// instructions. Calls to debug intrinsics like `@llvm.dbg.declare` are left
+/// out, as they only carry metadata.
+pub fn build(functions: &[Function]) -> CallGraph {
+    let mut names: Vec<String> = functions.iter().map(|f| f.name.clone()).collect();
+    let mut calls = BTreeMap::new();
+    let mut indirect = BTreeSet::new();
+    for (i, f) in functions.iter().enumerate() {
+        let insts = f.basic_blocks.iter().flat_map(|b| {
+            b.insts
+                .iter()
+                .filter(|i| i.opcode == 56 /* call */)
+                .map(|i| &i.string)
+                .chain(Some(&b.term.string).filter(|_| b.term.opcode == 5 /* invoke */))
+        });
+        for inst in insts {
+            let Some(callee) = callee(inst) else {
+                indirect.insert(i);
+                continue;
+            };
+            if callee.starts_with("llvm.dbg.") {
+                continue;
+            }
+            let j = match names.iter().position(|n| n == callee) {
+                Some(j) => j,
+                None => {
+                    names.push(callee.to_string());
+                    names.len() - 1
+                }
+            };
+            *calls.entry((i, j)).or_insert(0) += 1;
+        }
+    }
+
+    let mut g = DiGraph::<(), ()>::new();
+    let nodes: Vec<_> = names.iter().map(|_| g.add_node(())).collect();
+    for &(i, j) in calls.keys() {
+        g.add_edge(nodes[i], nodes[j], ());
+    }
+    let mut sccs: Vec<Vec<usize>> = petgraph::algo::tarjan_scc(&g)
+        .into_iter()
+        .map(|scc| {
+            let mut scc: Vec<_> = scc.into_iter().map(|n| n.index()).collect();
+            scc.sort();
+            scc
+        })
+        .filter(|scc| scc.len() > 1)
+        .collect();
+    sccs.sort();
+    let recursive = sccs
+        .iter()
+        .flatten()
+        .copied()
+        .chain(calls.keys().filter(|(i, j)| i == j).map(|&(i, _)| i))
+        .collect();
+
+    CallGraph {
+        names,
+        defined: functions.len(),
+        calls,
+        indirect,
+        sccs,
+        recursive,
+    }
+}
+
+/// Renders `graph` with a node per function. Nodes of defined functions get
+/// the id `function{i}` after their index in the module, external ones are
+/// dashed, recursive ones red, and every SCC is boxed in a cluster.
+pub fn dot(graph: &CallGraph) -> String {
+    let node = |i: usize| {
+        let mut attrs = format!("label=\"@{}\"", escape(&graph.names[i]));
+        if graph.external(i) {
+            attrs += " color=\"gray\" fontcolor=\"gray\" style=\"dashed\"";
+        } else {
+            write!(attrs, " id=\"function{i}\"").unwrap();
+        }
+        if graph.recursive.contains(&i) {
+            attrs += " color=\"red\" penwidth=2";
+        }
+        format!("{i} [{attrs}]")
+    };
+
+    let mut dot = String::new();
+    writeln!(dot, "digraph {{").unwrap();
+    writeln!(dot, "    bgcolor=\"transparent\"").unwrap();
+    writeln!(dot, "    node [shape=box fontname=\"monospace\"]").unwrap();
+    for (k, scc) in graph.sccs.iter().enumerate() {
+        writeln!(dot, "    subgraph cluster_scc{k} {{").unwrap();
+        writeln!(dot, "        label=\"SCC\" style=\"dashed\" color=\"red\"").unwrap();
+        for &i in scc {
+            writeln!(dot, "        {}", node(i)).unwrap();
+        }
+        writeln!(dot, "    }}").unwrap();
+    }
+    for i in 0..graph.names.len() {
+        if !graph.sccs.iter().flatten().any(|&j| j == i) {
+            writeln!(dot, "    {}", node(i)).unwrap();
+        }
+    }
+    if !graph.indirect.is_empty() {
+        writeln!(
+            dot,
+            "    indirect [label=\"(indirect)\" color=\"gray\" fontcolor=\"gray\" style=\"dashed\"]"
+        )
+        .unwrap();
+    }
+    for (&(i, j), &n) in &graph.calls {
+        if n > 1 {
+            writeln!(dot, "    {i} -> {j} [label=\"{n}\"]").unwrap();
+        } else {
+            writeln!(dot, "    {i} -> {j}").unwrap();
+        }
+    }
+    for &i in &graph.indirect {
+        writeln!(dot, "    {i} -> indirect [style=\"dashed\"]").unwrap();
+    }
+    writeln!(dot, "}}").unwrap();
+    dot
+}
+
+#[test]
+fn test_callgraph() {
+    use crate::builder::{constant, global, Builder};
+
+    assert_eq!(
+        callee("  %36 = call i32 (ptr, ...) @printf(ptr noundef @.str, i32 noundef %35)"),
+        Some("printf"),
+    );
+    assert_eq!(
+        callee("  call void @llvm.memset.p0.i64(ptr align 1 %7, i8 0, i64 1001, i1 false)"),
+        Some("llvm.memset.p0.i64"),
+    );
+    assert_eq!(callee(r#"  call void @"a b"(i32 1)"#), Some("a b"));
+    assert_eq!(callee("  %3 = call i32 %2(ptr @.str)"), None);
+    assert_eq!(
+        callee("  %4 = invoke i32 @f(i32 %3) to label %5 unwind label %6"),
+        Some("f"),
+    );
+
+    // a function calling each callee with an i32, still to be terminated
+    let function = |name: &str, calls: &[(&str, i32)]| {
+        let mut b = Builder::new(name);
+        for &(callee, arg) in calls {
+            b.call("void", &global(callee), &[&constant("i32", arg)]);
+        }
+        b
+    };
+    let mut main = Builder::new("main");
+    let fp = main.param("ptr");
+    main.call("void", &global("even"), &[&constant("i32", 3)]);
+    main.call("void", &global("printf"), &[&global(".str")]);
+    main.call("void", &fp, &[]);
+    let mut fact = function("fact", &[("fact", 1)]);
+    let (slot, variable) = (constant("metadata", "ptr null"), constant("metadata", "!1"));
+    fact.call("void", &global("llvm.dbg.declare"), &[&slot, &variable]);
+    let functions: Vec<_> = [
+        main,
+        function("even", &[("odd", 2)]),
+        function("odd", &[("even", 1), ("odd", 0), ("odd", 0)]),
+        fact,
+    ]
+    .into_iter()
+    .map(|mut b| {
+        b.ret(None);
+        b.build()
+    })
+    .collect();
+    let graph = build(&functions);
+    assert_eq!(graph.names, vec!["main", "even", "odd", "fact", "printf"]);
+    assert_eq!(graph.defined, 4);
+    assert!(graph.external(4) && !graph.external(3));
+    assert_eq!(
+        graph.calls,
+        BTreeMap::from([
+            ((0, 1), 1),
+            ((0, 4), 1),
+            ((1, 2), 1),
+            ((2, 1), 1),
+            ((2, 2), 2),
+            ((3, 3), 1),
+        ]),
+    );
+    assert_eq!(graph.indirect, BTreeSet::from([0]));
+    assert_eq!(graph.sccs, vec![vec![1, 2]]);
+    assert_eq!(graph.recursive, BTreeSet::from([1, 2, 3]));
+
+    assert_eq!(
+        dot(&graph),
+        r#"digraph {
+    bgcolor="transparent"
+    node [shape=box fontname="monospace"]
+    subgraph cluster_scc0 {
+        label="SCC" style="dashed" color="red"
+        1 [label="@even" id="function1" color="red" penwidth=2]
+        2 [label="@odd" id="function2" color="red" penwidth=2]
+    }
+    0 [label="@main" id="function0"]
+    3 [label="@fact" id="function3" color="red" penwidth=2]
+    4 [label="@printf" color="gray" fontcolor="gray" style="dashed"]
+    indirect [label="(indirect)" color="gray" fontcolor="gray" style="dashed"]
+    0 -> 1
+    0 -> 4
+    1 -> 2
+    2 -> 1
+    2 -> 2 [label="2"]
+    3 -> 3
+    0 -> indirect [style="dashed"]
+}
+"#,
+    );
+}
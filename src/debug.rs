@@ -0,0 +1,317 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use crate::ir::Name;
+use crate::source;
+use crate::text::local;
+
+/// A variable of the original source, declared by `llvm.dbg.declare` or
+/// `llvm.dbg.value`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Variable {
+    pub name: String,
+    pub line: Option<usize>,
+}
+
+/// The source line and column of a `!dbg` location.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Location {
+    pub line: usize,
+    pub column: usize,
+}
+
+/// The debug info of a module compiled with `-g`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Info {
+    /// The source file of the compile unit.
+    pub file: Option<String>,
+    /// The variables of every function, by the value that holds them.
+    pub variables: HashMap<String, HashMap<Name, Variable>>,
+    /// The location of every instruction of every function, in `ir::lva`
+    /// order.
+    pub locations: HashMap<String, Vec<Option<Location>>>,
+}
+
+impl Info {
+    /// The names of the variables of `function`, by the value that holds them.
+    pub fn names(&self, function: &str) -> HashMap<Name, String> {
+        self.variables
+            .get(function)
+            .into_iter()
+            .flatten()
+            .map(|(value, v)| (value.clone(), v.name.clone()))
+            .collect()
+    }
+
+    /// The source line of the instruction on `line` of the module text,
+    /// whose functions span `lines`.
+    pub fn source_line(
+        &self,
+        lines: &HashMap<String, source::Lines>,
+        line: usize,
+    ) -> Option<usize> {
+        lines.iter().find_map(|(function, l)| {
+            let row = l.row(line)?;
+            let (first, last) = l.inst(row)?;
+            if !(first..=last).contains(&line) {
+                return None;
+            }
+            Some(self.locations.get(function)?.get(row)?.as_ref()?.line)
+        })
+    }
+
+    /// The first and last line in the module text of every instruction from
+    /// source `line`.
+    pub fn module_lines(
+        &self,
+        lines: &HashMap<String, source::Lines>,
+        line: usize,
+    ) -> Vec<(usize, usize)> {
+        let mut ranges: Vec<_> = self
+            .locations
+            .iter()
+            .filter_map(|(function, locations)| Some((lines.get(function)?, locations)))
+            .flat_map(|(l, locations)| {
+                locations
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, location)| location.is_some_and(|l| l.line == line))
+                    .filter_map(|(row, _)| l.inst(row))
+            })
+            .collect();
+        ranges.sort();
+        ranges
+    }
+}
+
+/// `name` followed by the variable it holds, like `%5 (n)`.
+pub fn label(name: &Name, names: &HashMap<Name, String>) -> String {
+    match names.get(name) {
+        Some(v) => format!("{} ({v})", local(name)),
+        None => local(name),
+    }
+}
+
+/// The sorted labels of `set`, like `{%3, %5 (n)}`.
+pub fn labels(set: &HashSet<Name>, names: &HashMap<Name, String>) -> String {
+    let labels: Vec<_> = set
+        .iter()
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .map(|n| label(n, names))
+        .collect();
+    format!("{{{}}}", labels.join(", "))
+}
+
+/// Splits the fields of a specialized metadata node at top-level commas.
+fn fields(s: &str) -> HashMap<&str, &str> {
+    let (mut fields, mut start, mut depth, mut string) = (HashMap::new(), 0, 0, false);
+    for (i, c) in s.char_indices().chain([(s.len(), ',')]) {
+        match c {
+            '"' => string = !string,
+            '(' | '{' if !string => depth += 1,
+            ')' | '}' if !string => depth -= 1,
+            ',' if !string && depth == 0 => {
+                if let Some((k, v)) = s[start..i].split_once(':') {
+                    fields.insert(k.trim(), v.trim());
+                }
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    fields
+}
+
+/// The numbered specialized metadata nodes in `text`, like
+/// `!7 = !DILocation(line: 3, column: 5, scope: !4)`, as their kind and
+/// fields.
+fn nodes(text: &str) -> HashMap<&str, (&str, HashMap<&str, &str>)> {
+    text.lines()
+        .filter_map(|line| {
+            let (id, node) = line.split_once(" = ")?;
+            let node = node.trim().strip_prefix("distinct ").unwrap_or(node.trim());
+            let (kind, rest) = node.strip_prefix('!')?.split_once('(')?;
+            let rest = rest.strip_suffix(')')?;
+            Some((id.trim(), (kind, fields(rest))))
+        })
+        .collect()
+}
+
+fn unquote(s: &str) -> &str {
+    s.trim_matches('"')
+}
+
+/// Reads the debug info of the module in `text`.
+pub fn parse(text: &str) -> Info {
+    let nodes = nodes(text);
+    let file = |id: &str| match nodes.get(id) {
+        Some(("DIFile", f)) => f.get("filename").map(|n| unquote(n).to_string()),
+        _ => None,
+    };
+    let file = nodes
+        .values()
+        .find(|(kind, _)| *kind == "DICompileUnit")
+        .and_then(|(_, f)| file(f.get("file")?))
+        .or_else(|| {
+            nodes
+                .values()
+                .find(|(kind, _)| *kind == "DIFile")
+                .and_then(|(_, f)| Some(unquote(f.get("filename")?).to_string()))
+        });
+
+    let lines: Vec<_> = text.lines().collect();
+    let mut info = Info {
+        file,
+        ..Default::default()
+    };
+    for (function, l) in source::functions(text) {
+        let mut variables = HashMap::new();
+        let mut locations = vec![];
+        for (&start, &end) in l.insts.iter().zip(&l.ends) {
+            let inst = lines[start..=end].join(" ");
+            let code = inst.split(';').next().unwrap_or_default();
+
+            let location = code.rsplit_once("!dbg ").and_then(|(_, id)| {
+                let id = id.split(|c: char| c == ',' || c.is_whitespace()).next()?;
+                match nodes.get(id) {
+                    Some(("DILocation", f)) => Some(Location {
+                        line: f.get("line")?.parse().ok()?,
+                        column: f.get("column").and_then(|c| c.parse().ok()).unwrap_or(0),
+                    }),
+                    _ => None,
+                }
+            });
+            locations.push(location);
+
+            // call void @llvm.dbg.declare(metadata ptr %5, metadata !20, metadata !DIExpression())
+            let Some((_, args)) = code
+                .split_once("@llvm.dbg.declare(")
+                .or_else(|| code.split_once("@llvm.dbg.value("))
+            else {
+                continue;
+            };
+            let mut args = args.split("metadata ").skip(1);
+            let (Some(value), Some(variable)) = (args.next(), args.next()) else {
+                continue;
+            };
+            let value = value.trim().trim_end_matches(',');
+            let Some(value) = value
+                .rsplit_once(' ')
+                .map_or(value, |(_, v)| v)
+                .strip_prefix('%')
+                .and_then(|v| v.parse::<Name>().ok())
+            else {
+                continue;
+            };
+            let variable = variable.trim().trim_end_matches(',');
+            if let Some(("DILocalVariable", f)) = nodes.get(variable) {
+                if let Some(name) = f.get("name") {
+                    variables.insert(
+                        value,
+                        Variable {
+                            name: unquote(name).to_string(),
+                            line: f.get("line").and_then(|l| l.parse().ok()),
+                        },
+                    );
+                }
+            }
+        }
+        info.variables.insert(function.clone(), variables);
+        info.locations.insert(function, locations);
+    }
+    info
+}
+
+#[test]
+fn test_parse() {
+    let text = r#"source_filename = "ret.c"
+
+define i32 @main(i32 noundef %0) #0 !dbg !10 {
+  %2 = alloca i32, align 4
+  store i32 %0, ptr %2, align 4
+  call void @llvm.dbg.declare(metadata ptr %2, metadata !16, metadata !DIExpression()), !dbg !17
+  %3 = load i32, ptr %2, align 4, !dbg !18
+  ret i32 %3, !dbg !19
+}
+
+!llvm.dbg.cu = !{!0}
+
+!0 = distinct !DICompileUnit(language: DW_LANG_C11, file: !1, producer: "clang, version 17", isOptimized: false)
+!1 = !DIFile(filename: "ret.c", directory: "/tmp")
+!10 = distinct !DISubprogram(name: "main", scope: !1, file: !1, line: 1, type: !11, unit: !0)
+!16 = !DILocalVariable(name: "argc", arg: 1, scope: !10, file: !1, line: 1, type: !14)
+!17 = !DILocation(line: 1, column: 14, scope: !10)
+!18 = !DILocation(line: 2, column: 10, scope: !10)
+!19 = !DILocation(line: 2, column: 3, scope: !10)
+"#;
+    let info = parse(text);
+    assert_eq!(info.file.as_deref(), Some("ret.c"));
+    assert_eq!(
+        info.variables["main"],
+        HashMap::from([(
+            Name::Number(2),
+            Variable {
+                name: "argc".to_string(),
+                line: Some(1),
+            },
+        )]),
+    );
+    let location = |line, column| Some(Location { line, column });
+    assert_eq!(
+        info.locations["main"],
+        vec![None, None, location(1, 14), location(2, 10), location(2, 3),],
+    );
+
+    let names = info.names("main");
+    assert_eq!(label(&Name::Number(2), &names), "%2 (argc)");
+    assert_eq!(
+        labels(&HashSet::from([Name::Number(3), Name::Number(2)]), &names),
+        "{%2 (argc), %3}",
+    );
+    let lines = source::functions(text);
+    // %3 = load i32, ptr %2, align 4, !dbg !18
+    assert_eq!(info.source_line(&lines, 6), Some(2));
+    assert_eq!(info.source_line(&lines, 3), None);
+    assert_eq!(info.source_line(&lines, 2), None);
+    assert_eq!(info.module_lines(&lines, 2), vec![(6, 6), (7, 7)]);
+    assert_eq!(info.module_lines(&lines, 3), vec![]);
+
+    assert_eq!(parse("define void @f() {\n  ret void\n}\n").file, None);
+}
+
+#[test]
+fn test_parse_example() {
+    let text = include_str!("../examples/ll/fib.g.ll");
+    let info = parse(text);
+    assert_eq!(info.file.as_deref(), Some("fib.c"));
+    let names = info.names("main");
+    assert_eq!(
+        names,
+        HashMap::from([
+            (Name::Number(2), "i".to_string()),
+            (Name::Number(3), "n".to_string()),
+            (Name::Number(4), "t1".to_string()),
+            (Name::Number(5), "t2".to_string()),
+            (Name::Number(6), "nextTerm".to_string()),
+        ]),
+    );
+    assert_eq!(
+        labels(
+            &HashSet::from([Name::Number(7), Name::Number(6), Name::Number(2)]),
+            &names,
+        ),
+        "{%2 (i), %6 (nextTerm), %7}",
+    );
+
+    let lines = source::functions(text);
+    // %9 = add nsw i32 %7, %8, !dbg !26
+    assert_eq!(info.source_line(&lines, 28), Some(12));
+    // ret i32 0, !dbg !57
+    assert_eq!(info.source_line(&lines, 64), Some(29));
+    assert_eq!(info.source_line(&lines, 11), None);
+    // nextTerm = t1 + t2; in the loop
+    assert_eq!(
+        info.module_lines(&lines, 26),
+        vec![(51, 51), (52, 52), (53, 53), (54, 54)],
+    );
+}
@@ -0,0 +1,312 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use petgraph::algo::dominators;
+use petgraph::visit::Dfs;
+use petgraph::Direction;
+
+use crate::ir::{self, Function, Instruction, Name, Operand};
+
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+/// The output tab that explains a diagnostic, numbered by its position.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Tab {
+    Cfg = 0,
+    Lva = 1,
+}
+
+impl Tab {
+    pub fn name(self) -> &'static str {
+        match self {
+            Tab::Cfg => "CFG",
+            Tab::Lva => "LVA",
+        }
+    }
+}
+
+/// What a diagnostic is about: a block or an instruction, in `ir::lva` order.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum At {
+    Block(usize),
+    Inst(usize),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Diagnostic {
+    pub at: At,
+    pub severity: Severity,
+    pub message: String,
+    pub tab: Tab,
+}
+
+fn values(uses: &[Operand]) -> impl Iterator<Item = &Name> {
+    uses.iter()
+        .filter(|o| !o.constant && o.ty.id != 8)
+        .filter_map(|o| o.name.as_ref())
+}
+
+/// Problems found by ullvm's own analyses of `f`: possibly uninitialized
+/// uses, unreachable blocks, dead values, uses not dominated by their def
+/// and phis that do not match the predecessors of their block.
+pub fn diagnostics(f: &Function) -> Vec<Diagnostic> {
+    let mut diagnostics = vec![];
+    let Some(first) = f.basic_blocks.first() else {
+        return diagnostics;
+    };
+    let mut push = |at, severity, tab, message| {
+        diagnostics.push(Diagnostic {
+            at,
+            severity,
+            message,
+            tab,
+        })
+    };
+
+    let (blocks, cfg) = ir::cfg(f);
+    let node = |b: usize| blocks[&f.basic_blocks[b].name].1;
+    let entry = node(0);
+    let params: HashSet<&Name> = f.params.iter().map(|p| &p.name).collect();
+
+    // the block and instruction of every row, None for terminators
+    let rows: Vec<(usize, Option<&Instruction>)> = f
+        .basic_blocks
+        .iter()
+        .enumerate()
+        .flat_map(|(b, block)| {
+            block
+                .insts
+                .iter()
+                .map(move |i| (b, Some(i)))
+                .chain([(b, None)])
+        })
+        .collect();
+    let uses = ir::r#use(f);
+    let defs: HashMap<&Name, usize> = ir::def(f)
+        .iter()
+        .enumerate()
+        .flat_map(|(row, d)| d.iter().map(move |&n| (n, row)))
+        .collect();
+
+    let uninitialized: BTreeSet<&Name> = ir::lva(f)
+        .first()
+        .map(|(r#in, _, _)| {
+            r#in.iter()
+                .filter(|n| !params.contains(*n))
+                .copied()
+                .collect()
+        })
+        .unwrap_or_default();
+    for name in uninitialized {
+        for (row, _) in uses.iter().enumerate().filter(|(_, u)| u.contains(name)) {
+            push(
+                At::Inst(row),
+                Severity::Warning,
+                Tab::Lva,
+                format!(
+                    "{name:?} may be used uninitialized: it is live-in at {:?} but is not a parameter",
+                    first.name,
+                ),
+            );
+        }
+    }
+
+    // the remaining checks need every edge of the CFG, which it only has if
+    // every terminator is a br or ret
+    let complete = f
+        .basic_blocks
+        .iter()
+        .all(|b| matches!(b.term.opcode, 1 | 2));
+
+    let mut reachable = HashSet::new();
+    let mut dfs = Dfs::new(&cfg, entry);
+    while let Some(n) = dfs.next(&cfg) {
+        reachable.insert(n);
+    }
+    if complete {
+        for (b, block) in f.basic_blocks.iter().enumerate() {
+            if !reachable.contains(&node(b)) {
+                push(
+                    At::Block(b),
+                    Severity::Warning,
+                    Tab::Cfg,
+                    format!("{:?} is unreachable from the entry block", block.name),
+                );
+            }
+        }
+    }
+
+    let used: HashSet<&Name> = f
+        .basic_blocks
+        .iter()
+        .flat_map(|b| {
+            b.insts
+                .iter()
+                .flat_map(|i| values(&i.uses))
+                .chain(values(&b.term.uses))
+        })
+        .collect();
+    for (row, (_, inst)) in rows.iter().enumerate() {
+        if let Some(def) = inst.and_then(|i| i.def.as_ref()) {
+            if !used.contains(def) {
+                push(
+                    At::Inst(row),
+                    Severity::Info,
+                    Tab::Lva,
+                    format!("{def:?} is never used"),
+                );
+            }
+        }
+    }
+
+    if !complete {
+        return diagnostics;
+    }
+
+    let doms = dominators::simple_fast(&cfg, entry);
+    let dominates = |a: usize, b: usize| {
+        doms.dominators(node(b))
+            .is_some_and(|mut ds| ds.any(|d| d == node(a)))
+    };
+    let block_of = |name: &Name| f.basic_blocks.iter().position(|b| &b.name == name);
+    for (row, &(b, inst)) in rows.iter().enumerate() {
+        if !reachable.contains(&node(b)) {
+            continue;
+        }
+        // a phi uses its values at the end of the incoming blocks
+        let uses: Vec<(&Name, usize)> = match inst {
+            Some(i) if i.opcode == 55 => i
+                .uses
+                .iter()
+                .zip(i.blocks.iter().flatten())
+                .filter_map(|(o, pred)| {
+                    let name = values(std::slice::from_ref(o)).next()?;
+                    Some((name, block_of(pred)?))
+                })
+                .filter(|&(_, pred)| reachable.contains(&node(pred)))
+                .collect(),
+            _ => uses[row].iter().map(|&n| (n, b)).collect(),
+        };
+        for (name, at) in uses {
+            let Some(&def) = defs.get(name) else {
+                continue;
+            };
+            let d = rows[def].0;
+            let dominated = if inst.is_some_and(|i| i.opcode == 55) {
+                dominates(d, at)
+            } else if d == at {
+                def < row
+            } else {
+                dominates(d, at)
+            };
+            if !dominated {
+                push(
+                    At::Inst(row),
+                    Severity::Error,
+                    Tab::Cfg,
+                    format!("{name:?} is used here, but its definition does not dominate this use"),
+                );
+            }
+        }
+    }
+
+    for (row, &(b, inst)) in rows.iter().enumerate() {
+        let Some(inst) = inst.filter(|i| i.opcode == 55) else {
+            continue;
+        };
+        let preds: BTreeSet<&Name> = cfg
+            .neighbors_directed(node(b), Direction::Incoming)
+            .map(|p| cfg[p])
+            .collect();
+        let listed: BTreeSet<&Name> = inst.blocks.iter().flatten().collect();
+        for pred in preds.difference(&listed) {
+            push(
+                At::Inst(row),
+                Severity::Error,
+                Tab::Cfg,
+                format!("phi has no value for the predecessor {pred:?}"),
+            );
+        }
+        for block in listed.difference(&preds) {
+            push(
+                At::Inst(row),
+                Severity::Error,
+                Tab::Cfg,
+                format!(
+                    "{block:?} is not a predecessor of {:?}",
+                    f.basic_blocks[b].name
+                ),
+            );
+        }
+    }
+
+    diagnostics
+}
+
+#[test]
+fn test_diagnostics() {
+    use crate::builder::{constant, Builder};
+
+    // define i32 @f(i32 %0) {
+    //   %2 = add i32 %0, %6
+    //   br label %4
+    // 3:
+    //   ret i32 %0
+    // 4:
+    //   %5 = phi i32 [ %2, %3 ]
+    //   %6 = add i32 %5, 1
+    //   %7 = mul i32 %5, 2
+    //   ret i32 %5
+    // }
+    let mut b = Builder::new("f");
+    let n = b.param("i32");
+    let entry = b.current();
+    let (exit, body) = (b.block(), b.block());
+    b.position(body);
+    let phi = b.phi("i32");
+    let next = b.binary("add", &phi, &constant("i32", 1));
+    b.binary("mul", &phi, &constant("i32", 2));
+    b.ret(Some(&phi));
+
+    b.position(entry);
+    let sum = b.binary("add", &n, &next);
+    b.br(body);
+    b.incoming(&phi, &sum, exit);
+
+    b.position(exit);
+    b.ret(Some(&n));
+    let f = b.build();
+
+    let diagnostics = diagnostics(&f);
+    assert_eq!(
+        diagnostics
+            .iter()
+            .map(|d| (d.at, d.severity, d.tab))
+            .collect::<Vec<_>>(),
+        vec![
+            // %6 is live-in at the entry block
+            (At::Inst(0), Severity::Warning, Tab::Lva),
+            (At::Block(1), Severity::Warning, Tab::Cfg),
+            // %7 is never used
+            (At::Inst(5), Severity::Info, Tab::Lva),
+            // %6 is defined after its use
+            (At::Inst(0), Severity::Error, Tab::Cfg),
+            // the phi names %3 instead of %1
+            (At::Inst(3), Severity::Error, Tab::Cfg),
+            (At::Inst(3), Severity::Error, Tab::Cfg),
+        ],
+    );
+    assert_eq!(
+        diagnostics[0].message,
+        "%6 may be used uninitialized: it is live-in at %1 but is not a parameter",
+    );
+    assert_eq!(
+        diagnostics[4].message,
+        "phi has no value for the predecessor %1",
+    );
+    assert_eq!(diagnostics[5].message, "%3 is not a predecessor of %4");
+}
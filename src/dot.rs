@@ -0,0 +1,204 @@
+use std::collections::HashSet;
+use std::fmt::Write;
+
+use crate::ir::{self, BasicBlock, Function};
+
+/// Escapes `s` for use inside a record label.
+pub fn escape(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        if matches!(c, '\\' | '{' | '}' | '|' | '<' | '>' | '"') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Indices of the blocks reachable from the entry block of `f`.
+pub fn reachable(f: &Function) -> HashSet<usize> {
+    let (blocks, cfg) = ir::cfg(f);
+    let mut reachable = HashSet::new();
+    if let Some(entry) = f.basic_blocks.first() {
+        let mut dfs = petgraph::visit::Dfs::new(&cfg, blocks[&entry.name].1);
+        while let Some(n) = dfs.next(&cfg) {
+            reachable.insert(n.index());
+        }
+    }
+    reachable
+}
+
+/// Extra contents of a block node in `cfg`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Node {
+    /// A field shown between the label and the instructions.
+    pub before: Option<String>,
+    /// A field shown below the instructions.
+    pub after: Option<String>,
+    /// Further Graphviz attributes, e.g. `style=filled`.
+    pub attrs: String,
+}
+
+/// Renders the CFG of `f` with a record node per block listing its
+/// instructions. Nodes get the id `block{i}` after their index in `f`, and
+/// `node` and `edge` may add to each node and to each edge between blocks.
+pub fn cfg(
+    f: &Function,
+    node: impl Fn(usize, &BasicBlock) -> Node,
+    edge: impl Fn(usize, usize) -> String,
+) -> String {
+    let reachable = reachable(f);
+    let index = |n: &Option<ir::Name>| {
+        f.basic_blocks
+            .iter()
+            .position(|b| Some(&b.name) == n.as_ref())
+    };
+
+    let mut dot = String::new();
+    writeln!(dot, "digraph {{").unwrap();
+    writeln!(dot, "    bgcolor=\"transparent\"").unwrap();
+    writeln!(dot, "    node [shape=record fontname=\"monospace\"]").unwrap();
+    for (i, b) in f.basic_blocks.iter().enumerate() {
+        let body: String = b
+            .insts
+            .iter()
+            .map(|i| &i.string)
+            .chain(std::iter::once(&b.term.string))
+            .map(|s| format!("{}\\l", escape(s.trim())))
+            .collect();
+        let style = if reachable.contains(&i) {
+            ""
+        } else {
+            " color=\"gray\" fontcolor=\"gray\" style=\"dashed\""
+        };
+        let Node {
+            before,
+            after,
+            attrs,
+        } = node(i, b);
+        let fields: Vec<_> = std::iter::once(format!("{}:", escape(&format!("{:?}", b.name))))
+            .chain(before.map(|s| escape(&s)))
+            .chain(std::iter::once(body))
+            .chain(after.map(|s| escape(&s)))
+            .collect();
+        writeln!(
+            dot,
+            "    {i} [id=\"block{i}\" label=\"{{{}}}\" {attrs}{style}]",
+            fields.join("|"),
+        )
+        .unwrap();
+    }
+    for (i, b) in f.basic_blocks.iter().enumerate() {
+        match (b.term.opcode, &b.term.uses[..]) {
+            (2, [l]) => {
+                if let Some(j) = index(&l.name) {
+                    writeln!(dot, "    {i} -> {j} [{}]", edge(i, j)).unwrap();
+                }
+            }
+            // operands of a conditional br are stored as [cond, false, true]
+            (2, [_, l, r]) => {
+                if let Some(j) = index(&r.name) {
+                    writeln!(dot, "    {i} -> {j} [label=\"true\" {}]", edge(i, j)).unwrap();
+                }
+                if let Some(j) = index(&l.name) {
+                    writeln!(dot, "    {i} -> {j} [label=\"false\" {}]", edge(i, j)).unwrap();
+                }
+            }
+            _ => {}
+        }
+    }
+    writeln!(dot, "}}").unwrap();
+    dot
+}
+
+#[test]
+fn test_cfg() {
+    use ir::{Name, Operand, Terminator, Type};
+
+    let label = |n| Operand {
+        constant: false,
+        name: Some(Name::Number(n)),
+        ty: Type {
+            id: 8,
+            name: "label".to_string(),
+        },
+        value: None,
+    };
+    let block = |n, uses, string: &str| BasicBlock {
+        name: Name::Number(n),
+        insts: vec![],
+        term: Terminator {
+            opcode: if string.contains("ret") { 1 } else { 2 },
+            def: None,
+            uses,
+            string: string.to_string(),
+        },
+    };
+
+    let f = Function {
+        name: "main".to_string(),
+        params: vec![],
+        basic_blocks: vec![
+            block(
+                0,
+                vec![
+                    Operand {
+                        constant: true,
+                        name: None,
+                        ty: Type {
+                            id: 13,
+                            name: "i1".to_string(),
+                        },
+                        value: None,
+                    },
+                    label(2),
+                    label(1),
+                ],
+                "  br i1 true, label %1, label %2",
+            ),
+            block(1, vec![], "  ret void"),
+            block(2, vec![label(1)], "  br label %1"),
+            block(3, vec![label(1)], "  br label %1"),
+        ],
+    };
+
+    assert_eq!(
+        cfg(&f, |_, _| Node::default(), |_, _| String::new()),
+        r#"digraph {
+    bgcolor="transparent"
+    node [shape=record fontname="monospace"]
+    0 [id="block0" label="{%0:|br i1 true, label %1, label %2\l}" ]
+    1 [id="block1" label="{%1:|ret void\l}" ]
+    2 [id="block2" label="{%2:|br label %1\l}" ]
+    3 [id="block3" label="{%3:|br label %1\l}"  color="gray" fontcolor="gray" style="dashed"]
+    0 -> 1 [label="true" ]
+    0 -> 2 [label="false" ]
+    2 -> 1 []
+    3 -> 1 []
+}
+"#,
+    );
+    assert_eq!(
+        cfg(
+            &f,
+            |i, _| Node {
+                before: (i == 1).then(|| "in: {}".to_string()),
+                after: (i == 1).then(|| "out: {}".to_string()),
+                attrs: String::new(),
+            },
+            |i, _| if i == 2 {
+                "color=red".to_string()
+            } else {
+                String::new()
+            },
+        )
+        .lines()
+        .filter(|l| l.contains("block1") || l.contains("2 -> 1"))
+        .collect::<Vec<_>>(),
+        vec![
+            r#"    1 [id="block1" label="{%1:|in: \{\}|ret void\l|out: \{\}}" ]"#,
+            "    2 -> 1 [color=red]",
+        ],
+    );
+    assert_eq!(escape("{a|<b>}"), "\\{a\\|\\<b\\>\\}");
+}
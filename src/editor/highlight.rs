@@ -0,0 +1,36 @@
+use std::cell::RefCell;
+
+use wasm_bindgen::prelude::*;
+
+use super::call;
+
+thread_local! {
+    // the lines highlighted and the ids of their decorations
+    static SHOWN: RefCell<(Vec<(usize, usize)>, JsValue)> = RefCell::new((vec![], js_sys::Array::new().into()));
+}
+
+/// Highlights the 0-based first to last line of every range in `lines`,
+/// replacing the ones highlighted before.
+pub fn show(editor: &JsValue, lines: &[(usize, usize)]) -> Result<(), JsValue> {
+    let (shown, ids) = SHOWN.with(|s| s.borrow().clone());
+    if shown == lines {
+        return Ok(());
+    }
+
+    let decorations: js_sys::Array = lines
+        .iter()
+        .map(|&(first, last)| {
+            js_sys::JSON::parse(&format!(
+                r#"{{
+    "range": {{ "startLineNumber": {}, "startColumn": 1, "endLineNumber": {}, "endColumn": 1 }},
+    "options": {{ "isWholeLine": true, "className": "bg-yellow-100 dark:bg-yellow-900" }}
+}}"#,
+                first + 1,
+                last + 1,
+            ))
+        })
+        .collect::<Result<_, _>>()?;
+    let ids = call(editor, "deltaDecorations", &[&ids, &decorations])?;
+    SHOWN.with(|s| *s.borrow_mut() = (lines.to_vec(), ids));
+    Ok(())
+}
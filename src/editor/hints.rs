@@ -0,0 +1,133 @@
+use std::cell::RefCell;
+use std::collections::BTreeSet;
+
+use wasm_bindgen::prelude::*;
+
+use super::call;
+use crate::ir::{Name, OwnedInstLive};
+use crate::source;
+
+thread_local! {
+    // the hints shown and the ids of their decorations
+    static SHOWN: RefCell<(Vec<Hint>, JsValue)> = RefCell::new((vec![], js_sys::Array::new().into()));
+}
+
+/// Text shown at the end of a line.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Hint {
+    /// 0-based line of the instruction.
+    pub line: usize,
+    pub text: String,
+    pub class: &'static str,
+}
+
+/// The live-out set of every instruction at the end of its line. Rows that
+/// changed from `old` to `new`, the `selected` row and rows where
+/// `highlight` is live stand out the same way they do in `lva::Lva`.
+pub fn hints(
+    lines: &source::Lines,
+    old: &[OwnedInstLive],
+    new: &[OwnedInstLive],
+    selected: Option<usize>,
+    highlight: Option<&Name>,
+) -> Vec<Hint> {
+    old.iter()
+        .zip(new)
+        .enumerate()
+        .filter_map(|(row, ((_, oout, _), (r#in, out, _)))| {
+            let class = if selected == Some(row) {
+                "bg-yellow-100 dark:bg-yellow-900 text-zinc-500"
+            } else if highlight.is_some_and(|h| r#in.contains(h) || out.contains(h)) {
+                "font-bold text-red-500"
+            } else if oout != out {
+                "text-red-500"
+            } else {
+                "text-zinc-500"
+            };
+            Some(Hint {
+                line: *lines.insts.get(row)?,
+                text: format!("  out: {:?}", out.iter().collect::<BTreeSet<_>>()),
+                class,
+            })
+        })
+        .collect()
+}
+
+/// Shows `hints` in `editor`, replacing the ones shown before.
+pub fn show(editor: &JsValue, hints: &[Hint]) -> Result<(), JsValue> {
+    let (shown, ids) = SHOWN.with(|s| s.borrow().clone());
+    if shown == hints {
+        return Ok(());
+    }
+
+    let decorations: js_sys::Array = hints
+        .iter()
+        .map(|h| {
+            let line = h.line + 1;
+            js_sys::JSON::parse(&format!(
+                r#"{{
+    "range": {{ "startLineNumber": {line}, "startColumn": 1, "endLineNumber": {line}, "endColumn": 1 }},
+    "options": {{ "isWholeLine": true, "after": {{ "content": {:?}, "inlineClassName": {:?} }} }}
+}}"#,
+                h.text, h.class,
+            ))
+        })
+        .collect::<Result<_, _>>()?;
+    let ids = call(editor, "deltaDecorations", &[&ids, &decorations])?;
+    SHOWN.with(|s| *s.borrow_mut() = (hints.to_vec(), ids));
+    Ok(())
+}
+
+#[test]
+fn test_hints() {
+    use std::collections::HashSet;
+
+    let n = |n| Name::Number(n);
+    let lines = source::Lines {
+        define: 0,
+        blocks: vec![0],
+        insts: vec![1, 2, 4],
+        ends: vec![1, 2, 4],
+    };
+    let row = |out: &[usize]| {
+        (
+            HashSet::new(),
+            out.iter().map(|&i| n(i)).collect(),
+            String::new(),
+        )
+    };
+    let old = vec![row(&[]), row(&[1]), row(&[])];
+    let new = vec![row(&[1, 0]), row(&[1]), row(&[])];
+
+    assert_eq!(
+        hints(&lines, &old, &new, None, None),
+        vec![
+            Hint {
+                line: 1,
+                text: "  out: {%0, %1}".to_string(),
+                class: "text-red-500",
+            },
+            Hint {
+                line: 2,
+                text: "  out: {%1}".to_string(),
+                class: "text-zinc-500",
+            },
+            Hint {
+                line: 4,
+                text: "  out: {}".to_string(),
+                class: "text-zinc-500",
+            },
+        ],
+    );
+    assert_eq!(
+        hints(&lines, &old, &new, Some(2), Some(&n(1)))
+            .iter()
+            .map(|h| h.class)
+            .collect::<Vec<_>>(),
+        vec![
+            "font-bold text-red-500",
+            "font-bold text-red-500",
+            "bg-yellow-100 dark:bg-yellow-900 text-zinc-500",
+        ],
+    );
+}
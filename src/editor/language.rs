@@ -0,0 +1,169 @@
+use wasm_bindgen::prelude::*;
+
+use super::{call, get};
+
+/// Monarch tokenizer for LLVM 17 textual IR.
+pub const MONARCH: &str = r##"{
+    "defaultToken": "",
+    "keywords": [
+        "define", "declare", "attributes", "global", "constant", "alias", "ifunc",
+        "private", "internal", "external", "linkonce", "linkonce_odr", "weak",
+        "weak_odr", "common", "appending", "extern_weak", "available_externally",
+        "dso_local", "dso_preemptable", "unnamed_addr", "local_unnamed_addr",
+        "thread_local", "localdynamic", "initialexec", "localexec", "default",
+        "hidden", "protected", "dllimport", "dllexport", "section", "partition",
+        "align", "addrspace", "comdat", "any", "exactmatch", "largest",
+        "nodeduplicate", "samesize", "gc", "prefix", "prologue", "personality",
+        "target", "datalayout", "triple", "source_filename", "module", "asm",
+        "type", "opaque", "to", "nuw", "nsw", "exact", "inbounds", "inrange",
+        "volatile", "atomic", "syncscope", "unordered", "monotonic", "acquire",
+        "release", "acq_rel", "seq_cst", "tail", "musttail", "notail",
+        "cc", "ccc", "fastcc", "coldcc", "tailcc", "swiftcc", "swifttailcc",
+        "nnan", "ninf", "nsz", "arcp", "contract", "afn", "reassoc", "fast",
+        "within", "unwind", "cleanup", "catch", "filter", "from", "caller",
+        "none", "true", "false", "null", "undef", "poison", "zeroinitializer",
+        "blockaddress", "dso_local_equivalent", "no_cfi", "distinct", "uselistorder",
+        "eq", "ne", "ugt", "uge", "ult", "ule", "sgt", "sge", "slt", "sle",
+        "oeq", "ogt", "oge", "olt", "ole", "one", "ord", "ueq", "une", "uno",
+        "xchg", "nand", "max", "min", "umax", "umin", "fmax", "fmin",
+        "uinc_wrap", "udec_wrap", "vscale", "x"
+    ],
+    "opcodes": [
+        "ret", "br", "switch", "indirectbr", "invoke", "callbr", "resume",
+        "unreachable", "catchswitch", "catchret", "cleanupret", "fneg", "add",
+        "fadd", "sub", "fsub", "mul", "fmul", "udiv", "sdiv", "fdiv", "urem",
+        "srem", "frem", "shl", "lshr", "ashr", "and", "or", "xor",
+        "extractelement", "insertelement", "shufflevector", "extractvalue",
+        "insertvalue", "alloca", "load", "store", "fence", "cmpxchg", "atomicrmw",
+        "getelementptr", "trunc", "zext", "sext", "fptrunc", "fpext", "fptoui",
+        "fptosi", "uitofp", "sitofp", "ptrtoint", "inttoptr", "bitcast",
+        "addrspacecast", "icmp", "fcmp", "phi", "select", "freeze", "call",
+        "va_arg", "landingpad", "catchpad", "cleanuppad"
+    ],
+    "types": [
+        "void", "half", "bfloat", "float", "double", "x86_fp80", "fp128",
+        "ppc_fp128", "x86_amx", "x86_mmx", "label", "metadata", "token", "ptr"
+    ],
+    "attributes": [
+        "alwaysinline", "argmemonly", "builtin", "cold", "convergent",
+        "disable_sanitizer_instrumentation", "hot", "inlinehint", "jumptable",
+        "minsize", "mustprogress", "naked", "nobuiltin", "nocallback", "noduplicate",
+        "nofree", "noimplicitfloat", "noinline", "nomerge", "nonlazybind",
+        "noprofile", "norecurse", "noredzone", "noreturn", "nosanitize_bounds",
+        "nosanitize_coverage", "nosync", "nounwind", "null_pointer_is_valid",
+        "optforfuzzing", "optnone", "optsize", "presplitcoroutine", "readnone",
+        "readonly", "returns_twice", "safestack", "sanitize_address",
+        "sanitize_hwaddress", "sanitize_memory", "sanitize_memtag",
+        "sanitize_thread", "shadowcallstack", "speculatable",
+        "speculative_load_hardening", "ssp", "sspreq", "sspstrong", "strictfp",
+        "uwtable", "willreturn", "writeonly", "memory", "allockind", "allocsize",
+        "vscale_range", "alignstack", "fn_ret_thunk_extern", "skipprofile",
+        "zeroext", "signext", "inreg", "byval", "byref", "preallocated",
+        "inalloca", "sret", "elementtype", "noalias", "nocapture",
+        "nest", "returned", "nonnull", "dereferenceable",
+        "dereferenceable_or_null", "swiftself", "swiftasync", "swifterror",
+        "immarg", "noundef", "nofpclass", "allocalign", "allocptr",
+        "argmem", "inaccessiblemem", "read", "write", "readwrite"
+    ],
+    "escapes": "\\\\(?:[0-9a-fA-F]{2}|\\\\)",
+    "tokenizer": {
+        "root": [
+            [";.*$", "comment"],
+            ["c\"", "string", "@string"],
+            ["!?\"", "string", "@string"],
+            ["[%@](?:[-a-zA-Z$._][-a-zA-Z$._0-9]*|[0-9]+)", "variable"],
+            ["[%@]\"", "variable", "@name"],
+            ["!(?:[-a-zA-Z$._][-a-zA-Z$._0-9]*|[0-9]+)", "tag"],
+            ["#[0-9]+", "annotation"],
+            ["[-a-zA-Z$._0-9]+:", "tag"],
+            ["\\bi[0-9]+\\b", "type"],
+            ["0x[KLMHR]?[0-9a-fA-F]+", "number.hex"],
+            ["[-+]?[0-9]+\\.[0-9]*(?:[eE][-+]?[0-9]+)?", "number.float"],
+            ["[-+]?[0-9]+", "number"],
+            ["[a-zA-Z_][a-zA-Z0-9_.]*", {
+                "cases": {
+                    "@opcodes": "keyword.opcode",
+                    "@types": "type",
+                    "@attributes": "attribute.name",
+                    "@keywords": "keyword",
+                    "@default": "identifier"
+                }
+            }],
+            ["[{}()\\[\\]<>]", "delimiter.bracket"],
+            ["\\.\\.\\.|[=,*:]", "delimiter"]
+        ],
+        "string": [
+            ["[^\\\\\"]+", "string"],
+            ["@escapes", "string.escape"],
+            ["\\\\.", "string.escape.invalid"],
+            ["\"", "string", "@pop"]
+        ],
+        "name": [
+            ["[^\"]+", "variable"],
+            ["\"", "variable", "@pop"]
+        ]
+    }
+}"##;
+
+/// Colors of the semantic token types `register0`, `register1` and so on,
+/// which values get by their register modulo the number of colors.
+pub const PALETTE: [&str; 8] = [
+    "e5534b", "57ab5a", "539bf5", "c69026", "b083f0", "39c5cf", "f69d50", "dcbdfb",
+];
+
+/// Registers the llvm language and the `ullvm` and `ullvm-dark` themes that
+/// color its semantic tokens.
+pub fn register(monaco: &JsValue) -> Result<(), JsValue> {
+    let languages = get(monaco, "languages")?;
+    call(
+        &languages,
+        "register",
+        &[&js_sys::JSON::parse(r#"{ "id": "llvm" }"#)?],
+    )?;
+    call(
+        &languages,
+        "setMonarchTokensProvider",
+        &[&JsValue::from_str("llvm"), &js_sys::JSON::parse(MONARCH)?],
+    )?;
+
+    let rules: Vec<_> = PALETTE
+        .iter()
+        .enumerate()
+        .map(|(i, color)| format!(r#"{{ "token": "register{i}", "foreground": "{color}" }}"#))
+        .collect();
+    let editor = get(monaco, "editor")?;
+    for (name, base) in [("ullvm", "vs"), ("ullvm-dark", "vs-dark")] {
+        let theme = js_sys::JSON::parse(&format!(
+            r#"{{ "base": "{base}", "inherit": true, "rules": [{}], "colors": {{}} }}"#,
+            rules.join(", "),
+        ))?;
+        call(&editor, "defineTheme", &[&JsValue::from_str(name), &theme])?;
+    }
+    Ok(())
+}
+
+#[test]
+fn test_monarch() {
+    let monarch: serde_json::Value = serde_json::from_str(MONARCH).unwrap();
+    let opcodes = monarch["opcodes"].as_array().unwrap();
+
+    // every instruction in the examples starts with a known opcode
+    for text in [
+        include_str!("../../examples/ll/brainfuck.ll"),
+        include_str!("../../examples/ll/fib.ll"),
+        include_str!("../../examples/ll/fib.g.ll"),
+        include_str!("../../examples/ll/for1.ll"),
+    ] {
+        for lines in crate::source::functions(text).values() {
+            for &n in &lines.insts {
+                let line = text.lines().nth(n).unwrap();
+                let inst = line.split_once(" = ").map_or(line, |(_, i)| i).trim();
+                let opcode = inst
+                    .split_whitespace()
+                    .find(|w| !matches!(*w, "tail" | "musttail" | "notail"))
+                    .unwrap();
+                assert!(opcodes.contains(&opcode.into()), "{opcode} in {line:?}");
+            }
+        }
+    }
+}
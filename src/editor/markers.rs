@@ -0,0 +1,184 @@
+use std::cell::RefCell;
+
+use wasm_bindgen::prelude::*;
+
+use super::{call, get};
+use crate::diagnostics::{At, Diagnostic, Severity, Tab};
+use crate::source;
+
+thread_local! {
+    // the markers shown
+    static SHOWN: RefCell<Vec<Marker>> = const { RefCell::new(vec![]) };
+}
+
+/// A diagnostic placed on a line of the module text.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Marker {
+    /// 0-based line of the block or instruction.
+    pub line: usize,
+    pub severity: Severity,
+    pub message: String,
+    /// The function and tab the marker links to.
+    pub function: usize,
+    pub tab: Tab,
+}
+
+/// Places the `diagnostics` of the `function`th function on the `lines`
+/// it spans.
+pub fn markers(function: usize, lines: &source::Lines, diagnostics: &[Diagnostic]) -> Vec<Marker> {
+    diagnostics
+        .iter()
+        .filter_map(|d| {
+            let line = match d.at {
+                At::Block(b) => lines.blocks.get(b),
+                At::Inst(row) => lines.insts.get(row),
+            };
+            Some(Marker {
+                line: *line?,
+                severity: d.severity,
+                message: d.message.clone(),
+                function,
+                tab: d.tab,
+            })
+        })
+        .collect()
+}
+
+fn link(function: usize, tab: Tab) -> String {
+    format!("ullvm:tab?function={function}&tab={}", tab as usize)
+}
+
+/// The function and tab of a `link`.
+fn parse_link(query: &str) -> Option<(usize, Tab)> {
+    let (mut function, mut tab) = (None, None);
+    for pair in query.split('&') {
+        match pair.split_once('=')? {
+            ("function", f) => function = f.parse().ok(),
+            ("tab", "0") => tab = Some(Tab::Cfg),
+            ("tab", "1") => tab = Some(Tab::Lva),
+            _ => {}
+        }
+    }
+    Some((function?, tab?))
+}
+
+/// Shows `markers` in the model of `editor`, replacing the ones shown before.
+pub fn show(editor: &JsValue, markers: &[Marker]) -> Result<(), JsValue> {
+    if SHOWN.with(|s| *s.borrow() == markers) {
+        return Ok(());
+    }
+
+    let window = web_sys::window().unwrap();
+    let monaco = get(&window, "monaco")?;
+    let uri = get(&monaco, "Uri")?;
+    let model = call(editor, "getModel", &[])?;
+    let data: js_sys::Array = markers
+        .iter()
+        .map(|m| {
+            let line = m.line + 1;
+            let number = JsValue::from_f64(line as f64);
+            let start = call(&model, "getLineFirstNonWhitespaceColumn", &[&number])?
+                .as_f64()
+                .unwrap_or_default()
+                .max(1.0);
+            let end = call(&model, "getLineMaxColumn", &[&number])?
+                .as_f64()
+                .unwrap_or(1.0);
+            let severity = match m.severity {
+                Severity::Info => 2,
+                Severity::Warning => 4,
+                Severity::Error => 8,
+            };
+            let marker = js_sys::JSON::parse(&format!(
+                r#"{{
+    "severity": {severity},
+    "message": {:?},
+    "source": "ullvm",
+    "startLineNumber": {line}, "startColumn": {start}, "endLineNumber": {line}, "endColumn": {end},
+    "code": {{ "value": {:?} }}
+}}"#,
+                m.message,
+                m.tab.name(),
+            ))?;
+            let target = call(
+                &uri,
+                "parse",
+                &[&JsValue::from_str(&link(m.function, m.tab))],
+            )?;
+            js_sys::Reflect::set(&get(&marker, "code")?, &"target".into(), &target)?;
+            Ok(marker)
+        })
+        .collect::<Result<_, JsValue>>()?;
+    call(
+        &get(&monaco, "editor")?,
+        "setModelMarkers",
+        &[&model, &JsValue::from_str("ullvm"), &data],
+    )?;
+    SHOWN.with(|s| *s.borrow_mut() = markers.to_vec());
+    Ok(())
+}
+
+/// Registers a link opener that calls `onlink` with the function and tab of
+/// the links of markers.
+pub fn register(monaco: &JsValue, onlink: impl Fn(usize, Tab) + 'static) -> Result<(), JsValue> {
+    let open = Closure::<dyn Fn(JsValue) -> bool>::new(move |resource: JsValue| {
+        let scheme = get(&resource, "scheme").ok().and_then(|s| s.as_string());
+        if scheme.as_deref() != Some("ullvm") {
+            return false;
+        }
+        let query = get(&resource, "query")
+            .ok()
+            .and_then(|q| q.as_string())
+            .unwrap_or_default();
+        match parse_link(&query) {
+            Some((function, tab)) => {
+                onlink(function, tab);
+                true
+            }
+            None => false,
+        }
+    });
+    let opener = js_sys::Object::new();
+    js_sys::Reflect::set(&opener, &"open".into(), open.as_ref())?;
+    open.forget();
+    call(&get(monaco, "editor")?, "registerLinkOpener", &[&opener])?;
+    Ok(())
+}
+
+#[test]
+fn test_markers() {
+    let lines = source::Lines {
+        define: 0,
+        blocks: vec![0, 3],
+        insts: vec![1, 2, 4],
+        ends: vec![1, 2, 4],
+    };
+    let diagnostic = |at| Diagnostic {
+        at,
+        severity: Severity::Warning,
+        message: String::new(),
+        tab: Tab::Lva,
+    };
+    assert_eq!(
+        markers(
+            2,
+            &lines,
+            &[
+                diagnostic(At::Inst(2)),
+                diagnostic(At::Block(1)),
+                diagnostic(At::Inst(3)),
+            ],
+        )
+        .iter()
+        .map(|m| (m.line, m.function))
+        .collect::<Vec<_>>(),
+        vec![(4, 2), (3, 2)],
+    );
+
+    let link = link(2, Tab::Lva);
+    assert_eq!(
+        parse_link(link.split_once('?').unwrap().1),
+        Some((2, Tab::Lva)),
+    );
+    assert_eq!(parse_link("function=2"), None);
+}
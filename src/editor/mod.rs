@@ -1,10 +1,34 @@
 #![allow(non_snake_case)]
 
+use std::collections::HashMap;
+
 use dioxus::prelude::*;
 use wasm_bindgen::prelude::*;
 
+use crate::alloc::Assignment;
+use crate::diagnostics::Tab;
+use crate::ir::Function;
 use crate::util;
 
+pub use hints::{hints, Hint};
+pub use markers::{markers, Marker};
+
+mod highlight;
+mod hints;
+mod language;
+mod markers;
+mod semantic;
+mod symbols;
+
+fn get(target: &JsValue, key: &str) -> Result<JsValue, JsValue> {
+    js_sys::Reflect::get(target, &JsValue::from_str(key))
+}
+
+fn call(target: &JsValue, key: &str, args: &[&JsValue]) -> Result<JsValue, JsValue> {
+    let f: js_sys::Function = get(target, key)?.dyn_into()?;
+    f.apply(target, &args.iter().copied().collect())
+}
+
 #[wasm_bindgen]
 extern "C" {
     #[wasm_bindgen(js_name = "Module")]
@@ -18,10 +42,35 @@ extern "C" {
 }
 
 #[component]
-pub fn Editor(content: String, onChange: EventHandler<String>) -> Element {
+pub fn Editor(
+    content: String,
+    reveal: Signal<Option<(usize, usize)>>,
+    registers: HashMap<String, Assignment>,
+    functions: Vec<Function>,
+    hints: Vec<Hint>,
+    markers: Vec<Marker>,
+    highlight: Vec<(usize, usize)>,
+    onChange: EventHandler<String>,
+    onLink: EventHandler<(usize, Tab)>,
+    onCursor: EventHandler<usize>,
+    onHover: EventHandler<Option<usize>>,
+) -> Element {
     let mut editor = use_signal(|| None::<JsValue>);
 
+    semantic::update(&registers);
+    symbols::update(&functions);
+
     if let Some(ref editor) = *editor.read() {
+        if let Err(e) = hints::show(editor, &hints) {
+            tracing::warn!("failed to show hints: {e:?}");
+        }
+        if let Err(e) = markers::show(editor, &markers) {
+            tracing::warn!("failed to show markers: {e:?}");
+        }
+        if let Err(e) = highlight::show(editor, &highlight) {
+            tracing::warn!("failed to highlight lines: {e:?}");
+        }
+
         let get_model: js_sys::Function =
             js_sys::Reflect::get(editor, &JsValue::from_str("getModel"))
                 .unwrap()
@@ -76,62 +125,15 @@ pub fn Editor(content: String, onChange: EventHandler<String>) -> Element {
                     .unwrap();
             let container = document.get_element_by_id("container").unwrap();
 
-            // register llvm language
-            let languages = js_sys::Reflect::get(&monaco, &JsValue::from_str("languages")).unwrap();
-            let register: js_sys::Function =
-                js_sys::Reflect::get(&languages, &JsValue::from_str("register"))
-                    .unwrap()
-                    .dyn_into()
-                    .unwrap();
-            let arg1 = js_sys::JSON::parse(r#"{ "id": "llvm" }"#).unwrap();
-            register.call1(&languages, &arg1).unwrap();
-            let set_monarch_tokens_provider: js_sys::Function =
-                js_sys::Reflect::get(&languages, &JsValue::from_str("setMonarchTokensProvider"))
-                    .unwrap()
-                    .dyn_into()
-                    .unwrap();
-            let root = js_sys::Array::of4(
-                &js_sys::Array::of2(
-                    &js_sys::RegExp::new("(%|@)[a-zA-Z0-9\\.]+", ""),
-                    &JsValue::from_str("variable"),
-                ),
-                &js_sys::Array::of2(
-                    &js_sys::RegExp::new("@?[a-zA-Z][\\w$]*", ""),
-                    &js_sys::JSON::parse(
-                        r#"{
-				"cases": {
-					"@keywords": "keyword"
-				}
-			}"#,
-                    )
-                    .unwrap(),
-                ),
-                &js_sys::Array::of2(
-                    &js_sys::RegExp::new(r#"".*?""#, ""),
-                    &JsValue::from_str("string"),
-                ),
-                &js_sys::Array::of2(
-                    &js_sys::RegExp::new(r#";.*$"#, ""),
-                    &JsValue::from_str("comment"),
-                ),
-            );
-            let arg2 = js_sys::JSON::parse(
-                r#"{
-	"keywords": ["define", "declare", "attributes"],
-	"tokenizer": {}
-}"#,
-            )
-            .unwrap();
-            let tokenizer = js_sys::Reflect::get(&arg2, &JsValue::from_str("tokenizer")).unwrap();
-            js_sys::Reflect::set(&tokenizer, &JsValue::from_str("root"), &root).unwrap();
-            set_monarch_tokens_provider
-                .call2(&languages, &JsValue::from_str("llvm"), &arg2)
-                .unwrap();
+            language::register(&monaco).unwrap();
+            semantic::register(&monaco).unwrap();
+            symbols::register(&monaco).unwrap();
+            markers::register(&monaco, move |function, tab| onLink((function, tab))).unwrap();
 
             let theme = if util::dark_mode().unwrap() {
-                "vs-dark"
+                "ullvm-dark"
             } else {
-                "vs"
+                "ullvm"
             };
             let arg2 = js_sys::JSON::parse(&format!(
                 r#"{{
@@ -139,6 +141,7 @@ pub fn Editor(content: String, onChange: EventHandler<String>) -> Element {
     "language": "llvm",
     "minimap": {{ "enabled": false }},
     "automaticLayout": true,
+    "semanticHighlighting.enabled": true,
     "theme": {:?}
 }}"#,
                 content, theme,
@@ -187,6 +190,52 @@ pub fn Editor(content: String, onChange: EventHandler<String>) -> Element {
 
             callback.forget();
 
+            // the views follow the cursor when the user moves it, but not when
+            // it is moved by reveal or by setting the content
+            let on_cursor =
+                wasm_bindgen::closure::Closure::<dyn FnMut(JsValue)>::new(move |e: JsValue| {
+                    let source = get(&e, "source").ok().and_then(|s| s.as_string());
+                    if !matches!(source.as_deref(), Some("mouse" | "keyboard")) {
+                        return;
+                    }
+                    let line = get(&e, "position")
+                        .and_then(|p| get(&p, "lineNumber"))
+                        .ok()
+                        .and_then(|l| l.as_f64());
+                    if let Some(line) = line {
+                        onCursor(line as usize - 1);
+                    }
+                });
+            call(&e, "onDidChangeCursorPosition", &[on_cursor.as_ref()]).unwrap();
+            on_cursor.forget();
+
+            // the 0-based line under the mouse, only told when it changes
+            let hovered = std::rc::Rc::new(std::cell::Cell::new(None::<usize>));
+            let on_mouse_move = {
+                let hovered = hovered.clone();
+                wasm_bindgen::closure::Closure::<dyn FnMut(JsValue)>::new(move |e: JsValue| {
+                    let line = get(&e, "target")
+                        .and_then(|t| get(&t, "position"))
+                        .and_then(|p| get(&p, "lineNumber"))
+                        .ok()
+                        .and_then(|l| l.as_f64())
+                        .map(|l| l as usize - 1);
+                    if hovered.replace(line) != line {
+                        onHover(line);
+                    }
+                })
+            };
+            let on_mouse_leave =
+                wasm_bindgen::closure::Closure::<dyn FnMut(JsValue)>::new(move |_: JsValue| {
+                    if hovered.replace(None).is_some() {
+                        onHover(None);
+                    }
+                });
+            call(&e, "onMouseMove", &[on_mouse_move.as_ref()]).unwrap();
+            call(&e, "onMouseLeave", &[on_mouse_leave.as_ref()]).unwrap();
+            on_mouse_move.forget();
+            on_mouse_leave.forget();
+
             *editor.write() = Some(e);
         });
         require
@@ -217,9 +266,9 @@ pub fn Editor(content: String, onChange: EventHandler<String>) -> Element {
                         .unwrap();
 
                 let theme = if util::dark_mode().unwrap() {
-                    "vs-dark"
+                    "ullvm-dark"
                 } else {
-                    "vs"
+                    "ullvm"
                 };
 
                 set_theme
@@ -238,6 +287,43 @@ pub fn Editor(content: String, onChange: EventHandler<String>) -> Element {
         }
     });
 
+    use_effect(move || {
+        // scroll to and select the (1-based) first to last line in reveal
+        let Some((line, last)) = *reveal.read() else {
+            return;
+        };
+        if let Some(ref editor) = *editor.read() {
+            let reveal_line: js_sys::Function =
+                js_sys::Reflect::get(editor, &JsValue::from_str("revealLineInCenter"))
+                    .unwrap()
+                    .dyn_into()
+                    .unwrap();
+            reveal_line
+                .call1(editor, &JsValue::from_f64(line as f64))
+                .unwrap();
+
+            let set_selection: js_sys::Function =
+                js_sys::Reflect::get(editor, &JsValue::from_str("setSelection"))
+                    .unwrap()
+                    .dyn_into()
+                    .unwrap();
+            let model = call(editor, "getModel", &[]).unwrap();
+            let end = call(
+                &model,
+                "getLineMaxColumn",
+                &[&JsValue::from_f64(last as f64)],
+            )
+            .ok()
+            .and_then(|c| c.as_f64())
+            .unwrap_or(1.0);
+            let range = js_sys::JSON::parse(&format!(
+                r#"{{ "startLineNumber": {line}, "startColumn": 1, "endLineNumber": {last}, "endColumn": {end} }}"#
+            ))
+            .unwrap();
+            set_selection.call1(editor, &range).unwrap();
+        }
+    });
+
     rsx! {
         div { id: "container", class: "w-full h-full" }
     }
@@ -0,0 +1,210 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use wasm_bindgen::prelude::*;
+
+use super::language::PALETTE;
+use super::{call, get, symbols};
+use crate::alloc::{Assignment, Register};
+use crate::ir::Name;
+
+thread_local! {
+    // the registers of every function, by function name
+    static REGISTERS: RefCell<HashMap<String, Assignment>> = RefCell::new(HashMap::new());
+    // a monaco.Emitter that tells the editor to request new tokens
+    static CHANGED: RefCell<Option<JsValue>> = const { RefCell::new(None) };
+}
+
+pub(super) fn is_name_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '-' | '$' | '.' | '_')
+}
+
+/// The local values mentioned in `line` as their UTF-16 column and length.
+pub(super) fn locals(line: &str) -> Vec<(usize, usize, Name)> {
+    let column = |i: usize| line[..i].encode_utf16().count();
+    let mut locals = vec![];
+    let mut string = false;
+    let mut chars = line.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => string = !string,
+            ';' if !string => break,
+            '%' if !string => {
+                let (name, end) = if let Some((_, '"')) = chars.peek() {
+                    chars.next();
+                    let Some(len) = line[i + 2..].find('"') else {
+                        break;
+                    };
+                    for _ in 0..=line[i + 2..i + 2 + len].chars().count() {
+                        chars.next();
+                    }
+                    (
+                        Name::Name(line[i + 2..i + 2 + len].to_string()),
+                        i + 3 + len,
+                    )
+                } else {
+                    let mut end = i + 1;
+                    while let Some(&(j, c)) = chars.peek() {
+                        if !is_name_char(c) {
+                            break;
+                        }
+                        end = j + c.len_utf8();
+                        chars.next();
+                    }
+                    let Ok(name) = line[i + 1..end].parse() else {
+                        continue;
+                    };
+                    (name, end)
+                };
+                locals.push((column(i), column(end) - column(i), name));
+            }
+            _ => {}
+        }
+    }
+    locals
+}
+
+/// Semantic tokens for every value in `text` that has a register, encoded
+/// the way Monaco expects them: line and column relative to the previous
+/// token, length, type and modifiers.
+pub fn tokens(text: &str, registers: &HashMap<String, Assignment>) -> Vec<u32> {
+    let mut data = vec![];
+    let (mut prev_line, mut prev_column) = (0, 0);
+    for s in symbols::symbols(text) {
+        let Some(r) = registers.get(&s.function).and_then(|a| a.get(&s.name)) else {
+            continue;
+        };
+        let delta = if s.line == prev_line {
+            s.column - prev_column
+        } else {
+            s.column
+        };
+        data.extend([s.line - prev_line, delta, s.len, r.0 % PALETTE.len(), 0].map(|v| v as u32));
+        (prev_line, prev_column) = (s.line, s.column);
+    }
+    data
+}
+
+/// The register of `name` in `function`, if it has been assigned one.
+pub fn register_of(function: &str, name: &Name) -> Option<Register> {
+    REGISTERS.with(|r| r.borrow().get(function)?.get(name).copied())
+}
+
+/// Replaces the registers that values are colored by.
+pub fn update(registers: &HashMap<String, Assignment>) {
+    let changed = REGISTERS.with(|r| {
+        let mut r = r.borrow_mut();
+        if *r == *registers {
+            return false;
+        }
+        *r = registers.clone();
+        true
+    });
+    if changed {
+        if let Some(emitter) = CHANGED.with(|c| c.borrow().clone()) {
+            if let Err(e) = call(&emitter, "fire", &[]) {
+                tracing::warn!("failed to refresh semantic tokens: {e:?}");
+            }
+        }
+    }
+}
+
+/// Registers a semantic tokens provider coloring values by register.
+pub fn register(monaco: &JsValue) -> Result<(), JsValue> {
+    let emitter_class: js_sys::Function = get(monaco, "Emitter")?.dyn_into()?;
+    let emitter = js_sys::Reflect::construct(&emitter_class, &js_sys::Array::new())?;
+    CHANGED.with(|c| *c.borrow_mut() = Some(emitter.clone()));
+
+    let legend = js_sys::Object::new();
+    let types: js_sys::Array = (0..PALETTE.len())
+        .map(|i| JsValue::from_str(&format!("register{i}")))
+        .collect();
+    js_sys::Reflect::set(&legend, &JsValue::from_str("tokenTypes"), &types)?;
+    js_sys::Reflect::set(
+        &legend,
+        &JsValue::from_str("tokenModifiers"),
+        &js_sys::Array::new(),
+    )?;
+
+    let get_legend = Closure::<dyn Fn() -> JsValue>::new(move || legend.clone().into());
+    let provide = Closure::<dyn Fn(JsValue) -> JsValue>::new(|model: JsValue| {
+        let text = call(&model, "getValue", &[])
+            .ok()
+            .and_then(|t| t.as_string())
+            .unwrap_or_default();
+        let data = REGISTERS.with(|r| tokens(&text, &r.borrow()));
+        let tokens = js_sys::Object::new();
+        js_sys::Reflect::set(
+            &tokens,
+            &JsValue::from_str("data"),
+            &js_sys::Uint32Array::from(&data[..]),
+        )
+        .unwrap();
+        tokens.into()
+    });
+    let release = Closure::<dyn Fn()>::new(|| {});
+
+    let provider = js_sys::Object::new();
+    js_sys::Reflect::set(
+        &provider,
+        &JsValue::from_str("onDidChange"),
+        &get(&emitter, "event")?,
+    )?;
+    js_sys::Reflect::set(
+        &provider,
+        &JsValue::from_str("getLegend"),
+        get_legend.as_ref(),
+    )?;
+    js_sys::Reflect::set(
+        &provider,
+        &JsValue::from_str("provideDocumentSemanticTokens"),
+        provide.as_ref(),
+    )?;
+    js_sys::Reflect::set(
+        &provider,
+        &JsValue::from_str("releaseDocumentSemanticTokens"),
+        release.as_ref(),
+    )?;
+    get_legend.forget();
+    provide.forget();
+    release.forget();
+
+    call(
+        &get(monaco, "languages")?,
+        "registerDocumentSemanticTokensProvider",
+        &[&JsValue::from_str("llvm"), &provider],
+    )?;
+    Ok(())
+}
+
+#[test]
+fn test_tokens() {
+    assert_eq!(
+        locals(r#"  %x.1 = call i32 (ptr, ...) @printf(ptr @.str, i32 %"a b") ; %2"#),
+        vec![
+            (2, 4, Name::Name("x.1".to_string())),
+            (52, 6, Name::Name("a b".to_string())),
+        ],
+    );
+    assert_eq!(locals(r#"  store [3 x i8] c"%d\0A", ptr %1"#).len(), 1);
+
+    let text = "define i32 @main(i32 %0) {\n  %2 = add i32 %0, %0\n  ret i32 %2\n}\n\ndefine void @f(i32 %0) {\n  ret void\n}\n";
+    let registers = HashMap::from([(
+        "main".to_string(),
+        Assignment::from([
+            (Name::Number(0), Register(1)),
+            // registers past the palette wrap around
+            (Name::Number(2), Register(9)),
+        ]),
+    )]);
+    assert_eq!(
+        tokens(text, &registers),
+        vec![
+            0, 21, 2, 1, 0, // %0 in the params of main
+            1, 2, 2, 1, 0, // %2 =
+            0, 13, 2, 1, 0, // %0,
+            0, 4, 2, 1, 0, // %0
+            1, 10, 2, 1, 0, // ret i32 %2
+        ],
+    );
+}
@@ -0,0 +1,336 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use wasm_bindgen::prelude::*;
+
+use super::{call, get, semantic};
+use crate::alloc::Register;
+use crate::ir::{self, Function, Name};
+use crate::{pressure, source};
+
+thread_local! {
+    // the functions of the last parse, by name
+    static FUNCTIONS: RefCell<HashMap<String, Function>> = RefCell::new(HashMap::new());
+}
+
+/// A mention of a local value in the module text. Lines and columns are
+/// 0-based, columns and lengths in UTF-16 code units.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Symbol {
+    pub function: String,
+    pub name: Name,
+    pub line: usize,
+    pub column: usize,
+    pub len: usize,
+}
+
+/// Every local value mentioned in a function in `text`.
+pub fn symbols(text: &str) -> Vec<Symbol> {
+    let mut symbols = vec![];
+    let mut function = None;
+    for (n, line) in text.lines().enumerate() {
+        if line.starts_with("define") {
+            function = source::function_name(line);
+        }
+        let Some(f) = &function else {
+            continue;
+        };
+        for (column, len, name) in semantic::locals(line) {
+            symbols.push(Symbol {
+                function: f.clone(),
+                name,
+                line: n,
+                column,
+                len,
+            });
+        }
+        if line.starts_with('}') {
+            function = None;
+        }
+    }
+    symbols
+}
+
+/// The symbol at `column` of `line`.
+pub fn at(symbols: &[Symbol], line: usize, column: usize) -> Option<&Symbol> {
+    symbols
+        .iter()
+        .find(|s| s.line == line && (s.column..=s.column + s.len).contains(&column))
+}
+
+/// Where `symbol` is defined: as a param, by an instruction, or as a label.
+/// Returns the line, column and length of the definition.
+pub fn definition(
+    text: &str,
+    symbols: &[Symbol],
+    symbol: &Symbol,
+) -> Option<(usize, usize, usize)> {
+    let lines: Vec<_> = text.lines().collect();
+    let value = symbols.iter().find(|s| {
+        s.function == symbol.function && s.name == symbol.name && {
+            let line = lines[s.line];
+            line.starts_with("define") || {
+                let indent = line.len() - line.trim_start().len();
+                s.column == indent && line[indent..].contains(" = ")
+            }
+        }
+    });
+    if let Some(s) = value {
+        return Some((s.line, s.column, s.len));
+    }
+
+    let label = match &symbol.name {
+        Name::Number(n) => n.to_string(),
+        Name::Name(n) if n.chars().all(semantic::is_name_char) => n.clone(),
+        Name::Name(n) => format!("{n:?}"),
+    };
+    let define = symbols.iter().find(|s| s.function == symbol.function)?.line;
+    lines
+        .iter()
+        .enumerate()
+        .skip(define)
+        .take_while(|(_, l)| !l.starts_with('}'))
+        .find(|(_, l)| l.split(';').next().unwrap_or_default().trim_end() == format!("{label}:"))
+        .map(|(n, _)| (n, 0, label.encode_utf16().count()))
+}
+
+/// Every mention of the value of `symbol`.
+pub fn references<'a>(
+    symbols: &'a [Symbol],
+    symbol: &'a Symbol,
+) -> impl Iterator<Item = &'a Symbol> {
+    symbols
+        .iter()
+        .filter(|s| s.function == symbol.function && s.name == symbol.name)
+}
+
+/// Collapses sorted numbers into ranges like `3-5, 9`.
+fn spans(ns: &[usize]) -> String {
+    let mut spans: Vec<(usize, usize)> = vec![];
+    for &n in ns {
+        match spans.last_mut() {
+            Some((_, end)) if *end + 1 == n => *end = n,
+            _ => spans.push((n, n)),
+        }
+    }
+    spans
+        .iter()
+        .map(|&(a, b)| {
+            if a == b {
+                a.to_string()
+            } else {
+                format!("{a}-{b}")
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Markdown describing `name` in `f`: its type, definition, uses, the lines
+/// it is live on and its register. `lines` maps instructions to lines.
+pub fn hover(
+    f: &Function,
+    name: &Name,
+    lines: Option<&source::Lines>,
+    register: Option<Register>,
+) -> String {
+    let strings = ir::strings(f);
+    let line = |row: usize| lines.and_then(|l| l.insts.get(row)).map(|l| l + 1);
+
+    let mut hover = format!("**{name:?}**");
+    if let Some(ty) = pressure::types(f).get(name) {
+        hover += &format!(": `{}`", ty.name);
+    }
+    hover += "\n\n";
+
+    if f.params.iter().any(|p| &p.name == name) {
+        hover += &format!("parameter of `@{}`\n\n", f.name);
+    } else if let Some(row) = ir::def(f).iter().position(|d| d.contains(name)) {
+        hover += &format!("defined by `{}`\n\n", strings[row].trim());
+    } else if f.basic_blocks.iter().any(|b| &b.name == name) {
+        hover += "basic block\n\n";
+    }
+
+    let uses: Vec<_> = ir::r#use(f)
+        .iter()
+        .enumerate()
+        .filter(|(_, u)| u.contains(name))
+        .map(|(row, _)| row)
+        .collect();
+    if !uses.is_empty() {
+        hover += "used by:\n";
+        for row in uses {
+            hover += &format!("- `{}`\n", strings[row].trim());
+        }
+        hover += "\n";
+    }
+
+    let live: Vec<_> = ir::lva(f)
+        .iter()
+        .enumerate()
+        .filter(|(_, (i, o, _))| i.contains(name) || o.contains(name))
+        .filter_map(|(row, _)| line(row))
+        .collect();
+    if !live.is_empty() {
+        hover += &format!("live on lines {}\n\n", spans(&live));
+    }
+
+    if let Some(r) = register {
+        hover += &format!("register `{r}`\n");
+    }
+    hover.trim_end().to_string()
+}
+
+/// Replaces the functions that hovers describe.
+pub fn update(functions: &[Function]) {
+    FUNCTIONS.with(|fs| {
+        let mut fs = fs.borrow_mut();
+        if fs.len() != functions.len() || functions.iter().any(|f| fs.get(&f.name) != Some(f)) {
+            *fs = functions
+                .iter()
+                .map(|f| (f.name.clone(), f.clone()))
+                .collect();
+        }
+    });
+}
+
+fn position(position: &JsValue) -> Option<(usize, usize)> {
+    let line = get(position, "lineNumber").ok()?.as_f64()? as usize;
+    let column = get(position, "column").ok()?.as_f64()? as usize;
+    Some((line.checked_sub(1)?, column.checked_sub(1)?))
+}
+
+fn range(line: usize, column: usize, len: usize) -> JsValue {
+    js_sys::JSON::parse(&format!(
+        r#"{{ "startLineNumber": {0}, "startColumn": {1}, "endLineNumber": {0}, "endColumn": {2} }}"#,
+        line + 1,
+        column + 1,
+        column + len + 1,
+    ))
+    .unwrap()
+}
+
+fn location(model: &JsValue, range: JsValue) -> JsValue {
+    let location = js_sys::Object::new();
+    js_sys::Reflect::set(&location, &"uri".into(), &get(model, "uri").unwrap()).unwrap();
+    js_sys::Reflect::set(&location, &"range".into(), &range).unwrap();
+    location.into()
+}
+
+/// The text of `model` and the symbol at `position` in it.
+fn lookup(model: &JsValue, position: &JsValue) -> Option<(String, Vec<Symbol>, Symbol)> {
+    let text = call(model, "getValue", &[]).ok()?.as_string()?;
+    let (line, column) = self::position(position)?;
+    let symbols = symbols(&text);
+    let symbol = at(&symbols, line, column)?.clone();
+    Some((text, symbols, symbol))
+}
+
+fn provider(key: &str, f: Closure<dyn Fn(JsValue, JsValue) -> JsValue>) -> JsValue {
+    let provider = js_sys::Object::new();
+    js_sys::Reflect::set(&provider, &JsValue::from_str(key), f.as_ref()).unwrap();
+    f.forget();
+    provider.into()
+}
+
+/// Registers definition, reference and hover providers for values.
+pub fn register(monaco: &JsValue) -> Result<(), JsValue> {
+    let languages = get(monaco, "languages")?;
+    let llvm = JsValue::from_str("llvm");
+
+    let provide_definition = Closure::new(|model: JsValue, position: JsValue| {
+        let Some((text, symbols, symbol)) = lookup(&model, &position) else {
+            return JsValue::NULL;
+        };
+        match definition(&text, &symbols, &symbol) {
+            Some((line, column, len)) => location(&model, range(line, column, len)),
+            None => JsValue::NULL,
+        }
+    });
+    call(
+        &languages,
+        "registerDefinitionProvider",
+        &[&llvm, &provider("provideDefinition", provide_definition)],
+    )?;
+
+    let provide_references = Closure::new(|model: JsValue, position: JsValue| {
+        let Some((_, symbols, symbol)) = lookup(&model, &position) else {
+            return js_sys::Array::new().into();
+        };
+        references(&symbols, &symbol)
+            .map(|s| location(&model, range(s.line, s.column, s.len)))
+            .collect::<js_sys::Array>()
+            .into()
+    });
+    call(
+        &languages,
+        "registerReferenceProvider",
+        &[&llvm, &provider("provideReferences", provide_references)],
+    )?;
+
+    let provide_hover = Closure::new(|model: JsValue, position: JsValue| {
+        let Some((text, _, symbol)) = lookup(&model, &position) else {
+            return JsValue::NULL;
+        };
+        let Some(f) = FUNCTIONS.with(|fs| fs.borrow().get(&symbol.function).cloned()) else {
+            return JsValue::NULL;
+        };
+        let lines = source::functions(&text).remove(&f.name);
+        let register = semantic::register_of(&f.name, &symbol.name);
+        let contents = hover(&f, &symbol.name, lines.as_ref(), register);
+
+        let hover = js_sys::Object::new();
+        let value = js_sys::Object::new();
+        js_sys::Reflect::set(&value, &"value".into(), &JsValue::from_str(&contents)).unwrap();
+        js_sys::Reflect::set(&hover, &"contents".into(), &js_sys::Array::of1(&value)).unwrap();
+        js_sys::Reflect::set(
+            &hover,
+            &"range".into(),
+            &range(symbol.line, symbol.column, symbol.len),
+        )
+        .unwrap();
+        hover.into()
+    });
+    call(
+        &languages,
+        "registerHoverProvider",
+        &[&llvm, &provider("provideHover", provide_hover)],
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn test_symbols() {
+    let text = include_str!("../../examples/ll/for1.ll");
+    let symbols = symbols(text);
+    let lines: Vec<_> = text.lines().collect();
+    let find = |line: usize, name: &str| {
+        let column = lines[line].find(name).unwrap();
+        at(&symbols, line, column).unwrap()
+    };
+
+    // %6 = phi i32 [ %8, %5 ], [ 0, %2 ]
+    let phi = find(18, "%6");
+    assert_eq!(definition(text, &symbols, phi), Some((16, 2, 2)));
+    assert_eq!(
+        references(&symbols, phi)
+            .map(|s| s.line)
+            .collect::<Vec<_>>(),
+        vec![16, 17, 18],
+    );
+
+    // params are defined in the define line
+    let param = find(19, "%0");
+    assert_eq!(
+        definition(text, &symbols, param),
+        Some((8, lines[8].find("%0").unwrap(), 2)),
+    );
+
+    // br i1 %3, label %5, label %4
+    assert_eq!(definition(text, &symbols, find(10, "%4")), Some((12, 0, 1)),);
+    // the entry block has no label
+    assert_eq!(definition(text, &symbols, find(16, "%2")), None);
+
+    assert_eq!(spans(&[1, 2, 3, 5, 7, 8]), "1-3, 5, 7-8");
+}
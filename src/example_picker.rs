@@ -1,34 +1,43 @@
 use dioxus::prelude::*;
 
-macro_rules! test {
-    ($($data:expr),* $(,)?)  => {
-        [ $( ($data, include_str!(concat!("../examples/ll/", $data))) ),* ]
+macro_rules! example {
+    ($ll:expr) => {
+        ($ll, include_str!(concat!("../examples/ll/", $ll)), None)
+    };
+    ($ll:expr, $c:expr) => {
+        (
+            $ll,
+            include_str!(concat!("../examples/ll/", $ll)),
+            Some(include_str!(concat!("../examples/ll/", $c))),
+        )
     };
 }
 
-const EXAMPLES: &[(&str, &str)] = &test![
-    "min.ll",
-    "ret.ll",
-    "for0.ll",
-    "for1.ll",
-    "fib.ll",
-    "brainfuck.ll",
+/// The name, IR and C source of every example.
+const EXAMPLES: &[(&str, &str, Option<&str>)] = &[
+    example!("min.ll"),
+    example!("ret.ll", "ret.c"),
+    example!("for0.ll", "for.c"),
+    example!("for1.ll", "for.c"),
+    example!("fib.ll", "fib.c"),
+    example!("fib.g.ll", "fib.c"),
+    example!("brainfuck.ll", "brainfuck.c"),
 ];
 
 #[component]
-pub fn ExamplePicker(onpick: EventHandler<String>) -> Element {
+pub fn ExamplePicker(onpick: EventHandler<(String, Option<String>)>) -> Element {
     rsx! {
         select {
             id: "example-picker",
             class: "bg-zinc-100 dark:bg-zinc-800 h-12 w-full",
             onchange: move |e: Event<FormData>| {
                 let pick = e.data.value().parse::<usize>().unwrap() - 1;
-                let input = EXAMPLES[pick].1;
+                let (_, input, source) = EXAMPLES[pick];
                 tracing::info!("input changed to: \"{}\"", input);
-                onpick.call(input.to_string());
+                onpick.call((input.to_string(), source.map(str::to_string)));
             },
             option { value: "0", disabled: true, "-- example --" }
-            for (i , (n , _)) in EXAMPLES.iter().enumerate() {
+            for (i , (n , _ , _)) in EXAMPLES.iter().enumerate() {
                 option { key: "{i}", value: "{i + 1}", "{n}" }
             }
         }
@@ -0,0 +1,191 @@
+#![allow(non_snake_case)]
+
+use std::collections::{BTreeSet, HashSet};
+use std::fmt::Write;
+
+use dioxus::prelude::*;
+
+use crate::ir::{Function, Name, OwnedInstLive};
+use crate::{alloc, asm, ir};
+
+fn sorted(s: &HashSet<Name>) -> Vec<String> {
+    s.iter()
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .map(|n| format!("{n:?}"))
+        .collect()
+}
+
+/// The live-in and live-out sets of every instruction as a JSON array.
+pub fn lva_json(lives: &[OwnedInstLive]) -> String {
+    let rows: Vec<_> = lives
+        .iter()
+        .map(|(r#in, out, inst)| {
+            serde_json::json!({
+                "inst": inst.trim(),
+                "in": sorted(r#in),
+                "out": sorted(out),
+            })
+        })
+        .collect();
+    serde_json::to_string_pretty(&rows).unwrap()
+}
+
+fn field(s: &str) -> String {
+    if s.contains([',', '"', '\n']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+/// The live-in and live-out sets of every instruction as CSV, with the
+/// values of each set separated by spaces.
+pub fn lva_csv(lives: &[OwnedInstLive]) -> String {
+    let mut csv = "in,inst,out\n".to_string();
+    for (r#in, out, inst) in lives {
+        writeln!(
+            csv,
+            "{},{},{}",
+            field(&sorted(r#in).join(" ")),
+            field(inst.trim()),
+            field(&sorted(out).join(" ")),
+        )
+        .unwrap();
+    }
+    csv
+}
+
+pub fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// A self-contained page with the CFG, liveness and allocation of `f`.
+pub fn html(f: &Function, svg: &str, lives: &[OwnedInstLive], assignment: &str) -> String {
+    let (mut assignment, precolored) = alloc::parse(assignment).unwrap_or_default();
+    let violations = alloc::check(f, &ir::lva(f), &assignment, &precolored);
+    assignment.extend(precolored);
+
+    let mut html = String::new();
+    writeln!(html, "<!DOCTYPE html>").unwrap();
+    writeln!(html, "<html>").unwrap();
+    writeln!(html, "<head>").unwrap();
+    writeln!(html, "<meta charset=\"utf-8\">").unwrap();
+    writeln!(html, "<title>@{}</title>", escape_html(&f.name)).unwrap();
+    writeln!(
+        html,
+        "<style>body {{ font-family: monospace }} table {{ border-collapse: collapse }} td {{ border: 1px solid #ccc; padding: 0 4px; white-space: pre }} .violation {{ background: #fca5a5 }}</style>",
+    )
+    .unwrap();
+    writeln!(html, "</head>").unwrap();
+    writeln!(html, "<body>").unwrap();
+    writeln!(html, "<h1>@{}</h1>", escape_html(&f.name)).unwrap();
+    writeln!(html, "<h2>CFG</h2>").unwrap();
+    writeln!(html, "{svg}").unwrap();
+    writeln!(html, "<h2>Liveness</h2>").unwrap();
+    writeln!(html, "<table>").unwrap();
+    writeln!(html, "<tr><th>in</th><th>instruction</th><th>out</th></tr>").unwrap();
+    for (j, (r#in, out, inst)) in lives.iter().enumerate() {
+        let class = if violations.iter().any(|v| v.index() == j) {
+            " class=\"violation\""
+        } else {
+            ""
+        };
+        writeln!(
+            html,
+            "<tr{class}><td>{}</td><td>{}</td><td>{}</td></tr>",
+            escape_html(&sorted(r#in).join(" ")),
+            escape_html(inst.trim()),
+            escape_html(&sorted(out).join(" ")),
+        )
+        .unwrap();
+    }
+    writeln!(html, "</table>").unwrap();
+    writeln!(html, "<h2>Allocation</h2>").unwrap();
+    if !violations.is_empty() {
+        writeln!(html, "<ul>").unwrap();
+        for v in &violations {
+            writeln!(
+                html,
+                "<li>{}: {}</li>",
+                v.index(),
+                escape_html(&v.to_string())
+            )
+            .unwrap();
+        }
+        writeln!(html, "</ul>").unwrap();
+    }
+    writeln!(html, "<pre>").unwrap();
+    for line in asm::select(f, &assignment) {
+        writeln!(html, "{}", escape_html(&line.text)).unwrap();
+    }
+    writeln!(html, "</pre>").unwrap();
+    writeln!(html, "</body>").unwrap();
+    writeln!(html, "</html>").unwrap();
+    html
+}
+
+#[component]
+pub fn Download(name: String, mime: String, data: String) -> Element {
+    let data = String::from(js_sys::encode_uri_component(&data));
+    rsx! {
+        a {
+            class: "ml-1 underline",
+            href: "data:{mime};charset=utf-8,{data}",
+            download: "{name}",
+            "{name}"
+        }
+    }
+}
+
+#[test]
+fn test_export() {
+    let n = |n| Name::Number(n);
+    let lives = vec![
+        (
+            HashSet::from([n(0)]),
+            HashSet::from([n(0), n(2)]),
+            "  %2 = add i32 %0, 1".to_string(),
+        ),
+        (
+            HashSet::from([n(2), n(0)]),
+            HashSet::new(),
+            "  ret i32 %2".to_string(),
+        ),
+    ];
+
+    assert_eq!(
+        lva_json(&lives),
+        r#"[
+  {
+    "in": [
+      "%0"
+    ],
+    "inst": "%2 = add i32 %0, 1",
+    "out": [
+      "%0",
+      "%2"
+    ]
+  },
+  {
+    "in": [
+      "%0",
+      "%2"
+    ],
+    "inst": "ret i32 %2",
+    "out": []
+  }
+]"#,
+    );
+    assert_eq!(
+        lva_csv(&lives),
+        "in,inst,out\n%0,\"%2 = add i32 %0, 1\",%0 %2\n%0 %2,ret i32 %2,\n",
+    );
+    assert_eq!(
+        escape_html("<a href=\"&\">"),
+        "&lt;a href=&quot;&amp;&quot;&gt;"
+    );
+}
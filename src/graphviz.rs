@@ -1,31 +1,151 @@
+use std::cell::RefCell;
+use std::fmt;
+
 use wasm_bindgen::prelude::*;
 
-pub async fn svg(
-    dot: &petgraph::dot::Dot<'_, &'_ petgraph::graph::DiGraph<&'_ crate::ir::Name, ()>>,
-) -> String {
-    let cfg_dot = format!("{:?}", dot);
-
-    let window = web_sys::window().unwrap();
-    let hpccWasm = js_sys::Reflect::get(&window, &JsValue::from_str("@hpcc-js/wasm")).unwrap();
-
-    tracing::info!("{hpccWasm:?}");
-    let graphviz = js_sys::Reflect::get(&hpccWasm, &JsValue::from_str("Graphviz")).unwrap();
-    tracing::info!("{graphviz:?}");
-    let load = js_sys::Reflect::get(&graphviz, &JsValue::from_str("load")).unwrap();
-    let load: &js_sys::Function = load.dyn_ref().unwrap();
-    let promise: js_sys::Promise = load.call0(&graphviz).unwrap().dyn_into().unwrap();
-    tracing::info!("{promise:?}");
-    let graphviz = wasm_bindgen_futures::JsFuture::from(promise).await.unwrap();
-    let dot = js_sys::Reflect::get(&graphviz, &JsValue::from_str("dot")).unwrap();
-    let dot: &js_sys::Function = dot.dyn_ref().unwrap();
-    tracing::info!("{dot:?}");
-
-    let cfg: JsValue = dot
-        .call1(&graphviz, &JsValue::from_str(&cfg_dot))
-        .unwrap()
-        .dyn_into()
-        .unwrap();
-
-    let svg = cfg.dyn_ref::<js_sys::JsString>().unwrap().to_string();
-    svg.into()
+#[derive(
+    Clone, Copy, Debug, Default, Eq, Hash, PartialEq, serde::Deserialize, serde::Serialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum Engine {
+    #[default]
+    Dot,
+    Neato,
+    Circo,
+}
+
+impl Engine {
+    pub const ALL: [Engine; 3] = [Engine::Dot, Engine::Neato, Engine::Circo];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Engine::Dot => "dot",
+            Engine::Neato => "neato",
+            Engine::Circo => "circo",
+        }
+    }
+}
+
+impl std::str::FromStr for Engine {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Engine::ALL
+            .into_iter()
+            .find(|e| e.name() == s)
+            .ok_or_else(|| format!("unknown layout engine: {s:?}"))
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Format {
+    #[default]
+    Svg,
+    Json,
+    Plain,
+}
+
+impl Format {
+    pub const ALL: [Format; 3] = [Format::Svg, Format::Json, Format::Plain];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Format::Svg => "svg",
+            Format::Json => "json",
+            Format::Plain => "plain",
+        }
+    }
+
+    pub fn mime(self) -> &'static str {
+        match self {
+            Format::Svg => "image/svg+xml",
+            Format::Json => "application/json",
+            Format::Plain => "text/plain",
+        }
+    }
+}
+
+/// Anything that can be rendered by Graphviz.
+pub trait ToDot {
+    fn to_dot(&self) -> String;
+}
+
+impl ToDot for str {
+    fn to_dot(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl ToDot for String {
+    fn to_dot(&self) -> String {
+        self.clone()
+    }
+}
+
+impl<N, E, Ty, Ix> ToDot for petgraph::Graph<N, E, Ty, Ix>
+where
+    N: fmt::Debug,
+    E: fmt::Debug,
+    Ty: petgraph::EdgeType,
+    Ix: petgraph::graph::IndexType,
+{
+    fn to_dot(&self) -> String {
+        format!(
+            "{:?}",
+            petgraph::dot::Dot::with_config(self, &[petgraph::dot::Config::EdgeNoLabel])
+        )
+    }
+}
+
+thread_local! {
+    // the promise returned by Graphviz.load(), shared by every render until it
+    // is rejected
+    static GRAPHVIZ: RefCell<Option<js_sys::Promise>> = const { RefCell::new(None) };
+}
+
+fn get(target: &JsValue, key: &str) -> Result<JsValue, JsValue> {
+    let value = js_sys::Reflect::get(target, &JsValue::from_str(key))?;
+    if value.is_undefined() {
+        Err(JsValue::from_str(&format!("{key} is undefined")))
+    } else {
+        Ok(value)
+    }
+}
+
+fn load() -> Result<js_sys::Promise, JsValue> {
+    if let Some(promise) = GRAPHVIZ.with(|g| g.borrow().clone()) {
+        return Ok(promise);
+    }
+
+    let window = web_sys::window().ok_or_else(|| JsValue::from_str("no window"))?;
+    let graphviz = get(&get(&window, "@hpcc-js/wasm")?, "Graphviz")?;
+    let load: js_sys::Function = get(&graphviz, "load")?.dyn_into()?;
+    let promise: js_sys::Promise = load.call0(&graphviz)?.dyn_into()?;
+    tracing::info!("loading graphviz");
+
+    GRAPHVIZ.with(|g| *g.borrow_mut() = Some(promise.clone()));
+    Ok(promise)
+}
+
+/// Lays out `src` with `engine` and renders it as `format`.
+pub async fn render(
+    src: &(impl ToDot + ?Sized),
+    engine: Engine,
+    format: Format,
+) -> Result<String, JsValue> {
+    let graphviz = wasm_bindgen_futures::JsFuture::from(load()?)
+        .await
+        .inspect_err(|_| {
+            // let the next render try to load it again
+            GRAPHVIZ.with(|g| *g.borrow_mut() = None);
+        })?;
+    let layout: js_sys::Function = get(&graphviz, "layout")?.dyn_into()?;
+    let out = layout.call3(
+        &graphviz,
+        &JsValue::from_str(&src.to_dot()),
+        &JsValue::from_str(format.name()),
+        &JsValue::from_str(engine.name()),
+    )?;
+    out.as_string()
+        .ok_or_else(|| JsValue::from_str("layout did not return a string"))
 }
@@ -7,9 +7,14 @@ use petgraph::graph::{DiGraph, NodeIndex};
 pub struct Module {
     #[serde(rename = "FunctionList")]
     pub functions: Vec<Function>,
+    // the rest is read from the text of the module rather than the JSON
+    #[serde(skip)]
+    pub info: crate::module::Info,
+    #[serde(skip)]
+    pub debug: crate::debug::Info,
 }
 
-#[derive(Clone, Debug, serde::Deserialize)]
+#[derive(Clone, Debug, PartialEq, serde::Deserialize)]
 pub struct Function {
     #[serde(rename = "GlobalIdentifier")]
     pub name: String,
@@ -35,7 +40,22 @@ impl fmt::Debug for Name {
     }
 }
 
-#[derive(Clone, Debug, serde::Deserialize)]
+impl std::str::FromStr for Name {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.strip_prefix('%').unwrap_or(s);
+        if s.is_empty() {
+            Err("empty name".to_string())
+        } else if let Ok(n) = s.parse() {
+            Ok(Name::Number(n))
+        } else {
+            Ok(Name::Name(s.to_string()))
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Deserialize)]
 pub struct Type {
     #[serde(rename = "ID")]
     pub id: usize,
@@ -44,7 +64,7 @@ pub struct Type {
     pub name: String,
 }
 
-#[derive(Clone, Debug, serde::Deserialize)]
+#[derive(Clone, Debug, PartialEq, serde::Deserialize)]
 pub struct Param {
     #[serde(rename = "Name")]
     #[allow(dead_code)]
@@ -54,7 +74,7 @@ pub struct Param {
     pub ty: Type,
 }
 
-#[derive(Clone, Debug, serde::Deserialize)]
+#[derive(Clone, Debug, PartialEq, serde::Deserialize)]
 pub struct BasicBlock {
     #[serde(rename = "Name")]
     pub name: Name,
@@ -64,7 +84,7 @@ pub struct BasicBlock {
     pub term: Terminator,
 }
 
-#[derive(Clone, Debug, serde::Deserialize)]
+#[derive(Clone, Debug, PartialEq, serde::Deserialize)]
 pub struct Operand {
     #[serde(rename = "Constant")]
     pub constant: bool,
@@ -72,9 +92,13 @@ pub struct Operand {
     pub name: Option<Name>,
     #[serde(rename = "Type")]
     pub ty: Type,
+    /// The text of a constant, like `0`, `null` or `@.str`, as read from the
+    /// instruction by `text::read`.
+    #[serde(skip)]
+    pub value: Option<String>,
 }
 
-#[derive(Clone, Debug, serde::Deserialize)]
+#[derive(Clone, Debug, PartialEq, serde::Deserialize)]
 pub struct Instruction {
     #[serde(rename = "Opcode")]
     pub opcode: usize,
@@ -86,9 +110,17 @@ pub struct Instruction {
     pub blocks: Option<Vec<Name>>,
     #[serde(rename = "String")]
     pub string: String,
+    /// The type an instruction is about besides those of its operands: the
+    /// allocated type of an alloca, the result type of a load, a cast or a
+    /// call, or the source element type of a getelementptr.
+    #[serde(skip)]
+    pub ty: Option<String>,
+    /// The condition of an icmp or fcmp, like `slt`.
+    #[serde(skip)]
+    pub predicate: Option<String>,
 }
 
-#[derive(Clone, Debug, serde::Deserialize)]
+#[derive(Clone, Debug, PartialEq, serde::Deserialize)]
 pub struct Terminator {
     #[serde(rename = "Opcode")]
     pub opcode: usize,
@@ -201,7 +233,27 @@ fn init_lives(f: &Function) -> Vec<(HashSet<&Name>, HashSet<&Name>, &str)> {
     lives
 }
 
-fn r#use(f: &Function) -> Vec<HashSet<&Name>> {
+/// The text of every instruction in `f`, in the same order as `lva`.
+pub fn strings(f: &Function) -> Vec<&str> {
+    init_lives(f).into_iter().map(|(_, _, s)| s).collect()
+}
+
+/// The live-in and live-out sets of every block of `f`, taken from the first
+/// and last instruction of each block in `lives`.
+pub fn block_lives(f: &Function, lives: &[OwnedInstLive]) -> Vec<(HashSet<Name>, HashSet<Name>)> {
+    let mut j = 0;
+    f.basic_blocks
+        .iter()
+        .map(|b| {
+            let r#in = lives.get(j).map(|l| l.0.clone()).unwrap_or_default();
+            j += b.insts.len() + 1;
+            let out = lives.get(j - 1).map(|l| l.1.clone()).unwrap_or_default();
+            (r#in, out)
+        })
+        .collect()
+}
+
+pub fn r#use(f: &Function) -> Vec<HashSet<&Name>> {
     tracing::trace!("use {}", f.name);
 
     let (_blocks, _cfg) = cfg(f);
@@ -273,7 +325,10 @@ pub fn lva(f: &Function) -> Vec<(HashSet<&Name>, HashSet<&Name>, &str)> {
         f.basic_blocks.iter().map(|b| b.insts.len() + 1).sum()
     ];
 
-    for _ in 0..10 {
+    // every round moves the lives back by one instruction, so round until
+    // nothing changes
+    loop {
+        let old = lives.clone();
         for j in (0..lives.len()).rev() {
             let i = j + f.params.len();
             let (block_idx, block) = block_indices
@@ -367,114 +422,133 @@ pub fn lva(f: &Function) -> Vec<(HashSet<&Name>, HashSet<&Name>, &str)> {
                 }
             }
         }
+        if lives == old {
+            return lives;
+        }
     }
+}
+
+/// `lives` from `lva`, owning the names and strings they borrow.
+pub fn owned(lives: &[(HashSet<&Name>, HashSet<&Name>, &str)]) -> Vec<OwnedInstLive> {
     lives
+        .iter()
+        .map(|(r#in, out, s)| {
+            (
+                r#in.iter().map(|&n| n.clone()).collect(),
+                out.iter().map(|&n| n.clone()).collect(),
+                s.to_string(),
+            )
+        })
+        .collect()
+}
+
+/// `lva`, owning the names and strings it borrows from `f`.
+pub fn owned_lva(f: &Function) -> Vec<OwnedInstLive> {
+    owned(&lva(f))
+}
+
+// min.ll, with the parameters of main named and returned
+#[cfg(test)]
+fn min() -> Function {
+    use crate::builder::Builder;
+
+    let mut b = Builder::new("main");
+    let argc = b.param("i32");
+    let argv = b.param("ptr");
+    b.name(&argc, "argc");
+    b.name(&argv, "argv");
+    b.ret(Some(&argc));
+    b.build()
+}
+
+// ret.ll
+#[cfg(test)]
+fn ret() -> Function {
+    use crate::builder::{constant, Builder};
+
+    let mut b = Builder::new("main");
+    let retval = b.alloca("i32");
+    b.store(&constant("i32", 0), &retval);
+    b.ret(Some(&constant("i32", 42)));
+    b.build()
+}
+
+// for0.ll
+#[cfg(test)]
+fn for0() -> Function {
+    use crate::builder::{constant, global, Builder};
+
+    let mut b = Builder::new("main");
+    let argc = b.param("i32");
+    let argv = b.param("ptr");
+    let (cond, body, inc, end) = (b.block(), b.block(), b.block(), b.block());
+    let retval = b.alloca("i32");
+    let argc_addr = b.alloca("i32");
+    let argv_addr = b.alloca("ptr");
+    let i = b.alloca("i32");
+    b.store(&constant("i32", 0), &retval);
+    b.store(&argc, &argc_addr);
+    b.store(&argv, &argv_addr);
+    b.store(&constant("i32", 0), &i);
+    b.br(cond);
+
+    b.position(cond);
+    let x = b.load("i32", &i);
+    let n = b.load("i32", &argc_addr);
+    let lt = b.icmp("slt", &x, &n);
+    b.cond_br(&lt, body, end);
+
+    b.position(body);
+    let x = b.load("i32", &i);
+    b.call("i32 (ptr, ...)", &global("printf"), &[&global(".str"), &x]);
+    b.br(inc);
+
+    b.position(inc);
+    let x = b.load("i32", &i);
+    let x = b.binary("add", &x, &constant("i32", 1));
+    b.store(&x, &i);
+    b.br(cond);
+
+    b.position(end);
+    let x = b.load("i32", &retval);
+    b.ret(Some(&x));
+    b.build()
+}
+
+// for1.ll
+#[cfg(test)]
+pub fn for1() -> Function {
+    use crate::builder::{constant, global, Builder};
+
+    let mut b = Builder::new("main");
+    let n = b.param("i32");
+    b.param("ptr");
+    let entry = b.current();
+    let (end, body) = (b.block(), b.block());
+    let gt = b.icmp("sgt", &n, &constant("i32", 0));
+    b.cond_br(&gt, body, end);
+
+    b.position(end);
+    b.ret(Some(&constant("i32", 0)));
+
+    b.position(body);
+    let i = b.phi("i32");
+    b.call("i32 (ptr, ...)", &global("printf"), &[&global(".str"), &i]);
+    let next = b.binary("add", &i, &constant("i32", 1));
+    let eq = b.icmp("eq", &next, &n);
+    b.cond_br(&eq, end, body);
+    b.incoming(&i, &next, body);
+    b.incoming(&i, &constant("i32", 0), entry);
+    b.build()
 }
 
 #[test]
 fn test_def() {
     // min.ll
-    assert_eq!(
-        def(&Function {
-            name: "main".to_string(),
-            params: vec![
-                Param {
-                    name: Name::Name("argc".to_string()),
-                    ty: Type {
-                        id: 13,
-                        name: "i32".to_string(),
-                    },
-                },
-                Param {
-                    name: Name::Name("argv".to_string()),
-                    ty: Type {
-                        id: 15,
-                        name: "ptr".to_string(),
-                    },
-                },
-            ],
-            basic_blocks: vec![BasicBlock {
-                name: Name::Number(0),
-                insts: vec![],
-                term: Terminator {
-                    opcode: 1,
-                    def: None,
-                    uses: vec![Operand {
-                        constant: false,
-                        name: Some(Name::Name("argc".to_string())),
-                        ty: Type {
-                            id: 13,
-                            name: "i32".to_string(),
-                        },
-                    }],
-                    string: "  ret void".to_string(),
-                },
-            }],
-        }),
-        vec![HashSet::new()],
-    );
+    assert_eq!(def(&min()), vec![HashSet::new()]);
     // ret.ll
     assert_eq!(
-        def(&Function {
-            name: "main".to_string(),
-            params: vec![],
-            basic_blocks: vec![BasicBlock {
-                name: Name::Number(0),
-                insts: vec![
-                    Instruction {
-                        opcode: 31,
-                        def: Some(Name::Number(1),),
-                        uses: vec![Operand {
-                            constant: true,
-                            name: None,
-                            ty: Type {
-                                id: 13,
-                                name: "i32".to_string(),
-                            },
-                        },],
-                        blocks: None,
-                        string: "  %1 = alloca i32, align 4".to_string(),
-                    },
-                    Instruction {
-                        opcode: 33,
-                        def: None,
-                        uses: vec![
-                            Operand {
-                                constant: true,
-                                name: None,
-                                ty: Type {
-                                    id: 13,
-                                    name: "i32".to_string(),
-                                },
-                            },
-                            Operand {
-                                constant: false,
-                                name: Some(Name::Number(1),),
-                                ty: Type {
-                                    id: 15,
-                                    name: "ptr".to_string(),
-                                },
-                            },
-                        ],
-                        blocks: None,
-                        string: "  store i32 0, ptr %1, align 4".to_string(),
-                    },
-                ],
-                term: Terminator {
-                    opcode: 1,
-                    def: None,
-                    uses: vec![Operand {
-                        constant: true,
-                        name: None,
-                        ty: Type {
-                            id: 13,
-                            name: "i32".to_string(),
-                        },
-                    },],
-                    string: "  ret i32 42".to_string(),
-                },
-            },],
-        }),
+        def(&ret()),
         vec![
             HashSet::from([&Name::Number(1)]),
             HashSet::new(),
@@ -483,559 +557,7 @@ fn test_def() {
     );
     // for0.ll
     assert_eq!(
-    def(&Function {
-            name: "main".to_string(),
-            params: vec![
-                Param {
-                    name: Name::Number(0),
-                    ty: Type {
-                        id: 13,
-                        name: "i32".to_string(),
-                    },
-                },
-                Param {
-                    name: Name::Number(1),
-                    ty: Type {
-                        id: 15,
-                        name: "ptr".to_string(),
-                    },
-                },
-            ],
-            basic_blocks: vec![
-                BasicBlock {
-                    name: Name::Number(2),
-                    insts: vec![
-                        Instruction {
-                            opcode: 31,
-                            def: Some(
-                                Name::Number(3),
-                            ),
-                            uses: vec![
-                                Operand {
-                                    constant: true,
-                                    name: None,
-                                    ty: Type {
-                                        id: 13,
-                                        name: "i32".to_string(),
-                                    },
-                                },
-                            ],
-                            blocks: None,
-                            string: "  %3 = alloca i32, align 4".to_string(),
-                        },
-                        Instruction {
-                            opcode: 31,
-                            def: Some(
-                                Name::Number(4),
-                            ),
-                            uses: vec![
-                                Operand {
-                                    constant: true,
-                                    name: None,
-                                    ty: Type {
-                                        id: 13,
-                                        name: "i32".to_string(),
-                                    },
-                                },
-                            ],
-                            blocks: None,
-                            string: "  %4 = alloca i32, align 4".to_string(),
-                        },
-                        Instruction {
-                            opcode: 31,
-                            def: Some(
-                                Name::Number(5),
-                            ),
-                            uses: vec![
-                                Operand {
-                                    constant: true,
-                                    name: None,
-                                    ty: Type {
-                                        id: 13,
-                                        name: "i32".to_string(),
-                                    },
-                                },
-                            ],
-                            blocks: None,
-                            string: "  %5 = alloca ptr, align 8".to_string(),
-                        },
-                        Instruction {
-                            opcode: 31,
-                            def: Some(
-                                Name::Number(6),
-                            ),
-                            uses: vec![
-                                Operand {
-                                    constant: true,
-                                    name: None,
-                                    ty: Type {
-                                        id: 13,
-                                        name: "i32".to_string(),
-                                    },
-                                },
-                            ],
-                            blocks: None,
-                            string: "  %6 = alloca i32, align 4".to_string(),
-                        },
-                        Instruction {
-                            opcode: 33,
-                            def: None,
-                            uses: vec![
-                                Operand {
-                                    constant: true,
-                                    name: None,
-                                    ty: Type {
-                                        id: 13,
-                                        name: "i32".to_string(),
-                                    },
-                                },
-                                Operand {
-                                    constant: false,
-                                    name: Some(
-                                        Name::Number(3),
-                                    ),
-                                    ty: Type {
-                                        id: 15,
-                                        name: "ptr".to_string(),
-                                    },
-                                },
-                            ],
-                            blocks: None,
-                            string: "  store i32 0, ptr %3, align 4".to_string(),
-                        },
-                        Instruction {
-                            opcode: 33,
-                            def: None,
-                            uses: vec![
-                                Operand {
-                                    constant: false,
-                                    name: Some(
-                                        Name::Number(0),
-                                    ),
-                                    ty: Type {
-                                        id: 13,
-                                        name: "i32".to_string(),
-                                    },
-                                },
-                                Operand {
-                                    constant: false,
-                                    name: Some(
-                                        Name::Number(4),
-                                    ),
-                                    ty: Type {
-                                        id: 15,
-                                        name: "ptr".to_string(),
-                                    },
-                                },
-                            ],
-                            blocks: None,
-                            string: "  store i32 %0, ptr %4, align 4".to_string(),
-                        },
-                        Instruction {
-                            opcode: 33,
-                            def: None,
-                            uses: vec![
-                                Operand {
-                                    constant: false,
-                                    name: Some(
-                                        Name::Number(1),
-                                    ),
-                                    ty: Type {
-                                        id: 15,
-                                        name: "ptr".to_string(),
-                                    },
-                                },
-                                Operand {
-                                    constant: false,
-                                    name: Some(
-                                        Name::Number(5),
-                                    ),
-                                    ty: Type {
-                                        id: 15,
-                                        name: "ptr".to_string(),
-                                    },
-                                },
-                            ],
-                            blocks: None,
-                            string: "  store ptr %1, ptr %5, align 8".to_string(),
-                        },
-                        Instruction {
-                            opcode: 33,
-                            def: None,
-                            uses: vec![
-                                Operand {
-                                    constant: true,
-                                    name: None,
-                                    ty: Type {
-                                        id: 13,
-                                        name: "i32".to_string(),
-                                    },
-                                },
-                                Operand {
-                                    constant: false,
-                                    name: Some(
-                                        Name::Number(6),
-                                    ),
-                                    ty: Type {
-                                        id: 15,
-                                        name: "ptr".to_string(),
-                                    },
-                                },
-                            ],
-                            blocks: None,
-                            string: "  store i32 0, ptr %6, align 4".to_string(),
-                        },
-                    ],
-                    term: Terminator {
-                        opcode: 2,
-                        def: None,
-                        uses: vec![
-                            Operand {
-                                constant: false,
-                                name: Some(
-                                    Name::Number(7),
-                                ),
-                                ty: Type {
-                                    id: 8,
-                                    name: "label".to_string(),
-                                },
-                            },
-                        ],
-                        string: "  br label %7".to_string(),
-                    },
-                },
-                BasicBlock {
-                    name: Name::Number(7),
-                    insts: vec![
-                        Instruction {
-                            opcode: 32,
-                            def: Some(
-                                Name::Number(8),
-                            ),
-                            uses: vec![
-                                Operand {
-                                    constant: false,
-                                    name: Some(
-                                        Name::Number(6),
-                                    ),
-                                    ty: Type {
-                                        id: 15,
-                                        name: "ptr".to_string(),
-                                    },
-                                },
-                            ],
-                            blocks: None,
-                            string: "  %8 = load i32, ptr %6, align 4".to_string(),
-                        },
-                        Instruction {
-                            opcode: 32,
-                            def: Some(
-                                Name::Number(9),
-                            ),
-                            uses: vec![
-                                Operand {
-                                    constant: false,
-                                    name: Some(
-                                        Name::Number(4),
-                                    ),
-                                    ty: Type {
-                                        id: 15,
-                                        name: "ptr".to_string(),
-                                    },
-                                },
-                            ],
-                            blocks: None,
-                            string: "  %9 = load i32, ptr %4, align 4".to_string(),
-                        },
-                        Instruction {
-                            opcode: 53,
-                            def: Some(
-                                Name::Number(10),
-                            ),
-                            uses: vec![
-                                Operand {
-                                    constant: false,
-                                    name: Some(
-                                        Name::Number(8),
-                                    ),
-                                    ty: Type {
-                                        id: 13,
-                                        name: "i32".to_string(),
-                                    },
-                                },
-                                Operand {
-                                    constant: false,
-                                    name: Some(
-                                        Name::Number(9),
-                                    ),
-                                    ty: Type {
-                                        id: 13,
-                                        name: "i32".to_string(),
-                                    },
-                                },
-                            ],
-                            blocks: None,
-                            string: "  %10 = icmp slt i32 %8, %9".to_string(),
-                        },
-                    ],
-                    term: Terminator {
-                        opcode: 2,
-                        def: None,
-                        uses: vec![
-                            Operand {
-                                constant: false,
-                                name: Some(
-                                    Name::Number(10),
-                                ),
-                                ty: Type {
-                                    id: 13,
-                                    name: "i1".to_string(),
-                                },
-                            },
-                            Operand {
-                                constant: false,
-                                name: Some(
-                                    Name::Number(17),
-                                ),
-                                ty: Type {
-                                    id: 8,
-                                    name: "label".to_string(),
-                                },
-                            },
-                            Operand {
-                                constant: false,
-                                name: Some(
-                                    Name::Number(11),
-                                ),
-                                ty: Type {
-                                    id: 8,
-                                    name: "label".to_string(),
-                                },
-                            },
-                        ],
-                        string: "  br i1 %10, label %11, label %17".to_string(),
-                    },
-                },
-                BasicBlock {
-                    name: Name::Number(11),
-                    insts: vec![
-                        Instruction {
-                            opcode: 32,
-                            def: Some(
-                                Name::Number(12),
-                            ),
-                            uses: vec![
-                                Operand {
-                                    constant: false,
-                                    name: Some(
-                                        Name::Number(6),
-                                    ),
-                                    ty: Type {
-                                        id: 15,
-                                        name: "ptr".to_string(),
-                                    },
-                                },
-                            ],
-                            blocks: None,
-                            string: "  %12 = load i32, ptr %6, align 4".to_string(),
-                        },
-                        Instruction {
-                            opcode: 56,
-                            def: Some(
-                                Name::Number(13),
-                            ),
-                            uses: vec![
-                                Operand {
-                                    constant: true,
-                                    name: None,
-                                    ty: Type {
-                                        id: 15,
-                                        name: "ptr".to_string(),
-                                    },
-                                },
-                                Operand {
-                                    constant: false,
-                                    name: Some(
-                                        Name::Number(12),
-                                    ),
-                                    ty: Type {
-                                        id: 13,
-                                        name: "i32".to_string(),
-                                    },
-                                },
-                                Operand {
-                                    constant: true,
-                                    name: None,
-                                    ty: Type {
-                                        id: 15,
-                                        name: "ptr".to_string(),
-                                    },
-                                },
-                            ],
-                            blocks: None,
-                            string: "  %13 = call i32 (ptr, ...) @printf(ptr noundef @.str, i32 noundef %12)".to_string(),
-                        },
-                    ],
-                    term: Terminator {
-                        opcode: 2,
-                        def: None,
-                        uses: vec![
-                            Operand {
-                                constant: false,
-                                name: Some(
-                                    Name::Number(14),
-                                ),
-                                ty: Type {
-                                    id: 8,
-                                    name: "label".to_string(),
-                                },
-                            },
-                        ],
-                        string: "  br label %14".to_string(),
-                    },
-                },
-                BasicBlock {
-                    name: Name::Number(14),
-                    insts: vec![
-                        Instruction {
-                            opcode: 32,
-                            def: Some(
-                                Name::Number(15),
-                            ),
-                            uses: vec![
-                                Operand {
-                                    constant: false,
-                                    name: Some(
-                                        Name::Number(6),
-                                    ),
-                                    ty: Type {
-                                        id: 15,
-                                        name: "ptr".to_string(),
-                                    },
-                                },
-                            ],
-                            blocks: None,
-                            string: "  %15 = load i32, ptr %6, align 4".to_string(),
-                        },
-                        Instruction {
-                            opcode: 13,
-                            def: Some(
-                                Name::Number(16),
-                            ),
-                            uses: vec![
-                                Operand {
-                                    constant: false,
-                                    name: Some(
-                                        Name::Number(15),
-                                    ),
-                                    ty: Type {
-                                        id: 13,
-                                        name: "i32".to_string(),
-                                    },
-                                },
-                                Operand {
-                                    constant: true,
-                                    name: None,
-                                    ty: Type {
-                                        id: 13,
-                                        name: "i32".to_string(),
-                                    },
-                                },
-                            ],
-                            blocks: None,
-                            string: "  %16 = add nsw i32 %15, 1".to_string(),
-                        },
-                        Instruction {
-                            opcode: 33,
-                            def: None,
-                            uses: vec![
-                                Operand {
-                                    constant: false,
-                                    name: Some(
-                                        Name::Number(16),
-                                    ),
-                                    ty: Type {
-                                        id: 13,
-                                        name: "i32".to_string(),
-                                    },
-                                },
-                                Operand {
-                                    constant: false,
-                                    name: Some(
-                                        Name::Number(6),
-                                    ),
-                                    ty: Type {
-                                        id: 15,
-                                        name: "ptr".to_string(),
-                                    },
-                                },
-                            ],
-                            blocks: None,
-                            string: "  store i32 %16, ptr %6, align 4".to_string(),
-                        },
-                    ],
-                    term: Terminator {
-                        opcode: 2,
-                        def: None,
-                        uses: vec![
-                            Operand {
-                                constant: false,
-                                name: Some(
-                                    Name::Number(7),
-                                ),
-                                ty: Type {
-                                    id: 8,
-                                    name: "label".to_string(),
-                                },
-                            },
-                        ],
-                        string: "  br label %7, !llvm.loop !5".to_string(),
-                    },
-                },
-                BasicBlock {
-                    name: Name::Number(17),
-                    insts: vec![
-                        Instruction {
-                            opcode: 32,
-                            def: Some(
-                                Name::Number(18),
-                            ),
-                            uses: vec![
-                                Operand {
-                                    constant: false,
-                                    name: Some(
-                                        Name::Number(3),
-                                    ),
-                                    ty: Type {
-                                        id: 15,
-                                        name: "ptr".to_string(),
-                                    },
-                                },
-                            ],
-                            blocks: None,
-                            string: "  %18 = load i32, ptr %3, align 4".to_string(),
-                        },
-                    ],
-                    term: Terminator {
-                        opcode: 1,
-                        def: None,
-                        uses: vec![
-                            Operand {
-                                constant: false,
-                                name: Some(
-                                    Name::Number(18),
-                                ),
-                                ty: Type {
-                                    id: 13,
-                                    name: "i32".to_string(),
-                                },
-                            },
-                        ],
-                        string: "  ret i32 %18".to_string(),
-                    },
-                },
-            ],
-        }),
+        def(&for0()),
         vec![
             HashSet::from([&Name::Number(3)]),
             HashSet::from([&Name::Number(4)]),
@@ -1067,106 +589,12 @@ fn test_def() {
 fn test_use() {
     // min.ll
     assert_eq!(
-        r#use(&Function {
-            name: "main".to_string(),
-            params: vec![
-                Param {
-                    name: Name::Name("argc".to_string()),
-                    ty: Type {
-                        id: 13,
-                        name: "i32".to_string(),
-                    },
-                },
-                Param {
-                    name: Name::Name("argv".to_string()),
-                    ty: Type {
-                        id: 15,
-                        name: "ptr".to_string(),
-                    },
-                },
-            ],
-            basic_blocks: vec![BasicBlock {
-                name: Name::Number(0),
-                insts: vec![],
-                term: Terminator {
-                    opcode: 1,
-                    def: None,
-                    uses: vec![Operand {
-                        constant: false,
-                        name: Some(Name::Name("argc".to_string())),
-                        ty: Type {
-                            id: 13,
-                            name: "i32".to_string(),
-                        },
-                    }],
-                    string: "  ret void".to_string(),
-                },
-            }],
-        }),
+        r#use(&min()),
         vec![HashSet::from([&Name::Name("argc".to_string())])],
     );
     // ret.ll
     assert_eq!(
-        r#use(&Function {
-            name: "main".to_string(),
-            params: vec![],
-            basic_blocks: vec![BasicBlock {
-                name: Name::Number(0),
-                insts: vec![
-                    Instruction {
-                        opcode: 31,
-                        def: Some(Name::Number(1),),
-                        uses: vec![Operand {
-                            constant: true,
-                            name: None,
-                            ty: Type {
-                                id: 13,
-                                name: "i32".to_string(),
-                            },
-                        },],
-                        blocks: None,
-                        string: "  %1 = alloca i32, align 4".to_string(),
-                    },
-                    Instruction {
-                        opcode: 33,
-                        def: None,
-                        uses: vec![
-                            Operand {
-                                constant: true,
-                                name: None,
-                                ty: Type {
-                                    id: 13,
-                                    name: "i32".to_string(),
-                                },
-                            },
-                            Operand {
-                                constant: false,
-                                name: Some(Name::Number(1),),
-                                ty: Type {
-                                    id: 15,
-                                    name: "ptr".to_string(),
-                                },
-                            },
-                        ],
-                        blocks: None,
-                        string: "  store i32 0, ptr %1, align 4".to_string(),
-                    },
-                ],
-                term: Terminator {
-                    opcode: 1,
-                    def: None,
-                    uses: vec![Operand {
-                        constant: true,
-                        name: None,
-                        ty: Type {
-                            id: 13,
-                            name: "i32".to_string(),
-                        },
-                    },],
-                    string: "  ret i32 42".to_string(),
-                },
-            },],
-        }),
+        r#use(&ret()),
         vec![
             HashSet::new(),
             HashSet::from([&Name::Number(1)]),
@@ -1175,579 +603,27 @@ fn test_use() {
     );
     // for0.ll
     assert_eq!(
-    r#use(&Function {
-            name: "main".to_string(),
-            params: vec![
-                Param {
-                    name: Name::Number(0),
-                    ty: Type {
-                        id: 13,
-                        name: "i32".to_string(),
-                    },
-                },
-                Param {
-                    name: Name::Number(1),
-                    ty: Type {
-                        id: 15,
-                        name: "ptr".to_string(),
-                    },
-                },
-            ],
-            basic_blocks: vec![
-                BasicBlock {
-                    name: Name::Number(2),
-                    insts: vec![
-                        Instruction {
-                            opcode: 31,
-                            def: Some(
-                                Name::Number(3),
-                            ),
-                            uses: vec![
-                                Operand {
-                                    constant: true,
-                                    name: None,
-                                    ty: Type {
-                                        id: 13,
-                                        name: "i32".to_string(),
-                                    },
-                                },
-                            ],
-                            blocks: None,
-                            string: "  %3 = alloca i32, align 4".to_string(),
-                        },
-                        Instruction {
-                            opcode: 31,
-                            def: Some(
-                                Name::Number(4),
-                            ),
-                            uses: vec![
-                                Operand {
-                                    constant: true,
-                                    name: None,
-                                    ty: Type {
-                                        id: 13,
-                                        name: "i32".to_string(),
-                                    },
-                                },
-                            ],
-                            blocks: None,
-                            string: "  %4 = alloca i32, align 4".to_string(),
-                        },
-                        Instruction {
-                            opcode: 31,
-                            def: Some(
-                                Name::Number(5),
-                            ),
-                            uses: vec![
-                                Operand {
-                                    constant: true,
-                                    name: None,
-                                    ty: Type {
-                                        id: 13,
-                                        name: "i32".to_string(),
-                                    },
-                                },
-                            ],
-                            blocks: None,
-                            string: "  %5 = alloca ptr, align 8".to_string(),
-                        },
-                        Instruction {
-                            opcode: 31,
-                            def: Some(
-                                Name::Number(6),
-                            ),
-                            uses: vec![
-                                Operand {
-                                    constant: true,
-                                    name: None,
-                                    ty: Type {
-                                        id: 13,
-                                        name: "i32".to_string(),
-                                    },
-                                },
-                            ],
-                            blocks: None,
-                            string: "  %6 = alloca i32, align 4".to_string(),
-                        },
-                        Instruction {
-                            opcode: 33,
-                            def: None,
-                            uses: vec![
-                                Operand {
-                                    constant: true,
-                                    name: None,
-                                    ty: Type {
-                                        id: 13,
-                                        name: "i32".to_string(),
-                                    },
-                                },
-                                Operand {
-                                    constant: false,
-                                    name: Some(
-                                        Name::Number(3),
-                                    ),
-                                    ty: Type {
-                                        id: 15,
-                                        name: "ptr".to_string(),
-                                    },
-                                },
-                            ],
-                            blocks: None,
-                            string: "  store i32 0, ptr %3, align 4".to_string(),
-                        },
-                        Instruction {
-                            opcode: 33,
-                            def: None,
-                            uses: vec![
-                                Operand {
-                                    constant: false,
-                                    name: Some(
-                                        Name::Number(0),
-                                    ),
-                                    ty: Type {
-                                        id: 13,
-                                        name: "i32".to_string(),
-                                    },
-                                },
-                                Operand {
-                                    constant: false,
-                                    name: Some(
-                                        Name::Number(4),
-                                    ),
-                                    ty: Type {
-                                        id: 15,
-                                        name: "ptr".to_string(),
-                                    },
-                                },
-                            ],
-                            blocks: None,
-                            string: "  store i32 %0, ptr %4, align 4".to_string(),
-                        },
-                        Instruction {
-                            opcode: 33,
-                            def: None,
-                            uses: vec![
-                                Operand {
-                                    constant: false,
-                                    name: Some(
-                                        Name::Number(1),
-                                    ),
-                                    ty: Type {
-                                        id: 15,
-                                        name: "ptr".to_string(),
-                                    },
-                                },
-                                Operand {
-                                    constant: false,
-                                    name: Some(
-                                        Name::Number(5),
-                                    ),
-                                    ty: Type {
-                                        id: 15,
-                                        name: "ptr".to_string(),
-                                    },
-                                },
-                            ],
-                            blocks: None,
-                            string: "  store ptr %1, ptr %5, align 8".to_string(),
-                        },
-                        Instruction {
-                            opcode: 33,
-                            def: None,
-                            uses: vec![
-                                Operand {
-                                    constant: true,
-                                    name: None,
-                                    ty: Type {
-                                        id: 13,
-                                        name: "i32".to_string(),
-                                    },
-                                },
-                                Operand {
-                                    constant: false,
-                                    name: Some(
-                                        Name::Number(6),
-                                    ),
-                                    ty: Type {
-                                        id: 15,
-                                        name: "ptr".to_string(),
-                                    },
-                                },
-                            ],
-                            blocks: None,
-                            string: "  store i32 0, ptr %6, align 4".to_string(),
-                        },
-                    ],
-                    term: Terminator {
-                        opcode: 2,
-                        def: None,
-                        uses: vec![
-                            Operand {
-                                constant: false,
-                                name: Some(
-                                    Name::Number(7),
-                                ),
-                                ty: Type {
-                                    id: 8,
-                                    name: "label".to_string(),
-                                },
-                            },
-                        ],
-                        string: "  br label %7".to_string(),
-                    },
-                },
-                BasicBlock {
-                    name: Name::Number(7),
-                    insts: vec![
-                        Instruction {
-                            opcode: 32,
-                            def: Some(
-                                Name::Number(8),
-                            ),
-                            uses: vec![
-                                Operand {
-                                    constant: false,
-                                    name: Some(
-                                        Name::Number(6),
-                                    ),
-                                    ty: Type {
-                                        id: 15,
-                                        name: "ptr".to_string(),
-                                    },
-                                },
-                            ],
-                            blocks: None,
-                            string: "  %8 = load i32, ptr %6, align 4".to_string(),
-                        },
-                        Instruction {
-                            opcode: 32,
-                            def: Some(
-                                Name::Number(9),
-                            ),
-                            uses: vec![
-                                Operand {
-                                    constant: false,
-                                    name: Some(
-                                        Name::Number(4),
-                                    ),
-                                    ty: Type {
-                                        id: 15,
-                                        name: "ptr".to_string(),
-                                    },
-                                },
-                            ],
-                            blocks: None,
-                            string: "  %9 = load i32, ptr %4, align 4".to_string(),
-                        },
-                        Instruction {
-                            opcode: 53,
-                            def: Some(
-                                Name::Number(10),
-                            ),
-                            uses: vec![
-                                Operand {
-                                    constant: false,
-                                    name: Some(
-                                        Name::Number(8),
-                                    ),
-                                    ty: Type {
-                                        id: 13,
-                                        name: "i32".to_string(),
-                                    },
-                                },
-                                Operand {
-                                    constant: false,
-                                    name: Some(
-                                        Name::Number(9),
-                                    ),
-                                    ty: Type {
-                                        id: 13,
-                                        name: "i32".to_string(),
-                                    },
-                                },
-                            ],
-                            blocks: None,
-                            string: "  %10 = icmp slt i32 %8, %9".to_string(),
-                        },
-                    ],
-                    term: Terminator {
-                        opcode: 2,
-                        def: None,
-                        uses: vec![
-                            Operand {
-                                constant: false,
-                                name: Some(
-                                    Name::Number(10),
-                                ),
-                                ty: Type {
-                                    id: 13,
-                                    name: "i1".to_string(),
-                                },
-                            },
-                            Operand {
-                                constant: false,
-                                name: Some(
-                                    Name::Number(17),
-                                ),
-                                ty: Type {
-                                    id: 8,
-                                    name: "label".to_string(),
-                                },
-                            },
-                            Operand {
-                                constant: false,
-                                name: Some(
-                                    Name::Number(11),
-                                ),
-                                ty: Type {
-                                    id: 8,
-                                    name: "label".to_string(),
-                                },
-                            },
-                        ],
-                        string: "  br i1 %10, label %11, label %17".to_string(),
-                    },
-                },
-                BasicBlock {
-                    name: Name::Number(11),
-                    insts: vec![
-                        Instruction {
-                            opcode: 32,
-                            def: Some(
-                                Name::Number(12),
-                            ),
-                            uses: vec![
-                                Operand {
-                                    constant: false,
-                                    name: Some(
-                                        Name::Number(6),
-                                    ),
-                                    ty: Type {
-                                        id: 15,
-                                        name: "ptr".to_string(),
-                                    },
-                                },
-                            ],
-                            blocks: None,
-                            string: "  %12 = load i32, ptr %6, align 4".to_string(),
-                        },
-                        Instruction {
-                            opcode: 56,
-                            def: Some(
-                                Name::Number(13),
-                            ),
-                            uses: vec![
-                                Operand {
-                                    constant: true,
-                                    name: None,
-                                    ty: Type {
-                                        id: 15,
-                                        name: "ptr".to_string(),
-                                    },
-                                },
-                                Operand {
-                                    constant: false,
-                                    name: Some(
-                                        Name::Number(12),
-                                    ),
-                                    ty: Type {
-                                        id: 13,
-                                        name: "i32".to_string(),
-                                    },
-                                },
-                                Operand {
-                                    constant: true,
-                                    name: None,
-                                    ty: Type {
-                                        id: 15,
-                                        name: "ptr".to_string(),
-                                    },
-                                },
-                            ],
-                            blocks: None,
-                            string: "  %13 = call i32 (ptr, ...) @printf(ptr noundef @.str, i32 noundef %12)".to_string(),
-                        },
-                    ],
-                    term: Terminator {
-                        opcode: 2,
-                        def: None,
-                        uses: vec![
-                            Operand {
-                                constant: false,
-                                name: Some(
-                                    Name::Number(14),
-                                ),
-                                ty: Type {
-                                    id: 8,
-                                    name: "label".to_string(),
-                                },
-                            },
-                        ],
-                        string: "  br label %14".to_string(),
-                    },
-                },
-                BasicBlock {
-                    name: Name::Number(14),
-                    insts: vec![
-                        Instruction {
-                            opcode: 32,
-                            def: Some(
-                                Name::Number(15),
-                            ),
-                            uses: vec![
-                                Operand {
-                                    constant: false,
-                                    name: Some(
-                                        Name::Number(6),
-                                    ),
-                                    ty: Type {
-                                        id: 15,
-                                        name: "ptr".to_string(),
-                                    },
-                                },
-                            ],
-                            blocks: None,
-                            string: "  %15 = load i32, ptr %6, align 4".to_string(),
-                        },
-                        Instruction {
-                            opcode: 13,
-                            def: Some(
-                                Name::Number(16),
-                            ),
-                            uses: vec![
-                                Operand {
-                                    constant: false,
-                                    name: Some(
-                                        Name::Number(15),
-                                    ),
-                                    ty: Type {
-                                        id: 13,
-                                        name: "i32".to_string(),
-                                    },
-                                },
-                                Operand {
-                                    constant: true,
-                                    name: None,
-                                    ty: Type {
-                                        id: 13,
-                                        name: "i32".to_string(),
-                                    },
-                                },
-                            ],
-                            blocks: None,
-                            string: "  %16 = add nsw i32 %15, 1".to_string(),
-                        },
-                        Instruction {
-                            opcode: 33,
-                            def: None,
-                            uses: vec![
-                                Operand {
-                                    constant: false,
-                                    name: Some(
-                                        Name::Number(16),
-                                    ),
-                                    ty: Type {
-                                        id: 13,
-                                        name: "i32".to_string(),
-                                    },
-                                },
-                                Operand {
-                                    constant: false,
-                                    name: Some(
-                                        Name::Number(6),
-                                    ),
-                                    ty: Type {
-                                        id: 15,
-                                        name: "ptr".to_string(),
-                                    },
-                                },
-                            ],
-                            blocks: None,
-                            string: "  store i32 %16, ptr %6, align 4".to_string(),
-                        },
-                    ],
-                    term: Terminator {
-                        opcode: 2,
-                        def: None,
-                        uses: vec![
-                            Operand {
-                                constant: false,
-                                name: Some(
-                                    Name::Number(7),
-                                ),
-                                ty: Type {
-                                    id: 8,
-                                    name: "label".to_string(),
-                                },
-                            },
-                        ],
-                        string: "  br label %7, !llvm.loop !5".to_string(),
-                    },
-                },
-                BasicBlock {
-                    name: Name::Number(17),
-                    insts: vec![
-                        Instruction {
-                            opcode: 32,
-                            def: Some(
-                                Name::Number(18),
-                            ),
-                            uses: vec![
-                                Operand {
-                                    constant: false,
-                                    name: Some(
-                                        Name::Number(3),
-                                    ),
-                                    ty: Type {
-                                        id: 15,
-                                        name: "ptr".to_string(),
-                                    },
-                                },
-                            ],
-                            blocks: None,
-                            string: "  %18 = load i32, ptr %3, align 4".to_string(),
-                        },
-                    ],
-                    term: Terminator {
-                        opcode: 1,
-                        def: None,
-                        uses: vec![
-                            Operand {
-                                constant: false,
-                                name: Some(
-                                    Name::Number(18),
-                                ),
-                                ty: Type {
-                                    id: 13,
-                                    name: "i32".to_string(),
-                                },
-                            },
-                        ],
-                        string: "  ret i32 %18".to_string(),
-                    },
-                },
-            ],
-        }),
+        r#use(&for0()),
         vec![
             HashSet::new(),
             HashSet::new(),
             HashSet::new(),
             HashSet::new(),
             HashSet::from([&Name::Number(3)]),
-            HashSet::from([&Name::Number(0),&Name::Number(4)]),
-            HashSet::from([&Name::Number(1),&Name::Number(5)]),
+            HashSet::from([&Name::Number(0), &Name::Number(4)]),
+            HashSet::from([&Name::Number(1), &Name::Number(5)]),
             HashSet::from([&Name::Number(6)]),
             HashSet::new(),
             HashSet::from([&Name::Number(6)]),
             HashSet::from([&Name::Number(4)]),
-            HashSet::from([&Name::Number(8),&Name::Number(9)]),
+            HashSet::from([&Name::Number(8), &Name::Number(9)]),
             HashSet::from([&Name::Number(10)]),
             HashSet::from([&Name::Number(6)]),
             HashSet::from([&Name::Number(12)]),
             HashSet::new(),
             HashSet::from([&Name::Number(6)]),
             HashSet::from([&Name::Number(15)]),
-            HashSet::from([&Name::Number(6),&Name::Number(16)]),
+            HashSet::from([&Name::Number(6), &Name::Number(16)]),
             HashSet::new(),
             HashSet::from([&Name::Number(3)]),
             HashSet::from([&Name::Number(18)]),
@@ -1921,66 +797,7 @@ fn test_iter() {
     //use pretty_assertions::assert_eq;
 
     // ret.ll
-    let f = Function {
-        name: "main".to_string(),
-        params: vec![],
-        basic_blocks: vec![BasicBlock {
-            name: Name::Number(0),
-            insts: vec![
-                Instruction {
-                    opcode: 31,
-                    def: Some(Name::Number(1)),
-                    uses: vec![Operand {
-                        constant: true,
-                        name: None,
-                        ty: Type {
-                            id: 13,
-                            name: "i32".to_string(),
-                        },
-                    }],
-                    blocks: None,
-                    string: "  %1 = alloca i32, align 4".to_string(),
-                },
-                Instruction {
-                    opcode: 33,
-                    def: None,
-                    uses: vec![
-                        Operand {
-                            constant: true,
-                            name: None,
-                            ty: Type {
-                                id: 13,
-                                name: "i32".to_string(),
-                            },
-                        },
-                        Operand {
-                            constant: false,
-                            name: Some(Name::Number(1)),
-                            ty: Type {
-                                id: 15,
-                                name: "ptr".to_string(),
-                            },
-                        },
-                    ],
-                    blocks: None,
-                    string: "  store i32 0, ptr %1, align 4".to_string(),
-                },
-            ],
-            term: Terminator {
-                opcode: 1,
-                def: None,
-                uses: vec![Operand {
-                    constant: true,
-                    name: None,
-                    ty: Type {
-                        id: 13,
-                        name: "i32".to_string(),
-                    },
-                }],
-                string: "  ret i32 42".to_string(),
-            },
-        }],
-    };
+    let f = ret();
     assert_eq!(
         Iter::new(&f).collect::<Vec<_>>(),
         vec![
@@ -1988,12 +805,12 @@ fn test_iter() {
                 (
                     HashSet::new(),
                     HashSet::new(),
-                    "  %1 = alloca i32, align 4".to_string()
+                    "  %1 = alloca i32".to_string()
                 ),
                 (
                     HashSet::new(),
                     HashSet::new(),
-                    "  store i32 0, ptr %1, align 4".to_string(),
+                    "  store i32 0, ptr %1".to_string(),
                 ),
                 (HashSet::new(), HashSet::new(), "  ret i32 42".to_string()),
             ],
@@ -2001,12 +818,12 @@ fn test_iter() {
                 (
                     HashSet::new(),
                     HashSet::new(),
-                    "  %1 = alloca i32, align 4".to_string()
+                    "  %1 = alloca i32".to_string()
                 ),
                 (
                     HashSet::from([Name::Number(1)]),
                     HashSet::new(),
-                    "  store i32 0, ptr %1, align 4".to_string(),
+                    "  store i32 0, ptr %1".to_string(),
                 ),
                 (HashSet::new(), HashSet::new(), "  ret i32 42".to_string()),
             ],
@@ -2014,12 +831,12 @@ fn test_iter() {
                 (
                     HashSet::new(),
                     HashSet::new(),
-                    "  %1 = alloca i32, align 4".to_string()
+                    "  %1 = alloca i32".to_string()
                 ),
                 (
                     HashSet::from([Name::Number(1)]),
                     HashSet::new(),
-                    "  store i32 0, ptr %1, align 4".to_string(),
+                    "  store i32 0, ptr %1".to_string(),
                 ),
                 (HashSet::new(), HashSet::new(), "  ret i32 42".to_string()),
             ],
@@ -2027,12 +844,12 @@ fn test_iter() {
                 (
                     HashSet::new(),
                     HashSet::new(),
-                    "  %1 = alloca i32, align 4".to_string()
+                    "  %1 = alloca i32".to_string()
                 ),
                 (
                     HashSet::from([Name::Number(1)]),
                     HashSet::new(),
-                    "  store i32 0, ptr %1, align 4".to_string(),
+                    "  store i32 0, ptr %1".to_string(),
                 ),
                 (HashSet::new(), HashSet::new(), "  ret i32 42".to_string()),
             ],
@@ -2040,12 +857,12 @@ fn test_iter() {
                 (
                     HashSet::new(),
                     HashSet::new(),
-                    "  %1 = alloca i32, align 4".to_string()
+                    "  %1 = alloca i32".to_string()
                 ),
                 (
                     HashSet::from([Name::Number(1)]),
                     HashSet::new(),
-                    "  store i32 0, ptr %1, align 4".to_string(),
+                    "  store i32 0, ptr %1".to_string(),
                 ),
                 (HashSet::new(), HashSet::new(), "  ret i32 42".to_string()),
             ],
@@ -2053,12 +870,12 @@ fn test_iter() {
                 (
                     HashSet::new(),
                     HashSet::from([Name::Number(1)]),
-                    "  %1 = alloca i32, align 4".to_string(),
+                    "  %1 = alloca i32".to_string(),
                 ),
                 (
                     HashSet::from([Name::Number(1)]),
                     HashSet::new(),
-                    "  store i32 0, ptr %1, align 4".to_string(),
+                    "  store i32 0, ptr %1".to_string(),
                 ),
                 (HashSet::new(), HashSet::new(), "  ret i32 42".to_string()),
             ],
@@ -2067,12 +884,12 @@ fn test_iter() {
                 (
                     HashSet::new(),
                     HashSet::from([Name::Number(1)]),
-                    "  %1 = alloca i32, align 4".to_string(),
+                    "  %1 = alloca i32".to_string(),
                 ),
                 (
                     HashSet::from([Name::Number(1)]),
                     HashSet::new(),
-                    "  store i32 0, ptr %1, align 4".to_string(),
+                    "  store i32 0, ptr %1".to_string(),
                 ),
                 (HashSet::new(), HashSet::new(), "  ret i32 42".to_string()),
             ],
@@ -2080,12 +897,12 @@ fn test_iter() {
                 (
                     HashSet::new(),
                     HashSet::from([Name::Number(1)]),
-                    "  %1 = alloca i32, align 4".to_string(),
+                    "  %1 = alloca i32".to_string(),
                 ),
                 (
                     HashSet::from([Name::Number(1)]),
                     HashSet::new(),
-                    "  store i32 0, ptr %1, align 4".to_string(),
+                    "  store i32 0, ptr %1".to_string(),
                 ),
                 (HashSet::new(), HashSet::new(), "  ret i32 42".to_string()),
             ],
@@ -2093,12 +910,12 @@ fn test_iter() {
                 (
                     HashSet::new(),
                     HashSet::from([Name::Number(1)]),
-                    "  %1 = alloca i32, align 4".to_string(),
+                    "  %1 = alloca i32".to_string(),
                 ),
                 (
                     HashSet::from([Name::Number(1)]),
                     HashSet::new(),
-                    "  store i32 0, ptr %1, align 4".to_string(),
+                    "  store i32 0, ptr %1".to_string(),
                 ),
                 (HashSet::new(), HashSet::new(), "  ret i32 42".to_string()),
             ],
@@ -2106,12 +923,12 @@ fn test_iter() {
                 (
                     HashSet::new(),
                     HashSet::from([Name::Number(1)]),
-                    "  %1 = alloca i32, align 4".to_string(),
+                    "  %1 = alloca i32".to_string(),
                 ),
                 (
                     HashSet::from([Name::Number(1)]),
                     HashSet::new(),
-                    "  store i32 0, ptr %1, align 4".to_string(),
+                    "  store i32 0, ptr %1".to_string(),
                 ),
                 (HashSet::new(), HashSet::new(), "  ret i32 42".to_string()),
             ],
@@ -2119,12 +936,12 @@ fn test_iter() {
                 (
                     HashSet::new(),
                     HashSet::from([Name::Number(1)]),
-                    "  %1 = alloca i32, align 4".to_string(),
+                    "  %1 = alloca i32".to_string(),
                 ),
                 (
                     HashSet::from([Name::Number(1)]),
                     HashSet::new(),
-                    "  store i32 0, ptr %1, align 4".to_string(),
+                    "  store i32 0, ptr %1".to_string(),
                 ),
                 (HashSet::new(), HashSet::new(), "  ret i32 42".to_string()),
             ],
@@ -2132,12 +949,12 @@ fn test_iter() {
                 (
                     HashSet::new(),
                     HashSet::from([Name::Number(1)]),
-                    "  %1 = alloca i32, align 4".to_string(),
+                    "  %1 = alloca i32".to_string(),
                 ),
                 (
                     HashSet::from([Name::Number(1)]),
                     HashSet::new(),
-                    "  store i32 0, ptr %1, align 4".to_string(),
+                    "  store i32 0, ptr %1".to_string(),
                 ),
                 (HashSet::new(), HashSet::new(), "  ret i32 42".to_string()),
             ],
@@ -2145,12 +962,12 @@ fn test_iter() {
                 (
                     HashSet::new(),
                     HashSet::from([Name::Number(1)]),
-                    "  %1 = alloca i32, align 4".to_string(),
+                    "  %1 = alloca i32".to_string(),
                 ),
                 (
                     HashSet::from([Name::Number(1)]),
                     HashSet::new(),
-                    "  store i32 0, ptr %1, align 4".to_string(),
+                    "  store i32 0, ptr %1".to_string(),
                 ),
                 (HashSet::new(), HashSet::new(), "  ret i32 42".to_string()),
             ],
@@ -2158,559 +975,7 @@ fn test_iter() {
     );
 
     // for0.ll
-    let f = Function {
-            name: "main".to_string(),
-            params: vec![
-                Param {
-                    name: Name::Number(0),
-                    ty: Type {
-                        id: 13,
-                        name: "i32".to_string(),
-                    },
-                },
-                Param {
-                    name: Name::Number(1),
-                    ty: Type {
-                        id: 15,
-                        name: "ptr".to_string(),
-                    },
-                },
-            ],
-            basic_blocks: vec![
-                BasicBlock {
-                    name: Name::Number(2),
-                    insts: vec![
-                        Instruction {
-                            opcode: 31,
-                            def: Some(
-                                Name::Number(3),
-                            ),
-                            uses: vec![
-                                Operand {
-                                    constant: true,
-                                    name: None,
-                                    ty: Type {
-                                        id: 13,
-                                        name: "i32".to_string(),
-                                    },
-                                },
-                            ],
-                            blocks: None,
-                            string: "  %3 = alloca i32, align 4".to_string(),
-                        },
-                        Instruction {
-                            opcode: 31,
-                            def: Some(
-                                Name::Number(4),
-                            ),
-                            uses: vec![
-                                Operand {
-                                    constant: true,
-                                    name: None,
-                                    ty: Type {
-                                        id: 13,
-                                        name: "i32".to_string(),
-                                    },
-                                },
-                            ],
-                            blocks: None,
-                            string: "  %4 = alloca i32, align 4".to_string(),
-                        },
-                        Instruction {
-                            opcode: 31,
-                            def: Some(
-                                Name::Number(5),
-                            ),
-                            uses: vec![
-                                Operand {
-                                    constant: true,
-                                    name: None,
-                                    ty: Type {
-                                        id: 13,
-                                        name: "i32".to_string(),
-                                    },
-                                },
-                            ],
-                            blocks: None,
-                            string: "  %5 = alloca ptr, align 8".to_string(),
-                        },
-                        Instruction {
-                            opcode: 31,
-                            def: Some(
-                                Name::Number(6),
-                            ),
-                            uses: vec![
-                                Operand {
-                                    constant: true,
-                                    name: None,
-                                    ty: Type {
-                                        id: 13,
-                                        name: "i32".to_string(),
-                                    },
-                                },
-                            ],
-                            blocks: None,
-                            string: "  %6 = alloca i32, align 4".to_string(),
-                        },
-                        Instruction {
-                            opcode: 33,
-                            def: None,
-                            uses: vec![
-                                Operand {
-                                    constant: true,
-                                    name: None,
-                                    ty: Type {
-                                        id: 13,
-                                        name: "i32".to_string(),
-                                    },
-                                },
-                                Operand {
-                                    constant: false,
-                                    name: Some(
-                                        Name::Number(3),
-                                    ),
-                                    ty: Type {
-                                        id: 15,
-                                        name: "ptr".to_string(),
-                                    },
-                                },
-                            ],
-                            blocks: None,
-                            string: "  store i32 0, ptr %3, align 4".to_string(),
-                        },
-                        Instruction {
-                            opcode: 33,
-                            def: None,
-                            uses: vec![
-                                Operand {
-                                    constant: false,
-                                    name: Some(
-                                        Name::Number(0),
-                                    ),
-                                    ty: Type {
-                                        id: 13,
-                                        name: "i32".to_string(),
-                                    },
-                                },
-                                Operand {
-                                    constant: false,
-                                    name: Some(
-                                        Name::Number(4),
-                                    ),
-                                    ty: Type {
-                                        id: 15,
-                                        name: "ptr".to_string(),
-                                    },
-                                },
-                            ],
-                            blocks: None,
-                            string: "  store i32 %0, ptr %4, align 4".to_string(),
-                        },
-                        Instruction {
-                            opcode: 33,
-                            def: None,
-                            uses: vec![
-                                Operand {
-                                    constant: false,
-                                    name: Some(
-                                        Name::Number(1),
-                                    ),
-                                    ty: Type {
-                                        id: 15,
-                                        name: "ptr".to_string(),
-                                    },
-                                },
-                                Operand {
-                                    constant: false,
-                                    name: Some(
-                                        Name::Number(5),
-                                    ),
-                                    ty: Type {
-                                        id: 15,
-                                        name: "ptr".to_string(),
-                                    },
-                                },
-                            ],
-                            blocks: None,
-                            string: "  store ptr %1, ptr %5, align 8".to_string(),
-                        },
-                        Instruction {
-                            opcode: 33,
-                            def: None,
-                            uses: vec![
-                                Operand {
-                                    constant: true,
-                                    name: None,
-                                    ty: Type {
-                                        id: 13,
-                                        name: "i32".to_string(),
-                                    },
-                                },
-                                Operand {
-                                    constant: false,
-                                    name: Some(
-                                        Name::Number(6),
-                                    ),
-                                    ty: Type {
-                                        id: 15,
-                                        name: "ptr".to_string(),
-                                    },
-                                },
-                            ],
-                            blocks: None,
-                            string: "  store i32 0, ptr %6, align 4".to_string(),
-                        },
-                    ],
-                    term: Terminator {
-                        opcode: 2,
-                        def: None,
-                        uses: vec![
-                            Operand {
-                                constant: false,
-                                name: Some(
-                                    Name::Number(7),
-                                ),
-                                ty: Type {
-                                    id: 8,
-                                    name: "label".to_string(),
-                                },
-                            },
-                        ],
-                        string: "  br label %7".to_string(),
-                    },
-                },
-                BasicBlock {
-                    name: Name::Number(7),
-                    insts: vec![
-                        Instruction {
-                            opcode: 32,
-                            def: Some(
-                                Name::Number(8),
-                            ),
-                            uses: vec![
-                                Operand {
-                                    constant: false,
-                                    name: Some(
-                                        Name::Number(6),
-                                    ),
-                                    ty: Type {
-                                        id: 15,
-                                        name: "ptr".to_string(),
-                                    },
-                                },
-                            ],
-                            blocks: None,
-                            string: "  %8 = load i32, ptr %6, align 4".to_string(),
-                        },
-                        Instruction {
-                            opcode: 32,
-                            def: Some(
-                                Name::Number(9),
-                            ),
-                            uses: vec![
-                                Operand {
-                                    constant: false,
-                                    name: Some(
-                                        Name::Number(4),
-                                    ),
-                                    ty: Type {
-                                        id: 15,
-                                        name: "ptr".to_string(),
-                                    },
-                                },
-                            ],
-                            blocks: None,
-                            string: "  %9 = load i32, ptr %4, align 4".to_string(),
-                        },
-                        Instruction {
-                            opcode: 53,
-                            def: Some(
-                                Name::Number(10),
-                            ),
-                            uses: vec![
-                                Operand {
-                                    constant: false,
-                                    name: Some(
-                                        Name::Number(8),
-                                    ),
-                                    ty: Type {
-                                        id: 13,
-                                        name: "i32".to_string(),
-                                    },
-                                },
-                                Operand {
-                                    constant: false,
-                                    name: Some(
-                                        Name::Number(9),
-                                    ),
-                                    ty: Type {
-                                        id: 13,
-                                        name: "i32".to_string(),
-                                    },
-                                },
-                            ],
-                            blocks: None,
-                            string: "  %10 = icmp slt i32 %8, %9".to_string(),
-                        },
-                    ],
-                    term: Terminator {
-                        opcode: 2,
-                        def: None,
-                        uses: vec![
-                            Operand {
-                                constant: false,
-                                name: Some(
-                                    Name::Number(10),
-                                ),
-                                ty: Type {
-                                    id: 13,
-                                    name: "i1".to_string(),
-                                },
-                            },
-                            Operand {
-                                constant: false,
-                                name: Some(
-                                    Name::Number(17),
-                                ),
-                                ty: Type {
-                                    id: 8,
-                                    name: "label".to_string(),
-                                },
-                            },
-                            Operand {
-                                constant: false,
-                                name: Some(
-                                    Name::Number(11),
-                                ),
-                                ty: Type {
-                                    id: 8,
-                                    name: "label".to_string(),
-                                },
-                            },
-                        ],
-                        string: "  br i1 %10, label %11, label %17".to_string(),
-                    },
-                },
-                BasicBlock {
-                    name: Name::Number(11),
-                    insts: vec![
-                        Instruction {
-                            opcode: 32,
-                            def: Some(
-                                Name::Number(12),
-                            ),
-                            uses: vec![
-                                Operand {
-                                    constant: false,
-                                    name: Some(
-                                        Name::Number(6),
-                                    ),
-                                    ty: Type {
-                                        id: 15,
-                                        name: "ptr".to_string(),
-                                    },
-                                },
-                            ],
-                            blocks: None,
-                            string: "  %12 = load i32, ptr %6, align 4".to_string(),
-                        },
-                        Instruction {
-                            opcode: 56,
-                            def: Some(
-                                Name::Number(13),
-                            ),
-                            uses: vec![
-                                Operand {
-                                    constant: true,
-                                    name: None,
-                                    ty: Type {
-                                        id: 15,
-                                        name: "ptr".to_string(),
-                                    },
-                                },
-                                Operand {
-                                    constant: false,
-                                    name: Some(
-                                        Name::Number(12),
-                                    ),
-                                    ty: Type {
-                                        id: 13,
-                                        name: "i32".to_string(),
-                                    },
-                                },
-                                Operand {
-                                    constant: true,
-                                    name: None,
-                                    ty: Type {
-                                        id: 15,
-                                        name: "ptr".to_string(),
-                                    },
-                                },
-                            ],
-                            blocks: None,
-                            string: "  %13 = call i32 (ptr, ...) @printf(ptr noundef @.str, i32 noundef %12)".to_string(),
-                        },
-                    ],
-                    term: Terminator {
-                        opcode: 2,
-                        def: None,
-                        uses: vec![
-                            Operand {
-                                constant: false,
-                                name: Some(
-                                    Name::Number(14),
-                                ),
-                                ty: Type {
-                                    id: 8,
-                                    name: "label".to_string(),
-                                },
-                            },
-                        ],
-                        string: "  br label %14".to_string(),
-                    },
-                },
-                BasicBlock {
-                    name: Name::Number(14),
-                    insts: vec![
-                        Instruction {
-                            opcode: 32,
-                            def: Some(
-                                Name::Number(15),
-                            ),
-                            uses: vec![
-                                Operand {
-                                    constant: false,
-                                    name: Some(
-                                        Name::Number(6),
-                                    ),
-                                    ty: Type {
-                                        id: 15,
-                                        name: "ptr".to_string(),
-                                    },
-                                },
-                            ],
-                            blocks: None,
-                            string: "  %15 = load i32, ptr %6, align 4".to_string(),
-                        },
-                        Instruction {
-                            opcode: 13,
-                            def: Some(
-                                Name::Number(16),
-                            ),
-                            uses: vec![
-                                Operand {
-                                    constant: false,
-                                    name: Some(
-                                        Name::Number(15),
-                                    ),
-                                    ty: Type {
-                                        id: 13,
-                                        name: "i32".to_string(),
-                                    },
-                                },
-                                Operand {
-                                    constant: true,
-                                    name: None,
-                                    ty: Type {
-                                        id: 13,
-                                        name: "i32".to_string(),
-                                    },
-                                },
-                            ],
-                            blocks: None,
-                            string: "  %16 = add nsw i32 %15, 1".to_string(),
-                        },
-                        Instruction {
-                            opcode: 33,
-                            def: None,
-                            uses: vec![
-                                Operand {
-                                    constant: false,
-                                    name: Some(
-                                        Name::Number(16),
-                                    ),
-                                    ty: Type {
-                                        id: 13,
-                                        name: "i32".to_string(),
-                                    },
-                                },
-                                Operand {
-                                    constant: false,
-                                    name: Some(
-                                        Name::Number(6),
-                                    ),
-                                    ty: Type {
-                                        id: 15,
-                                        name: "ptr".to_string(),
-                                    },
-                                },
-                            ],
-                            blocks: None,
-                            string: "  store i32 %16, ptr %6, align 4".to_string(),
-                        },
-                    ],
-                    term: Terminator {
-                        opcode: 2,
-                        def: None,
-                        uses: vec![
-                            Operand {
-                                constant: false,
-                                name: Some(
-                                    Name::Number(7),
-                                ),
-                                ty: Type {
-                                    id: 8,
-                                    name: "label".to_string(),
-                                },
-                            },
-                        ],
-                        string: "  br label %7, !llvm.loop !5".to_string(),
-                    },
-                },
-                BasicBlock {
-                    name: Name::Number(17),
-                    insts: vec![
-                        Instruction {
-                            opcode: 32,
-                            def: Some(
-                                Name::Number(18),
-                            ),
-                            uses: vec![
-                                Operand {
-                                    constant: false,
-                                    name: Some(
-                                        Name::Number(3),
-                                    ),
-                                    ty: Type {
-                                        id: 15,
-                                        name: "ptr".to_string(),
-                                    },
-                                },
-                            ],
-                            blocks: None,
-                            string: "  %18 = load i32, ptr %3, align 4".to_string(),
-                        },
-                    ],
-                    term: Terminator {
-                        opcode: 1,
-                        def: None,
-                        uses: vec![
-                            Operand {
-                                constant: false,
-                                name: Some(
-                                    Name::Number(18),
-                                ),
-                                ty: Type {
-                                    id: 13,
-                                    name: "i32".to_string(),
-                                },
-                            },
-                        ],
-                        string: "  ret i32 %18".to_string(),
-                    },
-                },
-            ],
-        };
+    let f = for0();
     assert_eq!(
         Iter::new(&f)
             .map(|i| i
@@ -4571,286 +2836,16 @@ fn test_lva() {
 
     // min.ll
     assert_eq!(
-        lva(&Function {
-            name: "main".to_string(),
-            params: vec![
-                Param {
-                    name: Name::Name("argc".to_string()),
-                    ty: Type {
-                        id: 13,
-                        name: "i32".to_string(),
-                    },
-                },
-                Param {
-                    name: Name::Name("argv".to_string()),
-                    ty: Type {
-                        id: 15,
-                        name: "ptr".to_string(),
-                    },
-                },
-            ],
-            basic_blocks: vec![BasicBlock {
-                name: Name::Number(0),
-                insts: vec![],
-                term: Terminator {
-                    opcode: 1,
-                    def: None,
-                    uses: vec![Operand {
-                        constant: false,
-                        name: Some(Name::Name("argc".to_string())),
-                        ty: Type {
-                            id: 13,
-                            name: "i32".to_string(),
-                        },
-                    }],
-                    string: "  ret void".to_string(),
-                },
-            }],
-        }),
+        lva(&min()),
         vec![(
             HashSet::from([&Name::Name("argc".to_string())]),
             HashSet::new(),
-            "  ret void",
+            "  ret i32 %argc",
         )],
     );
     // for1.ll
     assert_eq!(
-        lva(&Function {
-            name: "main".to_string(),
-            params: vec![
-                Param {
-                    name: Name::Number(0),
-                    ty: Type {
-                        id: 13,
-                        name: "i32".to_string(),
-                    },
-                },
-                Param {
-                    name: Name::Number(1),
-                    ty: Type {
-                        id: 15,
-                        name: "ptr".to_string(),
-                    },
-                },
-            ],
-            basic_blocks: vec![
-                BasicBlock {
-                    name: Name::Number(2),
-                    insts: vec![Instruction {
-                        opcode: 53,
-                        def: Some(Name::Number(3),),
-                        uses: vec![
-                            Operand {
-                                constant: false,
-                                name: Some(Name::Number(0),),
-                                ty: Type {
-                                    id: 13,
-                                    name: "i32".to_string(),
-                                },
-                            },
-                            Operand {
-                                constant: true,
-                                name: None,
-                                ty: Type {
-                                    id: 13,
-                                    name: "i32".to_string(),
-                                },
-                            },
-                        ],
-                        blocks: None,
-                        string: "  %3 = icmp sgt i32 %0, 0".to_string(),
-                    },],
-                    term: Terminator {
-                        opcode: 2,
-                        def: None,
-                        uses: vec![
-                            Operand {
-                                constant: false,
-                                name: Some(Name::Number(3),),
-                                ty: Type {
-                                    id: 13,
-                                    name: "i1".to_string(),
-                                },
-                            },
-                            Operand {
-                                constant: false,
-                                name: Some(Name::Number(4),),
-                                ty: Type {
-                                    id: 8,
-                                    name: "label".to_string(),
-                                },
-                            },
-                            Operand {
-                                constant: false,
-                                name: Some(Name::Number(5),),
-                                ty: Type {
-                                    id: 8,
-                                    name: "label".to_string(),
-                                },
-                            },
-                        ],
-                        string: "  br i1 %3, label %5, label %4".to_string(),
-                    },
-                },
-                BasicBlock {
-                    name: Name::Number(4),
-                    insts: vec![],
-                    term: Terminator {
-                        opcode: 1,
-                        def: None,
-                        uses: vec![Operand {
-                            constant: true,
-                            name: None,
-                            ty: Type {
-                                id: 13,
-                                name: "i32".to_string(),
-                            },
-                        },],
-                        string: "  ret i32 0".to_string(),
-                    },
-                },
-                BasicBlock {
-                    name: Name::Number(5),
-                    insts: vec![
-                        Instruction {
-                            opcode: 55,
-                            def: Some(Name::Number(6),),
-                            uses: vec![
-                                Operand {
-                                    constant: false,
-                                    name: Some(Name::Number(8),),
-                                    ty: Type {
-                                        id: 13,
-                                        name: "i32".to_string(),
-                                    },
-                                },
-                                Operand {
-                                    constant: true,
-                                    name: None,
-                                    ty: Type {
-                                        id: 13,
-                                        name: "i32".to_string(),
-                                    },
-                                },
-                            ],
-                            blocks: Some(vec![Name::Number(5), Name::Number(2),],),
-                            string: "  %6 = phi i32 [ %8, %5 ], [ 0, %2 ]".to_string(),
-                        },
-                        Instruction {
-                            opcode: 56,
-                            def: Some(Name::Number(7),),
-                            uses: vec![
-                                Operand {
-                                    constant: true,
-                                    name: None,
-                                    ty: Type {
-                                        id: 15,
-                                        name: "ptr".to_string(),
-                                    },
-                                },
-                                Operand {
-                                    constant: false,
-                                    name: Some(Name::Number(6),),
-                                    ty: Type {
-                                        id: 13,
-                                        name: "i32".to_string(),
-                                    },
-                                },
-                                Operand {
-                                    constant: true,
-                                    name: None,
-                                    ty: Type {
-                                        id: 15,
-                                        name: "ptr".to_string(),
-                                    },
-                                },
-                            ],
-                            blocks: None,
-                            string: "  %7 = tail call i32 (ptr, ...) @printf(ptr noundef nonnull dereferenceable(1) @.str, i32 noundef %6)".to_string(),
-                        },
-                        Instruction {
-                            opcode: 13,
-                            def: Some(Name::Number(8),),
-                            uses: vec![
-                                Operand {
-                                    constant: false,
-                                    name: Some(Name::Number(6),),
-                                    ty: Type {
-                                        id: 13,
-                                        name: "i32".to_string(),
-                                    },
-                                },
-                                Operand {
-                                    constant: true,
-                                    name: None,
-                                    ty: Type {
-                                        id: 13,
-                                        name: "i32".to_string(),
-                                    },
-                                },
-                            ],
-                            blocks: None,
-                            string: "  %8 = add nuw nsw i32 %6, 1".to_string(),
-                        },
-                        Instruction {
-                            opcode: 53,
-                            def: Some(Name::Number(9),),
-                            uses: vec![
-                                Operand {
-                                    constant: false,
-                                    name: Some(Name::Number(8),),
-                                    ty: Type {
-                                        id: 13,
-                                        name: "i32".to_string(),
-                                    },
-                                },
-                                Operand {
-                                    constant: false,
-                                    name: Some(Name::Number(0),),
-                                    ty: Type {
-                                        id: 13,
-                                        name: "i32".to_string(),
-                                    },
-                                },
-                            ],
-                            blocks: None,
-                            string: "  %9 = icmp eq i32 %8, %0".to_string(),
-                        },
-                    ],
-                    term: Terminator {
-                        opcode: 2,
-                        def: None,
-                        uses: vec![
-                            Operand {
-                                constant: false,
-                                name: Some(Name::Number(9),),
-                                ty: Type {
-                                    id: 13,
-                                    name: "i1".to_string(),
-                                },
-                            },
-                            Operand {
-                                constant: false,
-                                name: Some(Name::Number(5),),
-                                ty: Type {
-                                    id: 8,
-                                    name: "label".to_string(),
-                                },
-                            },
-                            Operand {
-                                constant: false,
-                                name: Some(Name::Number(4),),
-                                ty: Type {
-                                    id: 8,
-                                    name: "label".to_string(),
-                                },
-                            },
-                        ],
-                        string: "  br i1 %3, label %5, label %4".to_string(),
-                    },
-                },
-            ],
-        }),
+        lva(&for1()),
         vec![
             (
                 HashSet::from([&Name::Number(0)]),
@@ -4871,12 +2866,12 @@ fn test_lva() {
             (
                 HashSet::from([&Name::Number(0), &Name::Number(6)]),
                 HashSet::from([&Name::Number(0), &Name::Number(6)]),
-                "  %7 = tail call i32 (ptr, ...) @printf(ptr noundef nonnull dereferenceable(1) @.str, i32 noundef %6)",
+                "  %7 = call i32 (ptr, ...) @printf(ptr @.str, i32 %6)",
             ),
             (
                 HashSet::from([&Name::Number(0), &Name::Number(6)]),
                 HashSet::from([&Name::Number(0), &Name::Number(8)]),
-                "  %8 = add nuw nsw i32 %6, 1",
+                "  %8 = add i32 %6, 1",
             ),
             (
                 HashSet::from([&Name::Number(0), &Name::Number(8)]),
@@ -4886,1094 +2881,219 @@ fn test_lva() {
             (
                 HashSet::from([&Name::Number(0), &Name::Number(8), &Name::Number(9)]),
                 HashSet::from([&Name::Number(0), &Name::Number(8)]),
-                "  br i1 %3, label %5, label %4",
+                "  br i1 %9, label %4, label %5",
             ),
         ],
     );
-    /*// fib.ll
+    // fib.ll
+    let f = {
+        use crate::builder::{constant, global, Builder};
+
+        let mut b = Builder::new("main");
+        let (cond, body, inc, end) = (b.block(), b.block(), b.block(), b.block());
+        let [retval, i, n, a, b_, c] = ["i32"; 6].map(|ty| b.alloca(ty));
+        b.store(&constant("i32", 0), &retval);
+        b.store(&constant("i32", 0), &a);
+        b.store(&constant("i32", 1), &b_);
+        let x = b.load("i32", &a);
+        let y = b.load("i32", &b_);
+        let sum = b.binary("add", &x, &y);
+        b.store(&sum, &c);
+        let printf = global("printf");
+        b.call("i32 (ptr, ...)", &printf, &[&global(".str")]);
+        let args = [&global(".str.1"), &n];
+        b.call("i32 (ptr, ...)", &global("scanf"), &args);
+        let x = b.load("i32", &a);
+        let y = b.load("i32", &b_);
+        b.call("i32 (ptr, ...)", &printf, &[&global(".str.2"), &x, &y]);
+        b.store(&constant("i32", 3), &i);
+        b.br(cond);
+
+        b.position(cond);
+        let x = b.load("i32", &i);
+        let y = b.load("i32", &n);
+        let le = b.icmp("sle", &x, &y);
+        b.cond_br(&le, body, end);
+
+        b.position(body);
+        let x = b.load("i32", &c);
+        b.call("i32 (ptr, ...)", &printf, &[&global(".str.3"), &x]);
+        let x = b.load("i32", &b_);
+        b.store(&x, &a);
+        let x = b.load("i32", &c);
+        b.store(&x, &b_);
+        let x = b.load("i32", &a);
+        let y = b.load("i32", &b_);
+        let sum = b.binary("add", &x, &y);
+        b.store(&sum, &c);
+        b.br(inc);
+
+        b.position(inc);
+        let x = b.load("i32", &i);
+        let x = b.binary("add", &x, &constant("i32", 1));
+        b.store(&x, &i);
+        b.br(cond);
+
+        b.position(end);
+        b.ret(Some(&constant("i32", 0)));
+        b.build()
+    };
+    let live = |r#in: &[usize], out: &[usize], s: &str| {
+        let set =
+            |names: &[usize]| -> HashSet<Name> { names.iter().map(|&n| Name::Number(n)).collect() };
+        (set(r#in), set(out), s.to_string())
+    };
     assert_eq!(
-        lva(&Function {
-            name: "main".to_string(),
-            params: vec![],
-            basic_blocks: vec![
-                BasicBlock {
-                    name: Name::Number(0),
-                    insts: vec![
-                        Instruction {
-                            opcode: 31,
-                            def: Some(
-                                Name::Number(1),
-                            ),
-                            uses: vec![
-                                Operand {
-                                    constant: true,
-                                    name: None,
-                                    ty: Type {
-                                        id: 13,
-                                        name: "i32".to_string(),
-                                    },
-                                },
-                            ],
-                            blocks: None,
-                            string: "  %1 = alloca i32, align 4".to_string(),
-                        },
-                        Instruction {
-                            opcode: 31,
-                            def: Some(
-                                Name::Number(2),
-                            ),
-                            uses: vec![
-                                Operand {
-                                    constant: true,
-                                    name: None,
-                                    ty: Type {
-                                        id: 13,
-                                        name: "i32".to_string(),
-                                    },
-                                },
-                            ],
-                            blocks: None,
-                            string: "  %2 = alloca i32, align 4".to_string(),
-                        },
-                        Instruction {
-                            opcode: 31,
-                            def: Some(
-                                Name::Number(3),
-                            ),
-                            uses: vec![
-                                Operand {
-                                    constant: true,
-                                    name: None,
-                                    ty: Type {
-                                        id: 13,
-                                        name: "i32".to_string(),
-                                    },
-                                },
-                            ],
-                            blocks: None,
-                            string: "  %3 = alloca i32, align 4".to_string(),
-                        },
-                        Instruction {
-                            opcode: 31,
-                            def: Some(
-                                Name::Number(4),
-                            ),
-                            uses: vec![
-                                Operand {
-                                    constant: true,
-                                    name: None,
-                                    ty: Type {
-                                        id: 13,
-                                        name: "i32".to_string(),
-                                    },
-                                },
-                            ],
-                            blocks: None,
-                            string: "  %4 = alloca i32, align 4".to_string(),
-                        },
-                        Instruction {
-                            opcode: 31,
-                            def: Some(
-                                Name::Number(5),
-                            ),
-                            uses: vec![
-                                Operand {
-                                    constant: true,
-                                    name: None,
-                                    ty: Type {
-                                        id: 13,
-                                        name: "i32".to_string(),
-                                    },
-                                },
-                            ],
-                            blocks: None,
-                            string: "  %5 = alloca i32, align 4".to_string(),
-                        },
-                        Instruction {
-                            opcode: 31,
-                            def: Some(
-                                Name::Number(6),
-                            ),
-                            uses: vec![
-                                Operand {
-                                    constant: true,
-                                    name: None,
-                                    ty: Type {
-                                        id: 13,
-                                        name: "i32".to_string(),
-                                    },
-                                },
-                            ],
-                            blocks: None,
-                            string: "  %6 = alloca i32, align 4".to_string(),
-                        },
-                        Instruction {
-                            opcode: 33,
-                            def: None,
-                            uses: vec![
-                                Operand {
-                                    constant: true,
-                                    name: None,
-                                    ty: Type {
-                                        id: 13,
-                                        name: "i32".to_string(),
-                                    },
-                                },
-                                Operand {
-                                    constant: false,
-                                    name: Some(
-                                        Name::Number(1),
-                                    ),
-                                    ty: Type {
-                                        id: 15,
-                                        name: "ptr".to_string(),
-                                    },
-                                },
-                            ],
-                            blocks: None,
-                            string: "  store i32 0, ptr %1, align 4".to_string(),
-                        },
-                        Instruction {
-                            opcode: 33,
-                            def: None,
-                            uses: vec![
-                                Operand {
-                                    constant: true,
-                                    name: None,
-                                    ty: Type {
-                                        id: 13,
-                                        name: "i32".to_string(),
-                                    },
-                                },
-                                Operand {
-                                    constant: false,
-                                    name: Some(
-                                        Name::Number(4),
-                                    ),
-                                    ty: Type {
-                                        id: 15,
-                                        name: "ptr".to_string(),
-                                    },
-                                },
-                            ],
-                            blocks: None,
-                            string: "  store i32 0, ptr %4, align 4".to_string(),
-                        },
-                        Instruction {
-                            opcode: 33,
-                            def: None,
-                            uses: vec![
-                                Operand {
-                                    constant: true,
-                                    name: None,
-                                    ty: Type {
-                                        id: 13,
-                                        name: "i32".to_string(),
-                                    },
-                                },
-                                Operand {
-                                    constant: false,
-                                    name: Some(
-                                        Name::Number(5),
-                                    ),
-                                    ty: Type {
-                                        id: 15,
-                                        name: "ptr".to_string(),
-                                    },
-                                },
-                            ],
-                            blocks: None,
-                            string: "  store i32 1, ptr %5, align 4".to_string(),
-                        },
-                        Instruction {
-                            opcode: 32,
-                            def: Some(
-                                Name::Number(7),
-                            ),
-                            uses: vec![
-                                Operand {
-                                    constant: false,
-                                    name: Some(
-                                        Name::Number(4),
-                                    ),
-                                    ty: Type {
-                                        id: 15,
-                                        name: "ptr".to_string(),
-                                    },
-                                },
-                            ],
-                            blocks: None,
-                            string: "  %7 = load i32, ptr %4, align 4".to_string(),
-                        },
-                        Instruction {
-                            opcode: 32,
-                            def: Some(
-                                Name::Number(8),
-                            ),
-                            uses: vec![
-                                Operand {
-                                    constant: false,
-                                    name: Some(
-                                        Name::Number(5),
-                                    ),
-                                    ty: Type {
-                                        id: 15,
-                                        name: "ptr".to_string(),
-                                    },
-                                },
-                            ],
-                            blocks: None,
-                            string: "  %8 = load i32, ptr %5, align 4".to_string(),
-                        },
-                        Instruction {
-                            opcode: 13,
-                            def: Some(
-                                Name::Number(9),
-                            ),
-                            uses: vec![
-                                Operand {
-                                    constant: false,
-                                    name: Some(
-                                        Name::Number(7),
-                                    ),
-                                    ty: Type {
-                                        id: 13,
-                                        name: "i32".to_string(),
-                                    },
-                                },
-                                Operand {
-                                    constant: false,
-                                    name: Some(
-                                        Name::Number(8),
-                                    ),
-                                    ty: Type {
-                                        id: 13,
-                                        name: "i32".to_string(),
-                                    },
-                                },
-                            ],
-                            blocks: None,
-                            string: "  %9 = add nsw i32 %7, %8".to_string(),
-                        },
-                        Instruction {
-                            opcode: 33,
-                            def: None,
-                            uses: vec![
-                                Operand {
-                                    constant: false,
-                                    name: Some(
-                                        Name::Number(9),
-                                    ),
-                                    ty: Type {
-                                        id: 13,
-                                        name: "i32".to_string(),
-                                    },
-                                },
-                                Operand {
-                                    constant: false,
-                                    name: Some(
-                                        Name::Number(6),
-                                    ),
-                                    ty: Type {
-                                        id: 15,
-                                        name: "ptr".to_string(),
-                                    },
-                                },
-                            ],
-                            blocks: None,
-                            string: "  store i32 %9, ptr %6, align 4".to_string(),
-                        },
-                        Instruction {
-                            opcode: 56,
-                            def: Some(
-                                Name::Number(10),
-                            ),
-                            uses: vec![
-                                Operand {
-                                    constant: true,
-                                    name: None,
-                                    ty: Type {
-                                        id: 15,
-                                        name: "ptr".to_string(),
-                                    },
-                                },
-                                Operand {
-                                    constant: true,
-                                    name: None,
-                                    ty: Type {
-                                        id: 15,
-                                        name: "ptr".to_string(),
-                                    },
-                                },
-                            ],
-                            blocks: None,
-                            string: "  %10 = call i32 (ptr, ...) @printf(ptr noundef @.str)".to_string(),
-                        },
-                        Instruction {
-                            opcode: 56,
-                            def: Some(
-                                Name::Number(11),
-                            ),
-                            uses: vec![
-                                Operand {
-                                    constant: true,
-                                    name: None,
-                                    ty: Type {
-                                        id: 15,
-                                        name: "ptr".to_string(),
-                                    },
-                                },
-                                Operand {
-                                    constant: false,
-                                    name: Some(
-                                        Name::Number(3),
-                                    ),
-                                    ty: Type {
-                                        id: 15,
-                                        name: "ptr".to_string(),
-                                    },
-                                },
-                                Operand {
-                                    constant: true,
-                                    name: None,
-                                    ty: Type {
-                                        id: 15,
-                                        name: "ptr".to_string(),
-                                    },
-                                },
-                            ],
-                            blocks: None,
-                            string: "  %11 = call i32 (ptr, ...) @scanf(ptr noundef @.str.1, ptr noundef %3)".to_string(),
-                        },
-                        Instruction {
-                            opcode: 32,
-                            def: Some(
-                                Name::Number(12),
-                            ),
-                            uses: vec![
-                                Operand {
-                                    constant: false,
-                                    name: Some(
-                                        Name::Number(4),
-                                    ),
-                                    ty: Type {
-                                        id: 15,
-                                        name: "ptr".to_string(),
-                                    },
-                                },
-                            ],
-                            blocks: None,
-                            string: "  %12 = load i32, ptr %4, align 4".to_string(),
-                        },
-                        Instruction {
-                            opcode: 32,
-                            def: Some(
-                                Name::Number(13),
-                            ),
-                            uses: vec![
-                                Operand {
-                                    constant: false,
-                                    name: Some(
-                                        Name::Number(5),
-                                    ),
-                                    ty: Type {
-                                        id: 15,
-                                        name: "ptr".to_string(),
-                                    },
-                                },
-                            ],
-                            blocks: None,
-                            string: "  %13 = load i32, ptr %5, align 4".to_string(),
-                        },
-                        Instruction {
-                            opcode: 56,
-                            def: Some(
-                                Name::Number(14),
-                            ),
-                            uses: vec![
-                                Operand {
-                                    constant: true,
-                                    name: None,
-                                    ty: Type {
-                                        id: 15,
-                                        name: "ptr".to_string(),
-                                    },
-                                },
-                                Operand {
-                                    constant: false,
-                                    name: Some(
-                                        Name::Number(12),
-                                    ),
-                                    ty: Type {
-                                        id: 13,
-                                        name: "i32".to_string(),
-                                    },
-                                },
-                                Operand {
-                                    constant: false,
-                                    name: Some(
-                                        Name::Number(13),
-                                    ),
-                                    ty: Type {
-                                        id: 13,
-                                        name: "i32".to_string(),
-                                    },
-                                },
-                                Operand {
-                                    constant: true,
-                                    name: None,
-                                    ty: Type {
-                                        id: 15,
-                                        name: "ptr".to_string(),
-                                    },
-                                },
-                            ],
-                            blocks: None,
-                            string: "  %14 = call i32 (ptr, ...) @printf(ptr noundef @.str.2, i32 noundef %12, i32 noundef %13)".to_string(),
-                        },
-                        Instruction {
-                            opcode: 33,
-                            def: None,
-                            uses: vec![
-                                Operand {
-                                    constant: true,
-                                    name: None,
-                                    ty: Type {
-                                        id: 13,
-                                        name: "i32".to_string(),
-                                    },
-                                },
-                                Operand {
-                                    constant: false,
-                                    name: Some(
-                                        Name::Number(2),
-                                    ),
-                                    ty: Type {
-                                        id: 15,
-                                        name: "ptr".to_string(),
-                                    },
-                                },
-                            ],
-                            blocks: None,
-                            string: "  store i32 3, ptr %2, align 4".to_string(),
-                        },
-                    ],
-                    term: Terminator {
-                        opcode: 2,
-                        def: None,
-                        uses: vec![
-                            Operand {
-                                constant: false,
-                                name: Some(
-                                    Name::Number(15),
-                                ),
-                                ty: Type {
-                                    id: 8,
-                                    name: "label".to_string(),
-                                },
-                            },
-                        ],
-                        string: "  br label %15".to_string(),
-                    },
-                },
-                BasicBlock {
-                    name: Name::Number(15),
-                    insts: vec![
-                        Instruction {
-                            opcode: 32,
-                            def: Some(
-                                Name::Number(16),
-                            ),
-                            uses: vec![
-                                Operand {
-                                    constant: false,
-                                    name: Some(
-                                        Name::Number(2),
-                                    ),
-                                    ty: Type {
-                                        id: 15,
-                                        name: "ptr".to_string(),
-                                    },
-                                },
-                            ],
-                            blocks: None,
-                            string: "  %16 = load i32, ptr %2, align 4".to_string(),
-                        },
-                        Instruction {
-                            opcode: 32,
-                            def: Some(
-                                Name::Number(17),
-                            ),
-                            uses: vec![
-                                Operand {
-                                    constant: false,
-                                    name: Some(
-                                        Name::Number(3),
-                                    ),
-                                    ty: Type {
-                                        id: 15,
-                                        name: "ptr".to_string(),
-                                    },
-                                },
-                            ],
-                            blocks: None,
-                            string: "  %17 = load i32, ptr %3, align 4".to_string(),
-                        },
-                        Instruction {
-                            opcode: 53,
-                            def: Some(
-                                Name::Number(18),
-                            ),
-                            uses: vec![
-                                Operand {
-                                    constant: false,
-                                    name: Some(
-                                        Name::Number(16),
-                                    ),
-                                    ty: Type {
-                                        id: 13,
-                                        name: "i32".to_string(),
-                                    },
-                                },
-                                Operand {
-                                    constant: false,
-                                    name: Some(
-                                        Name::Number(17),
-                                    ),
-                                    ty: Type {
-                                        id: 13,
-                                        name: "i32".to_string(),
-                                    },
-                                },
-                            ],
-                            blocks: None,
-                            string: "  %18 = icmp sle i32 %16, %17".to_string(),
-                        },
-                    ],
-                    term: Terminator {
-                        opcode: 2,
-                        def: None,
-                        uses: vec![
-                            Operand {
-                                constant: false,
-                                name: Some(
-                                    Name::Number(18),
-                                ),
-                                ty: Type {
-                                    id: 13,
-                                    name: "i1".to_string(),
-                                },
-                            },
-                            Operand {
-                                constant: false,
-                                name: Some(
-                                    Name::Number(30),
-                                ),
-                                ty: Type {
-                                    id: 8,
-                                    name: "label".to_string(),
-                                },
-                            },
-                            Operand {
-                                constant: false,
-                                name: Some(
-                                    Name::Number(19),
-                                ),
-                                ty: Type {
-                                    id: 8,
-                                    name: "label".to_string(),
-                                },
-                            },
-                        ],
-                        string: "  br i1 %18, label %19, label %30".to_string(),
-                    },
-                },
-                BasicBlock {
-                    name: Name::Number(19),
-                    insts: vec![
-                        Instruction {
-                            opcode: 32,
-                            def: Some(
-                                Name::Number(20),
-                            ),
-                            uses: vec![
-                                Operand {
-                                    constant: false,
-                                    name: Some(
-                                        Name::Number(6),
-                                    ),
-                                    ty: Type {
-                                        id: 15,
-                                        name: "ptr".to_string(),
-                                    },
-                                },
-                            ],
-                            blocks: None,
-                            string: "  %20 = load i32, ptr %6, align 4".to_string(),
-                        },
-                        Instruction {
-                            opcode: 56,
-                            def: Some(
-                                Name::Number(21),
-                            ),
-                            uses: vec![
-                                Operand {
-                                    constant: true,
-                                    name: None,
-                                    ty: Type {
-                                        id: 15,
-                                        name: "ptr".to_string(),
-                                    },
-                                },
-                                Operand {
-                                    constant: false,
-                                    name: Some(
-                                        Name::Number(20),
-                                    ),
-                                    ty: Type {
-                                        id: 13,
-                                        name: "i32".to_string(),
-                                    },
-                                },
-                                Operand {
-                                    constant: true,
-                                    name: None,
-                                    ty: Type {
-                                        id: 15,
-                                        name: "ptr".to_string(),
-                                    },
-                                },
-                            ],
-                            blocks: None,
-                            string: "  %21 = call i32 (ptr, ...) @printf(ptr noundef @.str.3, i32 noundef %20)".to_string(),
-                        },
-                        Instruction {
-                            opcode: 32,
-                            def: Some(
-                                Name::Number(22),
-                            ),
-                            uses: vec![
-                                Operand {
-                                    constant: false,
-                                    name: Some(
-                                        Name::Number(5),
-                                    ),
-                                    ty: Type {
-                                        id: 15,
-                                        name: "ptr".to_string(),
-                                    },
-                                },
-                            ],
-                            blocks: None,
-                            string: "  %22 = load i32, ptr %5, align 4".to_string(),
-                        },
-                        Instruction {
-                            opcode: 33,
-                            def: None,
-                            uses: vec![
-                                Operand {
-                                    constant: false,
-                                    name: Some(
-                                        Name::Number(22),
-                                    ),
-                                    ty: Type {
-                                        id: 13,
-                                        name: "i32".to_string(),
-                                    },
-                                },
-                                Operand {
-                                    constant: false,
-                                    name: Some(
-                                        Name::Number(4),
-                                    ),
-                                    ty: Type {
-                                        id: 15,
-                                        name: "ptr".to_string(),
-                                    },
-                                },
-                            ],
-                            blocks: None,
-                            string: "  store i32 %22, ptr %4, align 4".to_string(),
-                        },
-                        Instruction {
-                            opcode: 32,
-                            def: Some(
-                                Name::Number(23),
-                            ),
-                            uses: vec![
-                                Operand {
-                                    constant: false,
-                                    name: Some(
-                                        Name::Number(6),
-                                    ),
-                                    ty: Type {
-                                        id: 15,
-                                        name: "ptr".to_string(),
-                                    },
-                                },
-                            ],
-                            blocks: None,
-                            string: "  %23 = load i32, ptr %6, align 4".to_string(),
-                        },
-                        Instruction {
-                            opcode: 33,
-                            def: None,
-                            uses: vec![
-                                Operand {
-                                    constant: false,
-                                    name: Some(
-                                        Name::Number(23),
-                                    ),
-                                    ty: Type {
-                                        id: 13,
-                                        name: "i32".to_string(),
-                                    },
-                                },
-                                Operand {
-                                    constant: false,
-                                    name: Some(
-                                        Name::Number(5),
-                                    ),
-                                    ty: Type {
-                                        id: 15,
-                                        name: "ptr".to_string(),
-                                    },
-                                },
-                            ],
-                            blocks: None,
-                            string: "  store i32 %23, ptr %5, align 4".to_string(),
-                        },
-                        Instruction {
-                            opcode: 32,
-                            def: Some(
-                                Name::Number(24),
-                            ),
-                            uses: vec![
-                                Operand {
-                                    constant: false,
-                                    name: Some(
-                                        Name::Number(4),
-                                    ),
-                                    ty: Type {
-                                        id: 15,
-                                        name: "ptr".to_string(),
-                                    },
-                                },
-                            ],
-                            blocks: None,
-                            string: "  %24 = load i32, ptr %4, align 4".to_string(),
-                        },
-                        Instruction {
-                            opcode: 32,
-                            def: Some(
-                                Name::Number(25),
-                            ),
-                            uses: vec![
-                                Operand {
-                                    constant: false,
-                                    name: Some(
-                                        Name::Number(5),
-                                    ),
-                                    ty: Type {
-                                        id: 15,
-                                        name: "ptr".to_string(),
-                                    },
-                                },
-                            ],
-                            blocks: None,
-                            string: "  %25 = load i32, ptr %5, align 4".to_string(),
-                        },
-                        Instruction {
-                            opcode: 13,
-                            def: Some(
-                                Name::Number(26),
-                            ),
-                            uses: vec![
-                                Operand {
-                                    constant: false,
-                                    name: Some(
-                                        Name::Number(24),
-                                    ),
-                                    ty: Type {
-                                        id: 13,
-                                        name: "i32".to_string(),
-                                    },
-                                },
-                                Operand {
-                                    constant: false,
-                                    name: Some(
-                                        Name::Number(25),
-                                    ),
-                                    ty: Type {
-                                        id: 13,
-                                        name: "i32".to_string(),
-                                    },
-                                },
-                            ],
-                            blocks: None,
-                            string: "  %26 = add nsw i32 %24, %25".to_string(),
-                        },
-                        Instruction {
-                            opcode: 33,
-                            def: None,
-                            uses: vec![
-                                Operand {
-                                    constant: false,
-                                    name: Some(
-                                        Name::Number(26),
-                                    ),
-                                    ty: Type {
-                                        id: 13,
-                                        name: "i32".to_string(),
-                                    },
-                                },
-                                Operand {
-                                    constant: false,
-                                    name: Some(
-                                        Name::Number(6),
-                                    ),
-                                    ty: Type {
-                                        id: 15,
-                                        name: "ptr".to_string(),
-                                    },
-                                },
-                            ],
-                            blocks: None,
-                            string: "  store i32 %26, ptr %6, align 4".to_string(),
-                        },
-                    ],
-                    term: Terminator {
-                        opcode: 2,
-                        def: None,
-                        uses: vec![
-                            Operand {
-                                constant: false,
-                                name: Some(
-                                    Name::Number(27),
-                                ),
-                                ty: Type {
-                                    id: 8,
-                                    name: "label".to_string(),
-                                },
-                            },
-                        ],
-                        string: "  br label %27".to_string(),
-                    },
-                },
-                BasicBlock {
-                    name: Name::Number(27),
-                    insts: vec![
-                        Instruction {
-                            opcode: 32,
-                            def: Some(
-                                Name::Number(28),
-                            ),
-                            uses: vec![
-                                Operand {
-                                    constant: false,
-                                    name: Some(
-                                        Name::Number(2),
-                                    ),
-                                    ty: Type {
-                                        id: 15,
-                                        name: "ptr".to_string(),
-                                    },
-                                },
-                            ],
-                            blocks: None,
-                            string: "  %28 = load i32, ptr %2, align 4".to_string(),
-                        },
-                        Instruction {
-                            opcode: 13,
-                            def: Some(
-                                Name::Number(29),
-                            ),
-                            uses: vec![
-                                Operand {
-                                    constant: false,
-                                    name: Some(
-                                        Name::Number(28),
-                                    ),
-                                    ty: Type {
-                                        id: 13,
-                                        name: "i32".to_string(),
-                                    },
-                                },
-                                Operand {
-                                    constant: true,
-                                    name: None,
-                                    ty: Type {
-                                        id: 13,
-                                        name: "i32".to_string(),
-                                    },
-                                },
-                            ],
-                            blocks: None,
-                            string: "  %29 = add nsw i32 %28, 1".to_string(),
-                        },
-                        Instruction {
-                            opcode: 33,
-                            def: None,
-                            uses: vec![
-                                Operand {
-                                    constant: false,
-                                    name: Some(
-                                        Name::Number(29),
-                                    ),
-                                    ty: Type {
-                                        id: 13,
-                                        name: "i32".to_string(),
-                                    },
-                                },
-                                Operand {
-                                    constant: false,
-                                    name: Some(
-                                        Name::Number(2),
-                                    ),
-                                    ty: Type {
-                                        id: 15,
-                                        name: "ptr".to_string(),
-                                    },
-                                },
-                            ],
-                            blocks: None,
-                            string: "  store i32 %29, ptr %2, align 4".to_string(),
-                        },
-                    ],
-                    term: Terminator {
-                        opcode: 2,
-                        def: None,
-                        uses: vec![
-                            Operand {
-                                constant: false,
-                                name: Some(
-                                    Name::Number(15),
-                                ),
-                                ty: Type {
-                                    id: 8,
-                                    name: "label".to_string(),
-                                },
-                            },
-                        ],
-                        string: "  br label %15, !llvm.loop !5".to_string(),
-                    },
-                },
-                BasicBlock {
-                    name: Name::Number(30),
-                    insts: vec![],
-                    term: Terminator {
-                        opcode: 1,
-                        def: None,
-                        uses: vec![
-                            Operand {
-                                constant: true,
-                                name: None,
-                                ty: Type {
-                                    id: 13,
-                                    name: "i32".to_string(),
-                                },
-                            },
-                        ],
-                        string: "  ret i32 0".to_string(),
-                    },
-                },
-            ],
-        }),
+        owned(&lva(&f)),
         vec![
-            (
-                HashSet::from([]),
-                HashSet::from([&Name::Number(1)]),
-                "  %1 = alloca i32, align 4",
+            live(&[], &[1], "  %1 = alloca i32"),
+            live(&[1], &[1, 2], "  %2 = alloca i32"),
+            live(&[1, 2], &[1, 2, 3], "  %3 = alloca i32"),
+            live(&[1, 2, 3], &[1, 2, 3, 4], "  %4 = alloca i32"),
+            live(&[1, 2, 3, 4], &[1, 2, 3, 4, 5], "  %5 = alloca i32"),
+            live(&[1, 2, 3, 4, 5], &[1, 2, 3, 4, 5, 6], "  %6 = alloca i32"),
+            live(
+                &[1, 2, 3, 4, 5, 6],
+                &[2, 3, 4, 5, 6],
+                "  store i32 0, ptr %1"
             ),
-            (
-                HashSet::from([&Name::Number(1)]),
-                HashSet::from([&Name::Number(1), &Name::Number(2)]),
-                "  %2 = alloca i32, align 4",
+            live(&[2, 3, 4, 5, 6], &[2, 3, 4, 5, 6], "  store i32 0, ptr %4"),
+            live(&[2, 3, 4, 5, 6], &[2, 3, 4, 5, 6], "  store i32 1, ptr %5"),
+            live(
+                &[2, 3, 4, 5, 6],
+                &[2, 3, 4, 5, 6, 7],
+                "  %7 = load i32, ptr %4"
             ),
-            (HashSet::from([]), HashSet::from([]), "  ret i32 0"),
-            (
-                HashSet::from([&Name::Number(1), &Name::Number(2)]),
-                HashSet::from([&Name::Number(1), &Name::Number(2), &Name::Number(3)]),
-                "  %3 = alloca i32, align 4",
+            live(
+                &[2, 3, 4, 5, 6, 7],
+                &[2, 3, 4, 5, 6, 7, 8],
+                "  %8 = load i32, ptr %5"
             ),
-            (
-                HashSet::from([&Name::Number(1), &Name::Number(2), &Name::Number(3)]),
-                HashSet::from([
-                    &Name::Number(1),
-                    &Name::Number(2),
-                    &Name::Number(3),
-                    &Name::Number(4),
-                ]),
-                "  %4 = alloca i32, align 4",
+            live(
+                &[2, 3, 4, 5, 6, 7, 8],
+                &[2, 3, 4, 5, 6, 9],
+                "  %9 = add i32 %7, %8"
             ),
-            (
-                HashSet::from([
-                    &Name::Number(1),
-                    &Name::Number(2),
-                    &Name::Number(3),
-                    &Name::Number(4),
-                ]),
-                HashSet::from([
-                    &Name::Number(1),
-                    &Name::Number(2),
-                    &Name::Number(3),
-                    &Name::Number(4),
-                    &Name::Number(5),
-                ]),
-                "  %5 = alloca i32, align 4",
+            live(
+                &[2, 3, 4, 5, 6, 9],
+                &[2, 3, 4, 5, 6],
+                "  store i32 %9, ptr %6"
             ),
-            (
-                HashSet::from([
-                    &Name::Number(1),
-                    &Name::Number(2),
-                    &Name::Number(3),
-                    &Name::Number(4),
-                    &Name::Number(5),
-                ]),
-                HashSet::from([
-                    &Name::Number(1),
-                    &Name::Number(2),
-                    &Name::Number(3),
-                    &Name::Number(4),
-                    &Name::Number(5),
-                    &Name::Number(6),
-                ]),
-                "  %6 = alloca i32, align 4",
+            live(
+                &[2, 3, 4, 5, 6],
+                &[2, 3, 4, 5, 6],
+                "  %10 = call i32 (ptr, ...) @printf(ptr @.str)"
             ),
-            ( HashSet::from([&Name::Number(1),]), HashSet::from([]), "  store i32 0, ptr %1, align 4",),
-            ( HashSet::from([&Name::Number(2),]), HashSet::from([&Name::Number(2),]), "  store i32 0, ptr %4, align 4",),
-            ( HashSet::from([&Name::Number(2),]), HashSet::from([&Name::Number(2),]), "  store i32 1, ptr %5, align 4",),
-            ( HashSet::from([&Name::Number(2),]), HashSet::from([&Name::Number(2),]), "  %7 = load i32, ptr %4, align 4",),
-            ( HashSet::from([&Name::Number(2),]), HashSet::from([&Name::Number(2),]), "  %8 = load i32, ptr %5, align 4",),
-            ( HashSet::from([&Name::Number(2),]), HashSet::from([&Name::Number(2),]), "  %9 = add nsw i32 %7, %8",),
-            ( HashSet::from([&Name::Number(2),]), HashSet::from([&Name::Number(2),]), "  store i32 %9, ptr %6, align 4",),
-            ( HashSet::from([&Name::Number(2),]), HashSet::from([&Name::Number(2),]), "  %10 = call i32 (ptr, ...) @printf(ptr noundef @.str)",),
-            ( HashSet::from([&Name::Number(2),]), HashSet::from([&Name::Number(2),]), "  %11 = call i32 (ptr, ...) @scanf(ptr noundef @.str.1, ptr noundef %3)",),
-            ( HashSet::from([&Name::Number(2),]), HashSet::from([&Name::Number(2),]), "  %12 = load i32, ptr %4, align 4",),
-            ( HashSet::from([&Name::Number(2),]), HashSet::from([&Name::Number(2),]), "  %13 = load i32, ptr %5, align 4",),
-            ( HashSet::from([&Name::Number(2),]), HashSet::from([&Name::Number(2),]), "  %14 = call i32 (ptr, ...) @printf(ptr noundef @.str.2, i32 noundef %12, i32 noundef %13)",),
-            ( HashSet::from([&Name::Number(2),]), HashSet::from([&Name::Number(2),]), "  store i32 3, ptr %2, align 4",),
-            ( HashSet::from([&Name::Number(2),]), HashSet::from([&Name::Number(2),]), "  br label %15",),
-            ( HashSet::from([&Name::Number(2),]), HashSet::from([&Name::Number(2),]), "  %16 = load i32, ptr %2, align 4",),
-            ( HashSet::from([&Name::Number(2),]), HashSet::from([&Name::Number(2),]), "  %17 = load i32, ptr %3, align 4",),
-            ( HashSet::from([&Name::Number(2),]), HashSet::from([&Name::Number(2),]), "  %18 = icmp sle i32 %16, %17",),
-            ( HashSet::from([&Name::Number(2),]), HashSet::from([&Name::Number(2),]), "  br i1 %18, label %19, label %30",),
-            ( HashSet::from([&Name::Number(2),]), HashSet::from([&Name::Number(2),]), "  %20 = load i32, ptr %6, align 4",),
-            ( HashSet::from([&Name::Number(2),]), HashSet::from([&Name::Number(2),]), "  %21 = call i32 (ptr, ...) @printf(ptr noundef @.str.3, i32 noundef %20)",),
-            ( HashSet::from([&Name::Number(2),]), HashSet::from([&Name::Number(2),]), "  %22 = load i32, ptr %5, align 4",),
-            ( HashSet::from([&Name::Number(2),]), HashSet::from([&Name::Number(2),]), "  store i32 %22, ptr %4, align 4",),
-            ( HashSet::from([&Name::Number(2),]), HashSet::from([&Name::Number(2),]), "  %23 = load i32, ptr %6, align 4",),
-            ( HashSet::from([&Name::Number(2),]), HashSet::from([&Name::Number(2),]), "  store i32 %23, ptr %5, align 4",),
-            ( HashSet::from([&Name::Number(2),]), HashSet::from([&Name::Number(2),]), "  %24 = load i32, ptr %4, align 4",),
-            ( HashSet::from([&Name::Number(2),]), HashSet::from([&Name::Number(2),]), "  %25 = load i32, ptr %5, align 4",),
-            ( HashSet::from([&Name::Number(2),]), HashSet::from([&Name::Number(2),]), "  %26 = add nsw i32 %24, %25",),
-            ( HashSet::from([&Name::Number(2),]), HashSet::from([&Name::Number(2),]), "  store i32 %26, ptr %6, align 4",),
-            ( HashSet::from([&Name::Number(2),]), HashSet::from([&Name::Number(2),]), "  br label %27",),
-            ( HashSet::from([&Name::Number(2),]), HashSet::from([&Name::Number(2),]), "  %28 = load i32, ptr %2, align 4",),
-            ( HashSet::from([&Name::Number(2),]), HashSet::from([&Name::Number(2),]), "  %29 = add nsw i32 %28, 1",),
-            ( HashSet::from([&Name::Number(2),]), HashSet::from([&Name::Number(2),]), "  store i32 %29, ptr %2, align 4",),
-            ( HashSet::from([&Name::Number(2),]), HashSet::from([&Name::Number(2),]), "  br label %15, !llvm.loop !5",),
-            ( HashSet::from([]), HashSet::from([]), "  ret i32 0",),
+            live(
+                &[2, 3, 4, 5, 6],
+                &[2, 3, 4, 5, 6],
+                "  %11 = call i32 (ptr, ...) @scanf(ptr @.str.1, ptr %3)"
+            ),
+            live(
+                &[2, 3, 4, 5, 6],
+                &[2, 3, 4, 5, 6, 12],
+                "  %12 = load i32, ptr %4"
+            ),
+            live(
+                &[2, 3, 4, 5, 6, 12],
+                &[2, 3, 4, 5, 6, 12, 13],
+                "  %13 = load i32, ptr %5"
+            ),
+            live(
+                &[2, 3, 4, 5, 6, 12, 13],
+                &[2, 3, 4, 5, 6],
+                "  %14 = call i32 (ptr, ...) @printf(ptr @.str.2, i32 %12, i32 %13)"
+            ),
+            live(&[2, 3, 4, 5, 6], &[2, 3, 4, 5, 6], "  store i32 3, ptr %2"),
+            live(&[2, 3, 4, 5, 6], &[2, 3, 4, 5, 6], "  br label %15"),
+            live(
+                &[2, 3, 4, 5, 6],
+                &[2, 3, 4, 5, 6, 16],
+                "  %16 = load i32, ptr %2"
+            ),
+            live(
+                &[2, 3, 4, 5, 6, 16],
+                &[2, 3, 4, 5, 6, 16, 17],
+                "  %17 = load i32, ptr %3"
+            ),
+            live(
+                &[2, 3, 4, 5, 6, 16, 17],
+                &[2, 3, 4, 5, 6, 18],
+                "  %18 = icmp sle i32 %16, %17"
+            ),
+            live(
+                &[2, 3, 4, 5, 6, 18],
+                &[2, 3, 4, 5, 6],
+                "  br i1 %18, label %19, label %30"
+            ),
+            live(
+                &[2, 3, 4, 5, 6],
+                &[2, 3, 4, 5, 6, 20],
+                "  %20 = load i32, ptr %6"
+            ),
+            live(
+                &[2, 3, 4, 5, 6, 20],
+                &[2, 3, 4, 5, 6],
+                "  %21 = call i32 (ptr, ...) @printf(ptr @.str.3, i32 %20)"
+            ),
+            live(
+                &[2, 3, 4, 5, 6],
+                &[2, 3, 4, 5, 6, 22],
+                "  %22 = load i32, ptr %5"
+            ),
+            live(
+                &[2, 3, 4, 5, 6, 22],
+                &[2, 3, 4, 5, 6],
+                "  store i32 %22, ptr %4"
+            ),
+            live(
+                &[2, 3, 4, 5, 6],
+                &[2, 3, 4, 5, 6, 23],
+                "  %23 = load i32, ptr %6"
+            ),
+            live(
+                &[2, 3, 4, 5, 6, 23],
+                &[2, 3, 4, 5, 6],
+                "  store i32 %23, ptr %5"
+            ),
+            live(
+                &[2, 3, 4, 5, 6],
+                &[2, 3, 4, 5, 6, 24],
+                "  %24 = load i32, ptr %4"
+            ),
+            live(
+                &[2, 3, 4, 5, 6, 24],
+                &[2, 3, 4, 5, 6, 24, 25],
+                "  %25 = load i32, ptr %5"
+            ),
+            live(
+                &[2, 3, 4, 5, 6, 24, 25],
+                &[2, 3, 4, 5, 6, 26],
+                "  %26 = add i32 %24, %25"
+            ),
+            live(
+                &[2, 3, 4, 5, 6, 26],
+                &[2, 3, 4, 5, 6],
+                "  store i32 %26, ptr %6"
+            ),
+            live(&[2, 3, 4, 5, 6], &[2, 3, 4, 5, 6], "  br label %27"),
+            live(
+                &[2, 3, 4, 5, 6],
+                &[2, 3, 4, 5, 6, 28],
+                "  %28 = load i32, ptr %2"
+            ),
+            live(
+                &[2, 3, 4, 5, 6, 28],
+                &[2, 3, 4, 5, 6, 29],
+                "  %29 = add i32 %28, 1"
+            ),
+            live(
+                &[2, 3, 4, 5, 6, 29],
+                &[2, 3, 4, 5, 6],
+                "  store i32 %29, ptr %2"
+            ),
+            live(&[2, 3, 4, 5, 6], &[2, 3, 4, 5, 6], "  br label %15"),
+            live(&[], &[], "  ret i32 0"),
         ],
-    );*/
+    );
 }
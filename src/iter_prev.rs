@@ -28,14 +28,17 @@ where
         if self.0 > 0 {
             self.0 -= 1;
             Some(self.2[self.0].clone())
-        } else if self.0 == 1 {
-            self.0 -= 1;
-            None
         } else {
             None
         }
     }
 
+    /// Number of items before the cursor: `next` returns the item at this
+    /// index and `prev` the one before it.
+    pub fn position(&self) -> usize {
+        self.0
+    }
+
     pub fn first(&mut self) -> Option<<I as Iterator>::Item> {
         if self.0 > 0 {
             self.0 = 0;
@@ -120,5 +123,7 @@ fn test_iter_first() {
 
     let mut iter = Iter::new(0..5);
     let _: Vec<_> = iter.by_ref().take(3).collect();
+    assert_eq!(iter.position(), 3);
     assert_eq!(iter.first(), Some(0));
+    assert_eq!(iter.position(), 0);
 }
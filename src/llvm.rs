@@ -20,6 +20,61 @@ pub fn parse(input: &str) -> crate::ir::Module {
         js_sys::Array::of1(&JsValue::from_str(input)).into(),
     );
 
+    json(input)
+}
+
+/// Whether `bytes` look like LLVM bitcode, either raw or in a wrapper.
+pub fn is_bitcode(bytes: &[u8]) -> bool {
+    bytes.starts_with(b"BC\xC0\xDE") || bytes.starts_with(&[0xDE, 0xC0, 0x17, 0x0B])
+}
+
+fn exported(name: &str) -> bool {
+    js_sys::Reflect::get(&js_sys::global(), &JsValue::from_str("Module"))
+        .and_then(|m| js_sys::Reflect::get(&m, &JsValue::from_str(&format!("_{name}"))))
+        .is_ok_and(|f| f.is_function())
+}
+
+/// Reads the bitcode in `bytes`, returning its disassembly along with the
+/// module. This needs a build of llvm-json that exports `parse_bitcode`,
+/// which returns non-zero on failure, and `disassemble`.
+pub fn parse_bitcode(bytes: &[u8]) -> Result<(String, crate::ir::Module), String> {
+    if !exported("parse_bitcode") || !exported("disassemble") {
+        return Err(
+            "this build of llvm-json cannot read bitcode, run llvm-dis on it first".to_string(),
+        );
+    }
+
+    let status = Module::ccall(
+        JsValue::from_str("parse_bitcode"),
+        JsValue::from_str("number"),
+        js_sys::Array::of2(&JsValue::from_str("array"), &JsValue::from_str("number")).into(),
+        js_sys::Array::of2(
+            &js_sys::Uint8Array::from(bytes),
+            &JsValue::from_f64(bytes.len() as f64),
+        )
+        .into(),
+    );
+    if status.as_f64() != Some(0.0) {
+        return Err("invalid bitcode".to_string());
+    }
+
+    let ptr = Module::ccall(
+        JsValue::from_str("disassemble"),
+        JsValue::from_str("number"),
+        js_sys::Array::new().into(),
+        js_sys::Array::new().into(),
+    );
+    let text = Module::UTF8ToString(ptr)
+        .as_string()
+        .ok_or("disassembly is not a string")?;
+
+    let m = json(&text);
+    Ok((text, m))
+}
+
+/// The module last parsed, with what the JSON leaves out read from its
+/// `text`.
+fn json(text: &str) -> crate::ir::Module {
     let ptr = Module::ccall(
         JsValue::from_str("json"),
         JsValue::from_str("number"),
@@ -36,5 +91,19 @@ pub fn parse(input: &str) -> crate::ir::Module {
     )
     .unwrap();
 
-    serde_json::from_str(&String::from(out)).unwrap()
+    let mut m: crate::ir::Module = serde_json::from_str(&String::from(out)).unwrap();
+    m.info = crate::module::parse(text);
+    m.debug = crate::debug::parse(text);
+    for f in &mut m.functions {
+        crate::text::read(f);
+    }
+    m
+}
+
+#[test]
+fn test_is_bitcode() {
+    assert!(is_bitcode(b"BC\xC0\xDE\x35\x14\x00\x00"));
+    assert!(is_bitcode(&[0xDE, 0xC0, 0x17, 0x0B, 0, 0, 0, 0]));
+    assert!(!is_bitcode(b"; ModuleID = 'for1.c'"));
+    assert!(!is_bitcode(b"BC"));
 }
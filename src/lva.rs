@@ -1,6 +1,6 @@
 #![allow(non_snake_case)]
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 use dioxus::prelude::*;
 
@@ -8,6 +8,11 @@ use dioxus::prelude::*;
 pub fn Lva(
     old: Vec<(HashSet<crate::ir::Name>, HashSet<crate::ir::Name>, String)>,
     new: Vec<(HashSet<crate::ir::Name>, HashSet<crate::ir::Name>, String)>,
+    violations: Vec<crate::alloc::Violation>,
+    selected: Option<usize>,
+    highlight: Signal<Option<crate::ir::Name>>,
+    onselect: EventHandler<usize>,
+    variables: HashMap<crate::ir::Name, String>,
 ) -> Element {
     let mut names: Vec<_> = new
         .iter()
@@ -18,7 +23,8 @@ pub fn Lva(
     names.sort();
     names.dedup();
 
-    let mut highlight = use_signal(|| None::<crate::ir::Name>);
+    let mut highlight = highlight;
+    let labels = |set: &HashSet<crate::ir::Name>| crate::debug::labels(set, &variables);
 
     rsx! {
         div { class: "ml-1",
@@ -31,44 +37,85 @@ pub fn Lva(
                 },
                 option { "None" }
                 for (i , name) in names.iter().enumerate() {
-                    option { value: "{i}", "{name:?}" }
+                    option {
+                        value: "{i}",
+                        selected: highlight.read().as_ref() == Some(name),
+                        {crate::debug::label(name, &variables)}
+                    }
                 }
             }
             div { class: "font-mono whitespace-pre bg-white box-border",
-                for ((oin , oout , _) , (r#in , out , s)) in old.iter().zip(new) {
-                    if highlight.read().is_some()
+                for (j , ((oin , oout , _) , (r#in , out , s))) in old.iter().zip(new).enumerate() {
+                    if selected == Some(j) {
+                        div {
+                            class: "flex bg-yellow-100 dark:bg-yellow-900 cursor-pointer",
+                            onclick: move |_| onselect(j),
+                            onmounted: move |e: MountedEvent| async move {
+                                let _ = e.data.scroll_to(ScrollBehavior::Smooth).await;
+                            },
+                            span { class: "flex-none text-right w-1/4 text-green-900",
+                                {labels(&r#in)}
+                            }
+                            span { class: "flex-none text-right w-1/4 text-red-900",
+                                {labels(&out)}
+                            }
+                            span { class: "flex-none w-1/2", "{s}" }
+                        }
+                    } else if violations.iter().any(|v| v.index() == j) {
+                        div {
+                            class: "flex bg-red-100 dark:bg-red-950 cursor-pointer",
+                            onclick: move |_| onselect(j),
+                            title: violations
+                                .iter()
+                                .filter(|v| v.index() == j)
+                                .map(|v| v.to_string())
+                                .collect::<Vec<_>>()
+                                .join("\n"),
+                            span { class: "flex-none text-right w-1/4 text-green-900",
+                                {labels(&r#in)}
+                            }
+                            span { class: "flex-none text-right w-1/4 text-red-900",
+                                {labels(&out)}
+                            }
+                            span { class: "flex-none w-1/2 text-red-500", "{s}" }
+                        }
+                    } else if highlight.read().is_some()
                         && (r#in.contains(&highlight.read().clone().unwrap())
                             || out.contains(&highlight.read().clone().unwrap()))
                     {
 
-                        div { class: "flex text-red-500",
+                        div {
+                            class: "flex text-red-500 cursor-pointer",
+                            onclick: move |_| onselect(j),
                             span { class: "flex-none text-right w-1/4 text-green-500",
-                                "{r#in:?}"
+                                {labels(&r#in)}
                             }
                             span { class: "flex-none text-right w-1/4 text-red-500",
-                                "{out:?}"
+                                {labels(&out)}
                             }
                             span { class: "flex-none w-1/2", "{s}" }
                         }
                     } else {
-                        div { class: "flex",
+                        div {
+                            class: "flex cursor-pointer",
+                            onclick: move |_| onselect(j),
                             if *oin == r#in {
 
                                 span { class: "flex-none text-right w-1/4 text-green-900",
-                                    "{r#in:?}"
+                                    {labels(&r#in)}
                                 }
                             } else {
                                 span { class: "flex-none text-right w-1/4 text-green-500",
-                                    "{r#in:?}"
+                                    {labels(&r#in)}
                                 }
                             }
                             if *oout == out {
                                 span { class: "flex-none text-right w-1/4 text-red-900",
-                                    "{out:?}"
+                                    {labels(&out)}
                                 }
                             } else {
                                 span { class: "flex-none text-right w-1/4 text-red-500",
-                                    "{out:?}"
+                                    {labels(&out)}
                                 }
                             }
                             span { class: "flex-none w-1/2", "{s}" }
@@ -76,6 +123,9 @@ pub fn Lva(
                     }
                 }
             }
+            for v in violations.iter() {
+                div { class: "font-mono text-red-500", "{v.index()}: {v}" }
+            }
         }
     }
 }
@@ -1,21 +1,42 @@
 #![allow(non_snake_case)]
 
-use std::collections::HashSet;
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
 
 use dioxus::prelude::*;
 use tracing::Level;
 use wasm_bindgen::prelude::*;
 
+mod alloc;
+mod asm;
+mod builder;
+mod c_source;
+mod callgraph;
 mod code;
+mod debug;
+mod diagnostics;
+mod dot;
 mod editor;
 mod example_picker;
+mod export;
 mod graphviz;
 mod ir;
 mod iter_prev;
 mod llvm;
 mod lva;
+mod mem2reg;
+mod module;
+mod open;
+mod permalink;
+mod pressure;
+mod ranges;
+mod source;
+mod stack;
 mod tabs;
+mod text;
 mod util;
+mod workspace;
 
 fn main() {
     console_error_panic_hook::set_once();
@@ -25,15 +46,8 @@ fn main() {
     launch(App);
 }
 
-#[wasm_bindgen]
-extern "C" {
-    #[wasm_bindgen(js_namespace = Graphviz)]
-    async fn load(g: &JsValue) -> wasm_bindgen::JsValue;
-}
-
 #[component]
 fn App() -> Element {
-    let mut output_cfg = use_signal(|| vec![(String::new(), String::new(), String::new())]);
     let mut output_lva = use_signal(|| {
         vec![(
             String::new(),
@@ -50,6 +64,245 @@ fn App() -> Element {
         )]
     });
     let mut output_iter: Signal<Vec<iter_prev::Iter<ir::Iter>>> = use_signal(Vec::new);
+    // whether each stepper last stepped back, showing the step at its
+    // position rather than the one before it
+    let mut output_back: Signal<Vec<bool>> = use_signal(Vec::new);
+    let mut output_fns: Signal<Vec<ir::Function>> = use_signal(Vec::new);
+    let restore = use_hook(permalink::read);
+    let mut workspace = use_signal(|| {
+        let mut w = workspace::load().unwrap_or_else(|| {
+            workspace::Workspace::new(include_str!("../examples/ll/for1.ll").to_string())
+        });
+        if let Some(state) = &restore {
+            // reuse the document of a permalink that was opened before
+            w.current = match w.documents.iter().position(|d| d.text == state.text) {
+                Some(i) => i,
+                None => w.add("permalink", state.text.clone()),
+            };
+            let settings = &mut w.current_mut().settings;
+            settings.tab = state.tab;
+            settings.function = state.function;
+        }
+        w
+    });
+    let settings = use_hook(|| workspace.peek().current().settings.clone());
+    let mut assignments = use_signal(|| settings.assignments.clone());
+    let mut k = use_signal(|| settings.k);
+    let mut engine = use_signal(|| settings.engine);
+    let mut output_src: Signal<HashMap<String, source::Lines>> = use_signal(HashMap::new);
+    let mut output_markers: Signal<Vec<editor::Marker>> = use_signal(Vec::new);
+    let mut output_module: Signal<module::Info> = use_signal(Default::default);
+    let mut output_debug: Signal<debug::Info> = use_signal(Default::default);
+    // the C source of the current document and the lines hovered in it
+    // and in the editor
+    let mut c_source = use_signal(|| workspace.peek().current().source.clone());
+    let mut c_hover = use_signal(|| None::<usize>);
+    let mut ir_hover = use_signal(|| None::<usize>);
+    let mut reveal = use_signal(|| None::<(usize, usize)>);
+    // the name and read-only disassembly of an opened bitcode file
+    let mut disassembly = use_signal(|| None::<(String, String)>);
+    let mut open_error = use_signal(|| None::<String>);
+    let mut tab = use_signal(|| settings.tab);
+    let mut function = use_signal(|| settings.function);
+    let mut auto = use_signal(|| settings.auto);
+    // bumped on every edit so only the last of a burst of edits parses
+    let mut edits = use_signal(|| 0usize);
+    let mut selected = use_signal(|| None::<(usize, usize)>);
+    // the value highlighted in the liveness table and the editor hints
+    let highlight = use_signal(|| None::<ir::Name>);
+    // layouts in every format by the DOT and engine they were laid out from,
+    // so the CFGs of functions that did not change are not laid out again
+    let layouts = use_hook(|| Rc::new(RefCell::new(HashMap::new())));
+    let output_cfg = use_resource(move || {
+        let layouts = layouts.clone();
+        async move {
+            let k = k();
+            let engine = engine();
+            let fns = output_fns();
+            let lva = output_lva();
+            let cfgs = futures::future::join_all(fns.iter().enumerate().map(|(i, f)| {
+                let layouts = layouts.clone();
+                let (old, new) = lva
+                    .get(i)
+                    .map(|(_, old, new)| (old.as_slice(), new.as_slice()))
+                    .unwrap_or_default();
+                let (old, new) = (ir::block_lives(f, old), ir::block_lives(f, new));
+                async move {
+                    let profile = pressure::pressure(f, &ir::lva(f));
+                    let highlight = " color=\"red\" penwidth=3";
+                    let node = |b: usize, _: &ir::BasicBlock| {
+                        let max = profile.blocks[b].1;
+                        let (r#in, out) = &new[b];
+                        dot::Node {
+                            before: Some(format!("in: {}", debug::labels(r#in, &HashMap::new()))),
+                            after: Some(format!("out: {}", debug::labels(out, &HashMap::new()))),
+                            attrs: format!(
+                                "style=filled fillcolor=\"{}\" tooltip=\"MaxLive {max}\"{}",
+                                pressure::heat(max, k),
+                                if old[b] != new[b] { highlight } else { "" },
+                            ),
+                        }
+                    };
+                    // an edge is highlighted if liveness just flowed across it,
+                    // i.e. values live into its target were added to its source
+                    let edge = |b: usize, s: usize| {
+                        let added: HashSet<_> = new[b].1.difference(&old[b].1).collect();
+                        let phis = f.basic_blocks[s]
+                            .insts
+                            .iter()
+                            .take_while(|i| i.opcode == 55 /* phi */)
+                            .flat_map(|phi| phi.blocks.iter().flatten().zip(&phi.uses))
+                            .filter(|(from, _)| **from == f.basic_blocks[b].name)
+                            .filter_map(|(_, u)| u.name.as_ref());
+                        if new[s].0.iter().chain(phis).any(|n| added.contains(n)) {
+                            highlight.to_string()
+                        } else {
+                            String::new()
+                        }
+                    };
+                    let dot = dot::cfg(f, node, edge);
+                    let cached = layouts.borrow().get(&(dot.clone(), engine)).cloned();
+                    let laid = match cached {
+                        Some(laid) => laid,
+                        None => {
+                            let render = |format| {
+                                let dot = &dot;
+                                async move {
+                                    let layout = graphviz::render(dot, engine, format).await;
+                                    (format, layout.map_err(|e| format!("{e:?}")))
+                                }
+                            };
+                            let laid: Vec<_> =
+                                futures::future::join_all(graphviz::Format::ALL.map(render)).await;
+                            layouts.borrow_mut().insert((dot.clone(), engine), laid.clone());
+                            laid
+                        }
+                    };
+                    (f.name.clone(), dot, laid)
+                }
+            }))
+            .await;
+            layouts
+                .borrow_mut()
+                .retain(|(dot, e), _| *e == engine && cfgs.iter().any(|(_, d, _)| d == dot));
+            cfgs
+        }
+    });
+    // every function before and after promoting its stack slots to SSA
+    // values, with liveness computed on both
+    let output_ssa = use_resource(move || async move {
+        let engine = engine();
+        let render = move |dot: String| async move {
+            graphviz::render(&dot, engine, graphviz::Format::Svg)
+                .await
+                .map_err(|e| format!("{e:?}"))
+        };
+        futures::future::join_all(output_fns().into_iter().map(|f| async move {
+            let before = render(mem2reg::cfg(&f)).await;
+            let after = match mem2reg::promote(&f) {
+                Ok(p) => Ok((p.to_string(), render(mem2reg::cfg(&p)).await)),
+                Err(e) => Err(e),
+            };
+            (f.name.clone(), (f.to_string(), before), after)
+        }))
+        .await
+    });
+    let output_calls = use_resource(move || async move {
+        let graph = callgraph::build(&output_fns());
+        let dot = callgraph::dot(&graph);
+        let svg = graphviz::render(&dot, engine(), graphviz::Format::Svg)
+            .await
+            .map_err(|e| format!("{e:?}"));
+        (graph, dot, svg)
+    });
+    type Layouts = Vec<(graphviz::Format, Result<String, String>)>;
+    let map_cfg = |(i, (name, dot, layouts)): (usize, (String, String, Layouts))| {
+        // the first layout is the SVG that is shown
+        let svg = layouts[0].1.clone();
+        let onclick = move |e: MouseEvent| {
+            let Some(block) = e
+                .data
+                .downcast::<web_sys::MouseEvent>()
+                .and_then(|e| e.target())
+                .and_then(|t| t.dyn_into::<web_sys::Element>().ok())
+                .and_then(|t| t.closest("g.node").ok().flatten())
+                .and_then(|n| n.id().strip_prefix("block")?.parse::<usize>().ok())
+            else {
+                return;
+            };
+            let Some(f) = output_fns.read().get(i).cloned() else {
+                return;
+            };
+            if let Some(lines) = output_src.read().get(&f.name) {
+                *reveal.write() = lines.blocks.get(block).map(|l| (l + 1, l + 1));
+            }
+            *selected.write() = ranges::block_starts(&f).get(block).map(|&row| (i, row));
+        };
+        // outline the block of the selected instruction
+        let outline = output_fns.read().get(i).and_then(|f| {
+            let (_, row) = selected().filter(|&(f, _)| f == i)?;
+            let block = ranges::block_starts(f).iter().rposition(|&s| s <= row)?;
+            Some(format!(
+                "#block{block} polygon {{ stroke: #eab308; stroke-width: 4px; }}"
+            ))
+        });
+        let html = match (&svg, output_fns.read().get(i)) {
+            (Ok(svg), Some(f)) => {
+                // the fixed point rather than the stepper's state
+                let lives = ir::owned_lva(f);
+                let assignment = assignments.read().get(&f.name).cloned().unwrap_or_default();
+                Some(export::html(f, svg, &lives, &assignment))
+            }
+            _ => None,
+        };
+        (
+            name.clone(),
+            rsx! {
+                div {
+                    div { class: "ml-1",
+                        "Export:"
+                        for (format , layout) in layouts {
+                            if let Ok(layout) = layout {
+                                export::Download {
+                                    name: "{name}.{format.name()}",
+                                    mime: "{format.mime()}",
+                                    data: layout,
+                                }
+                            }
+                        }
+                        export::Download { name: "{name}.dot", mime: "text/vnd.graphviz", data: "{dot}" }
+                        if let Some(html) = html {
+                            export::Download { name: "{name}.html", mime: "text/html", data: html }
+                        }
+                    }
+                    match svg {
+                        Ok(svg) => rsx! {
+                            if let Some(outline) = outline {
+                                style { "{outline}" }
+                            }
+                            div { class: "dark:invert cursor-pointer", onclick, dangerous_inner_html: "{svg}" }
+                        },
+                        Err(e) => rsx! {
+                            div { class: "text-red-500", "Graphviz: {e}" }
+                        },
+                    }
+                    code::Code { code: "{dot}" }
+                }
+            },
+        )
+    };
+    // selects instruction `row` of function `i` in the views and reveals it
+    // in the editor
+    let mut select = move |i: usize, row: usize| {
+        selected.set(Some((i, row)));
+        let lines = output_fns
+            .read()
+            .get(i)
+            .and_then(|f| output_src.read().get(&f.name)?.inst(row));
+        if let Some((first, last)) = lines {
+            reveal.set(Some((first + 1, last + 1)));
+        }
+    };
     let map_lva = |(i, a): (
         usize,
         (String, Vec<ir::OwnedInstLive>, Vec<ir::OwnedInstLive>),
@@ -58,6 +311,7 @@ fn App() -> Element {
             if let Some(iter) = output_iter.write().get_mut(i) {
                 if let Some(lives) = iter.next() {
                     tracing::info!("next: {:?}", lives);
+                    output_back.write()[i] = false;
                     let name = output_lva.read()[i].0.clone();
                     let old = output_lva.read()[i].2.clone();
                     output_lva.write()[i] = (name, old, lives);
@@ -68,6 +322,7 @@ fn App() -> Element {
             if let Some(iter) = output_iter.write().get_mut(i) {
                 if let Some(lives) = iter.last() {
                     tracing::info!("last: {:?}", lives);
+                    output_back.write()[i] = false;
                     let name = output_lva.read()[i].0.clone();
                     let old = output_lva.read()[i].2.clone();
                     output_lva.write()[i] = (name, old, lives);
@@ -76,7 +331,9 @@ fn App() -> Element {
         };
         let mut lva_prev = move || {
             if let Some(iter) = output_iter.write().get_mut(i) {
-                if let Some(lives) = iter.prev() {
+                let back = iter.prev();
+                output_back.write()[i] = back.is_some();
+                if let Some(lives) = back {
                     tracing::info!("prev: {:?}", lives);
                     let name = output_lva.read()[i].0.clone();
                     let old = output_lva.read()[i].2.clone();
@@ -97,6 +354,7 @@ fn App() -> Element {
         let mut lva_reset = move || {
             if let Some(iter) = output_iter.write().get_mut(i) {
                 let _ = iter.first();
+                output_back.write()[i] = false;
                 let name = output_lva.read()[i].0.clone();
                 let old = output_lva.read()[i].2.clone();
                 output_lva.write()[i] = (
@@ -108,11 +366,73 @@ fn App() -> Element {
                 );
             }
         };
+        let assignment = assignments.read().get(&a.0).cloned().unwrap_or_default();
+        let parsed = alloc::parse(&assignment);
+        let f = output_fns.read().get(i).cloned();
+        let lva = f.as_ref().map(ir::lva);
+        let violations = match (&f, &lva, &parsed) {
+            (Some(f), Some(lva), Ok((assignment, precolored)))
+                if !assignment.is_empty() || !precolored.is_empty() =>
+            {
+                alloc::check(f, lva, assignment, precolored)
+            }
+            _ => vec![],
+        };
+        let error = parsed.err();
+        let variables = output_debug.read().names(&a.0);
+        let legend = debug::labels(&variables.keys().cloned().collect(), &variables);
+        let pressure = f.as_ref().zip(lva.as_ref()).map(|(f, lva)| pressure::pressure(f, lva));
+        // export the fixed point, wherever the stepper is
+        let fixed = lva.as_deref().map(ir::owned).unwrap_or_default();
+        let (json, csv) = (export::lva_json(&fixed), export::lva_csv(&fixed));
+        let lives = a.2.clone();
+        let stack = f
+            .as_ref()
+            .map(stack::analyze)
+            .filter(|s| !s.slots.is_empty());
+        let name = a.0.clone();
+        let onselect = move |row| select(i, row);
         (
             a.0.clone(),
             rsx! {
                 div {
-                    lva::Lva { old: a.1, new: a.2 }
+                    div { class: "ml-1",
+                        "Export:"
+                        export::Download { name: "{a.0}.json", mime: "application/json", data: json }
+                        export::Download { name: "{a.0}.csv", mime: "text/csv", data: csv }
+                    }
+                    div { class: "flex ml-1",
+                        {"Registers: "},
+                        input {
+                            class: "flex-1 font-mono bg-white dark:bg-zinc-800",
+                            placeholder: "%1=r0 %2=r1 %0=r0!",
+                            value: "{assignment}",
+                            oninput: move |e: Event<FormData>| {
+                                assignments.write().insert(name.clone(), e.data.value());
+                            },
+                        }
+                    }
+                    if !variables.is_empty() {
+                        div { class: "ml-1 font-mono text-zinc-500", "Variables: {legend}" }
+                    }
+                    if let Some(error) = error {
+                        div { class: "ml-1 text-red-500", "{error}" }
+                    }
+                    if let Some(pressure) = pressure {
+                        div { class: "flex ml-1 items-center",
+                            "MaxLive: {pressure.max} {pressure.classes:?}"
+                            pressure::Sparkline { values: pressure.totals(), k: k() }
+                        }
+                    }
+                    lva::Lva {
+                        old: a.1,
+                        new: a.2,
+                        violations,
+                        selected: selected().filter(|&(f, _)| f == i).map(|(_, row)| row),
+                        highlight,
+                        variables: variables.clone(),
+                        onselect,
+                    }
                     div { class: "flex columns-4",
                         button {
                             class: "w-full h-12",
@@ -135,37 +455,200 @@ fn App() -> Element {
                             ">>"
                         }
                     }
+                    if let Some(f) = f {
+                        ranges::Ranges { f, lives, variables: variables.clone() }
+                    }
+                    if let Some(stack) = stack {
+                        stack::Slots { stack: stack.clone(), variables: variables.clone() }
+                        lva::Lva {
+                            old: stack.lives.clone(),
+                            new: stack.lives,
+                            violations: vec![],
+                            selected: selected().filter(|&(f, _)| f == i).map(|(_, row)| row),
+                            highlight,
+                            variables,
+                            onselect,
+                        }
+                    }
                 }
             },
         )
     };
 
-    let mut content = use_signal(|| include_str!("../examples/ll/for1.ll").to_string());
+    let map_asm = |(i, f): (usize, &ir::Function)| {
+        let assignment = assignments.read().get(&f.name).cloned().unwrap_or_default();
+        let (mut assignment, precolored) = alloc::parse(&assignment).unwrap_or_default();
+        assignment.extend(precolored);
+        let strings = ir::strings(f);
+        let lines = asm::select(f, &assignment);
+        let row = selected().filter(|&(f, _)| f == i).map(|(_, row)| row);
+        let rows: Vec<_> = lines
+            .iter()
+            .enumerate()
+            .map(|(k, l)| {
+                let ir = match l.index {
+                    Some(j) if k == 0 || lines[k - 1].index != l.index => {
+                        strings[j].trim().to_string()
+                    }
+                    _ => String::new(),
+                };
+                // lines of an instruction are linked back to its row
+                let class = match l.index {
+                    Some(_) if l.index == row => {
+                        "flex cursor-pointer bg-yellow-100 dark:bg-yellow-900"
+                    }
+                    Some(_) => "flex cursor-pointer",
+                    None => "flex",
+                };
+                (l.index, class, l.text.clone(), ir)
+            })
+            .collect();
+        let text: String = lines.iter().map(|l| format!("{}\n", l.text)).collect();
+        (
+            f.name.clone(),
+            rsx! {
+                div { class: "ml-1",
+                    "Export:"
+                    export::Download { name: "{f.name}.s", mime: "text/plain", data: text }
+                }
+                div { class: "font-mono whitespace-pre ml-1",
+                    for (index , class , text , ir) in rows {
+                        div {
+                            class,
+                            onclick: move |_| {
+                                if let Some(index) = index {
+                                    select(i, index);
+                                }
+                            },
+                            span { class: "flex-none w-1/2", "{text}" }
+                            span { class: "flex-none w-1/2 text-zinc-500", "{ir}" }
+                        }
+                    }
+                }
+            },
+        )
+    };
 
-    let onclickparse = move |_| async move {
-        let input = content.read().clone();
-        let m: ir::Module = llvm::parse(&input);
+    type Listing = (String, Result<String, String>);
+    let map_ssa = |(name, before, after): (String, Listing, Result<Listing, String>)| {
+        let column = |title: &str, (text, svg): Listing| {
+            rsx! {
+                div { class: "w-1/2 min-w-0",
+                    h2 { class: "ml-1 font-bold", "{title}" }
+                    match svg {
+                        Ok(svg) => rsx! {
+                            div { class: "dark:invert", dangerous_inner_html: "{svg}" }
+                        },
+                        Err(e) => rsx! {
+                            div { class: "text-red-500", "Graphviz: {e}" }
+                        },
+                    }
+                    code::Code { code: text }
+                }
+            }
+        };
+        (
+            name,
+            rsx! {
+                div { class: "flex",
+                    {column("Before", before)}
+                    match after {
+                        Ok(after) => column("After mem2reg", after),
+                        Err(e) => rsx! {
+                            div { class: "w-1/2 ml-1 text-red-500", "{e}" }
+                        },
+                    }
+                }
+            },
+        )
+    };
 
-        *output_cfg.write() = futures::future::join_all(m.functions.iter().map(|f| async {
-            let (_blocks, cfg) = ir::cfg(f);
-            let dot = petgraph::dot::Dot::with_config(
-                &cfg,
-                &[
-                    petgraph::dot::Config::EdgeNoLabel,
-                    petgraph::dot::Config::_GraphAttr("bgcolor", "transparent"),
-                ],
-            );
-            let svg = graphviz::svg(&dot).await;
-            (f.name.clone(), format!("{dot:?}"), svg)
-        }))
-        .await;
+    let calls = move || {
+        let (graph, dot, svg) = output_calls.read().clone()?;
+        // switch the per-function tabs to the function clicked
+        let onclick = move |e: MouseEvent| {
+            let Some(f) = e
+                .data
+                .downcast::<web_sys::MouseEvent>()
+                .and_then(|e| e.target())
+                .and_then(|t| t.dyn_into::<web_sys::Element>().ok())
+                .and_then(|t| t.closest("g.node").ok().flatten())
+                .and_then(|n| n.id().strip_prefix("function")?.parse::<usize>().ok())
+            else {
+                return;
+            };
+            function.set(f);
+        };
+        let outline = format!(
+            "#function{} polygon {{ stroke: #eab308; stroke-width: 4px; }}",
+            function(),
+        );
+        let sccs: Vec<_> = graph
+            .sccs
+            .iter()
+            .map(|scc| {
+                let names: Vec<_> = scc
+                    .iter()
+                    .map(|&i| format!("@{}", graph.names[i]))
+                    .collect();
+                names.join(", ")
+            })
+            .collect();
+        rsx! {
+            div {
+                div { class: "ml-1",
+                    "Export:"
+                    if let Ok(svg) = &svg {
+                        export::Download { name: "calls.svg", mime: "image/svg+xml", data: "{svg}" }
+                    }
+                    export::Download { name: "calls.dot", mime: "text/vnd.graphviz", data: "{dot}" }
+                }
+                for scc in sccs {
+                    div { class: "ml-1 text-red-500", "Mutually recursive: {scc}" }
+                }
+                match svg {
+                    Ok(svg) => rsx! {
+                        style { "{outline}" }
+                        div { class: "dark:invert cursor-pointer", onclick, dangerous_inner_html: "{svg}" }
+                    },
+                    Err(e) => rsx! {
+                        div { class: "text-red-500", "Graphviz: {e}" }
+                    },
+                }
+                code::Code { code: "{dot}" }
+            }
+        }
+    };
+
+    let mut content = use_signal(|| workspace.peek().current().text.clone());
 
-        *output_lva.write() = m
+    let mut analyze = move |input: String, m: ir::Module| {
+        // functions that did not change keep their results and stepper
+        let old_fns = output_fns.peek().clone();
+        let old_lva = output_lva.peek().clone();
+        let mut old_iters: Vec<_> = std::mem::take(&mut *output_iter.write())
+            .into_iter()
+            .map(Some)
+            .collect();
+        let old_back = output_back.peek().clone();
+        let ((lva, iters), back): ((Vec<_>, Vec<_>), Vec<_>) = m
             .functions
             .iter()
             .map(|f| {
+                let reused = old_fns
+                    .iter()
+                    .position(|o| o == f)
+                    .and_then(|j| {
+                        let lva = old_lva.get(j)?.clone();
+                        Some(((lva, old_iters.get_mut(j)?.take()?), old_back[j]))
+                    });
+                if let Some(reused) = reused {
+                    tracing::info!("reusing analysis of {}", f.name);
+                    return reused;
+                }
+
                 let insns = ir::lva(f);
-                (
+                let lva = (
                     f.name.to_string(),
                     insns
                         .iter()
@@ -175,19 +658,215 @@ fn App() -> Element {
                         .iter()
                         .map(|(_in, _out, insn)| (HashSet::new(), HashSet::new(), insn.to_string()))
                         .collect(),
-                )
+                );
+                let f: ir::Function = f.clone();
+                let iter = ir::Iter::new(&f);
+                ((lva, iter_prev::Iter::new(iter)), false)
             })
-            .collect();
+            .unzip();
+        *output_lva.write() = lva;
+        *output_iter.write() = iters;
+        *output_back.write() = back;
 
-        *output_iter.write() = m
+        let src = source::functions(&input);
+        *output_markers.write() = m
             .functions
             .iter()
-            .map(|f| {
-                let f: ir::Function = f.clone();
-                let iter = ir::Iter::new(&f);
-                iter_prev::Iter::new(iter)
+            .enumerate()
+            .filter_map(|(i, f)| {
+                let lines = src.get(&f.name)?;
+                Some(editor::markers(i, lines, &diagnostics::diagnostics(f)))
             })
+            .flatten()
             .collect();
+        *output_src.write() = src;
+        *selected.write() = None;
+        *output_module.write() = m.info;
+        *output_debug.write() = m.debug;
+        *output_fns.write() = m.functions;
+    };
+    let mut parse = move || {
+        let input = content.peek().clone();
+        let m: ir::Module = llvm::parse(&input);
+        analyze(input, m);
+    };
+    let onclickparse = move |_| parse();
+    // select the instruction under the cursor in the views
+    let oncursor = move |line: usize| {
+        let src = output_src.read();
+        let at = output_fns
+            .read()
+            .iter()
+            .enumerate()
+            .find_map(|(i, f)| Some((i, src.get(&f.name)?.row(line)?)));
+        if let Some((i, row)) = at {
+            function.set(i);
+            selected.set(Some((i, row)));
+        }
+    };
+    let onchange = move |s: String| {
+        *content.write() = s;
+        if !*auto.peek() {
+            return;
+        }
+        *edits.write() += 1;
+        let edit = *edits.peek();
+        spawn(async move {
+            util::sleep(500).await;
+            if *edits.peek() == edit {
+                parse();
+            }
+        });
+    };
+
+    let mut switch = move |i: usize| {
+        let doc = {
+            let mut w = workspace.write();
+            w.current = i.min(w.documents.len() - 1);
+            w.current().clone()
+        };
+        content.set(doc.text);
+        c_source.set(doc.source);
+        k.set(doc.settings.k);
+        engine.set(doc.settings.engine);
+        tab.set(doc.settings.tab);
+        function.set(doc.settings.function);
+        auto.set(doc.settings.auto);
+        assignments.set(doc.settings.assignments);
+        disassembly.set(None);
+        open_error.set(None);
+        parse();
+    };
+
+    // text IR opens as a new document, bitcode is analyzed as is
+    let open = move |(name, bytes): (String, Vec<u8>)| {
+        if llvm::is_bitcode(&bytes) {
+            match llvm::parse_bitcode(&bytes) {
+                Ok((text, m)) => {
+                    open_error.set(None);
+                    disassembly.set(Some((name, text.clone())));
+                    analyze(text, m);
+                }
+                Err(e) => open_error.set(Some(format!("{name}: {e}"))),
+            }
+        } else {
+            match String::from_utf8(bytes) {
+                // C opens next to the current document
+                Ok(text) if name.ends_with(".c") => c_source.set(Some(text)),
+                Ok(text) => {
+                    let i = workspace.write().add(&name, text);
+                    switch(i);
+                }
+                Err(e) => open_error.set(Some(format!("{name}: {e}"))),
+            }
+        }
+    };
+
+    // autosave the editor content and settings into the current document
+    use_effect(move || {
+        let text = content();
+        let source = c_source();
+        let settings = workspace::Settings {
+            k: k(),
+            engine: engine(),
+            assignments: assignments(),
+            tab: tab(),
+            function: function(),
+            auto: auto(),
+        };
+        let mut w = workspace.write();
+        let doc = w.current_mut();
+        doc.text = text;
+        doc.source = source;
+        doc.settings = settings;
+    });
+    use_effect(move || {
+        if let Err(e) = workspace::save(&workspace.read()) {
+            tracing::warn!("failed to save workspace: {e:?}");
+        }
+    });
+
+    // restore the stepper of a permalink once its module has been parsed
+    use_hook(move || {
+        if let Some(state) = restore {
+            spawn(async move {
+                parse();
+                if let Some(iter) = output_iter.write().get_mut(state.function) {
+                    let mut lives: Vec<_> = iter.by_ref().take(state.step).collect();
+                    if let Some(l) = output_lva.write().get_mut(state.function) {
+                        if let Some(new) = lives.pop() {
+                            l.1 = lives.pop().unwrap_or_else(|| l.2.clone());
+                            l.2 = new;
+                        }
+                    }
+                }
+            });
+        }
+    });
+    use_effect(move || {
+        let function = function();
+        let state = permalink::State {
+            text: content(),
+            function,
+            tab: tab(),
+            // the number of steps up to the one shown, which is the one at
+            // the position after stepping back
+            step: output_iter.read().get(function).map_or(0, |iter| {
+                iter.position() + usize::from(output_back.read()[function])
+            }),
+        };
+        if let Err(e) = permalink::write(&state) {
+            tracing::warn!("failed to update permalink: {e:?}");
+        }
+    });
+
+    // values are colored by register in the editor once they are assigned
+    let registers: HashMap<_, _> = output_fns
+        .read()
+        .iter()
+        .filter_map(|f| {
+            let assignment = assignments.read().get(&f.name)?.clone();
+            let (mut assignment, precolored) = alloc::parse(&assignment).ok()?;
+            assignment.extend(precolored);
+            Some((f.name.clone(), assignment))
+        })
+        .collect();
+    // the live-out sets of the current function next to its instructions:
+    // the sets of the current step while stepping, the final sets otherwise
+    let hints = {
+        let i = function();
+        let fns = output_fns.read();
+        let lva = output_lva.read();
+        let src = output_src.read();
+        match (fns.get(i), lva.get(i), output_iter.read().get(i)) {
+            (Some(f), Some((_, old, new)), Some(iter)) => src
+                .get(&f.name)
+                .map(|lines| {
+                    let selected = selected().filter(|&(f, _)| f == i).map(|(_, row)| row);
+                    let highlight = highlight.read();
+                    if iter.position() > 0 {
+                        editor::hints(lines, old, new, selected, highlight.as_ref())
+                    } else {
+                        let lives = ir::owned_lva(f);
+                        editor::hints(lines, &lives, &lives, selected, highlight.as_ref())
+                    }
+                })
+                .unwrap_or_default(),
+            _ => vec![],
+        }
+    };
+    // debug locations map the line hovered on either side to the other
+    let c_highlight: Vec<usize> = ir_hover()
+        .and_then(|l| output_debug.read().source_line(&output_src.read(), l))
+        .into_iter()
+        .collect();
+    let ir_highlight = c_hover()
+        .map(|l| output_debug.read().module_lines(&output_src.read(), l))
+        .unwrap_or_default();
+    let editor_class = if disassembly.read().is_some() {
+        "hidden"
+    } else {
+        "h-full"
     };
 
     rsx! {
@@ -196,39 +875,177 @@ fn App() -> Element {
                 div { class: "h-1/2 w-full md:h-full md:w-1/2 lg:w-1/3",
                     div { class: "flex flex-col h-full",
                         div { class: "flex-none",
-                            example_picker::ExamplePicker { onpick: move |s| *content.write() = s }
+                            div { class: "flex",
+                                div { class: "flex-1",
+                                    example_picker::ExamplePicker {
+                                        onpick: move |(text, source)| {
+                                            content.set(text);
+                                            c_source.set(source);
+                                        },
+                                    }
+                                }
+                                div { class: "flex-1",
+                                    workspace::Documents { workspace, onswitch: switch }
+                                }
+                                div { class: "flex-none",
+                                    open::Open { onopen: open }
+                                }
+                            }
+                            if let Some(e) = open_error() {
+                                div { class: "ml-1 text-red-500", "{e}" }
+                            }
                         }
-                        div { class: "flex-1",
-                            editor::Editor { content, onChange: move |s| *content.write() = s }
+                        div { class: "flex-1 min-h-0",
+                            open::Drop { onopen: open,
+                                if let Some((name, text)) = disassembly() {
+                                    div { class: "flex flex-col h-full",
+                                        div { class: "flex flex-none items-center",
+                                            span { class: "flex-1 ml-1", "{name} (read-only)" }
+                                            button {
+                                                class: "bg-zinc-100 dark:bg-zinc-800 h-12 px-2",
+                                                onclick: move |_| disassembly.set(None),
+                                                "Close"
+                                            }
+                                        }
+                                        div { class: "flex-1 overflow-scroll",
+                                            code::Code { code: text }
+                                        }
+                                    }
+                                }
+                                div { class: "{editor_class}",
+                                    div { class: "flex h-full",
+                                        if let Some(text) = c_source() {
+                                            div { class: "w-1/2 min-w-0",
+                                                c_source::CSource {
+                                                    text,
+                                                    highlight: c_highlight,
+                                                    onhover: move |l| c_hover.set(l),
+                                                    onclose: move |_| {
+                                                        c_source.set(None);
+                                                        c_hover.set(None);
+                                                    },
+                                                }
+                                            }
+                                        }
+                                        div { class: "flex-1 min-w-0",
+                                            editor::Editor {
+                                                content,
+                                                reveal,
+                                                registers,
+                                                functions: output_fns(),
+                                                hints,
+                                                markers: output_markers(),
+                                                onChange: onchange,
+                                                onLink: move |(f, t): (usize, diagnostics::Tab)| {
+                                                    function.set(f);
+                                                    tab.set(t as usize);
+                                                },
+                                                onCursor: oncursor,
+                                                highlight: ir_highlight,
+                                                onHover: move |l| ir_hover.set(l),
+                                            }
+                                        }
+                                    }
+                                }
+                            }
                         }
-                        div { class: "flex-none",
+                        div { class: "flex flex-none",
                             button {
                                 class: "bg-zinc-100 dark:bg-zinc-800 h-12 w-full",
                                 onclick: onclickparse,
                                 "Parse"
                             }
+                            label {
+                                class: "bg-zinc-100 dark:bg-zinc-800 flex h-12 items-center px-2",
+                                title: "Parse again after every edit",
+                                input {
+                                    r#type: "checkbox",
+                                    class: "mr-1",
+                                    checked: auto(),
+                                    onclick: move |_| auto.toggle(),
+                                }
+                                "Auto"
+                            }
                         }
                     }
                 }
                 div { class: "bg-green-500 h-1/2 md:h-full md:w-1/2 lg:w-2/3",
                     tabs::Tabs {
+                        active: tab,
                         tabs: vec![
                             (
                                 "CFG".to_string(),
                                 rsx! {
-                                    tabs::Tabs { tabs : output_cfg.read().clone().into_iter().map(| s | { (s
-                                    .0.clone(), rsx! { div { div { class : "dark:invert",
-                                    dangerous_inner_html : "{s.2}", } code::Code { code : "{s.1}" } } }) })
-                                    .collect::< Vec < _ >> (), }
+                                    div { class: "ml-1",
+                                        {"K: "},
+                                        input {
+                                            r#type: "number",
+                                            min: "1",
+                                            class: "bg-white dark:bg-zinc-800",
+                                            value: "{k}",
+                                            oninput: move |e: Event<FormData>| {
+                                                if let Ok(v) = e.data.value().parse() {
+                                                    k.set(v);
+                                                }
+                                            },
+                                        }
+                                        {" Layout: "},
+                                        select {
+                                            class: "bg-white dark:bg-zinc-800",
+                                            oninput: move |e: Event<FormData>| {
+                                                if let Ok(e) = e.data.value().parse() {
+                                                    engine.set(e);
+                                                }
+                                            },
+                                            for e in graphviz::Engine::ALL {
+                                                option {
+                                                    value: e.name(),
+                                                    selected: e == engine(),
+                                                    {e.name()}
+                                                }
+                                            }
+                                        }
+                                    }
+                                    tabs::Tabs { active: function, tabs : output_cfg.read().clone().unwrap_or_default().into_iter().enumerate()
+                                    .map(map_cfg).collect::< Vec < _ >> (), }
                                 },
                             ),
                             (
                                 "LVA".to_string(),
                                 rsx! {
-                                    tabs::Tabs { tabs : output_lva.read().clone().into_iter().enumerate()
+                                    tabs::Tabs { active: function, tabs : output_lva.read().clone().into_iter().enumerate()
                                     .map(map_lva).collect::< Vec < _ >> (), }
                                 },
                             ),
+                            (
+                                "Assembly".to_string(),
+                                rsx! {
+                                    tabs::Tabs { active: function, tabs : output_fns.read().iter().enumerate().map(map_asm)
+                                    .collect::< Vec < _ >> (), }
+                                },
+                            ),
+                            (
+                                "Module".to_string(),
+                                rsx! {
+                                    module::Summary {
+                                        info: output_module(),
+                                        definitions: output_fns.read().iter().map(|f| f.name.clone()).collect::<Vec<_>>(),
+                                    }
+                                },
+                            ),
+                            (
+                                "Calls".to_string(),
+                                rsx! {
+                                    {calls()}
+                                },
+                            ),
+                            (
+                                "SSA".to_string(),
+                                rsx! {
+                                    tabs::Tabs { active: function, tabs : output_ssa.read().clone().unwrap_or_default().into_iter()
+                                    .map(map_ssa).collect::< Vec < _ >> (), }
+                                },
+                            ),
                         ]
                     }
                 }
@@ -0,0 +1,442 @@
+use std::collections::{BTreeSet, HashMap};
+
+use petgraph::algo::dominators;
+use petgraph::Direction;
+
+use crate::builder::{self, constant, type_of};
+use crate::ir::{self, BasicBlock, Function, Instruction, Name, Operand};
+use crate::ranges::block_starts;
+use crate::stack::{self, Access};
+use crate::text::local;
+
+/// A value that replaces the loads of a slot, as an operand and its text.
+type Value = (Operand, String);
+
+/// Replaces every `from` in the instruction text `s` with `to`.
+pub fn rename(s: &str, from: &Name, to: &str) -> String {
+    let from = local(from);
+    let (mut renamed, mut rest) = (String::new(), s);
+    while let Some(i) = rest.find(&from) {
+        let end = i + from.len();
+        // %1 is not renamed in %11 or %1.0
+        let whole = !rest[end..]
+            .starts_with(|c: char| c.is_alphanumeric() || matches!(c, '.' | '_' | '$' | '-'));
+        renamed += &rest[..i];
+        renamed += if whole { to } else { &from };
+        rest = &rest[end..];
+    }
+    renamed + rest
+}
+
+fn undef(ty: &str) -> Value {
+    (constant(ty, "undef"), "undef".to_string())
+}
+
+/// The text of the value stored by `store`, like `%5` or `0`.
+fn stored(store: &str) -> Option<&str> {
+    let (_, rest) = store.split_once("store ")?;
+    let rest = rest.trim_start().trim_start_matches("volatile ");
+    let (_, rest) = crate::module::ty(rest);
+    let (value, _) = rest.rsplit_once(", ptr")?;
+    Some(value.trim())
+}
+
+enum Visit {
+    Block(usize),
+    Restore(HashMap<Name, Value>),
+}
+
+/// Promotes the promotable stack slots of `f` to SSA values like LLVM's
+/// mem2reg: phis are placed on the iterated dominance frontiers of the
+/// stores to each slot where the slot is live, and the loads are replaced
+/// by walking the dominator tree with the value last stored to each slot.
+pub fn promote(f: &Function) -> Result<Function, String> {
+    if !f
+        .basic_blocks
+        .iter()
+        .all(|b| matches!(b.term.opcode, 1 | 2))
+    {
+        return Err("only functions whose terminators are br and ret can be promoted".to_string());
+    }
+    let stack = stack::analyze(f);
+    let slots: Vec<&stack::Slot> = stack.slots.iter().filter(|s| s.escape.is_none()).collect();
+    if slots.is_empty() {
+        return Ok(f.clone());
+    }
+    let ty: HashMap<&Name, &str> = slots.iter().map(|s| (&s.name, &s.ty[..])).collect();
+
+    let (blocks, cfg) = ir::cfg(f);
+    let node = |b: usize| blocks[&f.basic_blocks[b].name].1;
+    let index: HashMap<_, usize> = (0..f.basic_blocks.len()).map(|b| (node(b), b)).collect();
+    let doms = dominators::simple_fast(&cfg, node(0));
+    let idom = |b: usize| doms.immediate_dominator(node(b)).map(|d| index[&d]);
+    let reachable = |b: usize| b == 0 || idom(b).is_some();
+    let preds = |b: usize| {
+        let mut preds: Vec<_> = cfg
+            .neighbors_directed(node(b), Direction::Incoming)
+            .map(|p| index[&p])
+            .collect();
+        preds.sort();
+        preds.dedup();
+        preds
+    };
+    // a successor comes once for every edge, like `%x` of `br i1 %c, label %x, label %x`
+    let succs = |b: usize| {
+        cfg.neighbors(node(b))
+            .map(|s| index[&s])
+            .collect::<Vec<_>>()
+    };
+
+    // the dominance frontier of every block, after Cooper, Harvey and Kennedy
+    let n = f.basic_blocks.len();
+    let mut frontiers = vec![BTreeSet::new(); n];
+    for b in (0..n).filter(|&b| reachable(b)) {
+        let preds = preds(b);
+        if preds.len() < 2 {
+            continue;
+        }
+        for p in preds.into_iter().filter(|&p| reachable(p)) {
+            let mut runner = Some(p);
+            while let Some(r) = runner.filter(|&r| Some(r) != idom(b)) {
+                frontiers[r].insert(b);
+                runner = idom(r);
+            }
+        }
+    }
+
+    let starts = block_starts(f);
+    let accesses: Vec<Vec<Option<(usize, Access)>>> = f
+        .basic_blocks
+        .iter()
+        .map(|b| {
+            b.insts
+                .iter()
+                .map(|i| stack::access(i.opcode, &i.uses, &i.string))
+                .collect()
+        })
+        .collect();
+    let accessed = |b: usize, i: usize| {
+        let (k, access) = accesses[b][i]?;
+        let name = f.basic_blocks[b].insts[i].uses[k].name.as_ref()?;
+        ty.contains_key(name).then_some((name, access))
+    };
+
+    // the slots that get a phi at the start of every block
+    let mut phis: Vec<Vec<&Name>> = vec![vec![]; n];
+    for slot in &slots {
+        let mut work: Vec<usize> = (0..n)
+            .filter(|&b| {
+                (0..f.basic_blocks[b].insts.len())
+                    .any(|i| accessed(b, i) == Some((&slot.name, Access::Store)))
+            })
+            .collect();
+        while let Some(b) = work.pop() {
+            for &d in &frontiers[b] {
+                let live = stack.lives[starts[d]].0.contains(&slot.name);
+                if live && !phis[d].contains(&&slot.name) {
+                    phis[d].push(&slot.name);
+                    work.push(d);
+                }
+            }
+        }
+    }
+    // the phis of slot %2 are named %"2.0", %"2.1" and so on
+    let mut count: HashMap<&Name, usize> = HashMap::new();
+    let phi_names: Vec<Vec<Name>> = phis
+        .iter()
+        .map(|phis| {
+            phis.iter()
+                .map(|slot| {
+                    let k = count.entry(slot).or_default();
+                    let name = format!("{slot:?}");
+                    *k += 1;
+                    Name::Name(format!("{}.{}", &name[1..], *k - 1))
+                })
+                .collect()
+        })
+        .collect();
+
+    let mut children = vec![vec![]; n];
+    for b in 1..n {
+        if let Some(d) = idom(b) {
+            children[d].push(b);
+        }
+    }
+
+    // blocks that are not reachable are renamed last, with every slot undef
+    let mut todo: Vec<_> = (0..n)
+        .rev()
+        .filter(|&b| !reachable(b))
+        .map(Visit::Block)
+        .collect();
+    todo.push(Visit::Block(0));
+    let mut current: HashMap<Name, Value> = HashMap::new();
+    let mut replaced: HashMap<Name, Value> = HashMap::new();
+    let mut kept: Vec<Vec<bool>> = f
+        .basic_blocks
+        .iter()
+        .map(|b| vec![true; b.insts.len()])
+        .collect();
+    let mut incoming: Vec<Vec<Vec<(Value, Name)>>> =
+        phis.iter().map(|p| vec![vec![]; p.len()]).collect();
+    while let Some(visit) = todo.pop() {
+        let b = match visit {
+            Visit::Block(b) => b,
+            Visit::Restore(saved) => {
+                current = saved;
+                continue;
+            }
+        };
+        todo.push(Visit::Restore(current.clone()));
+        for (slot, name) in phis[b].iter().zip(&phi_names[b]) {
+            let operand = Operand {
+                constant: false,
+                name: Some(name.clone()),
+                ty: type_of(ty[slot]),
+                value: None,
+            };
+            current.insert((*slot).clone(), (operand, local(name)));
+        }
+        for (i, inst) in f.basic_blocks[b].insts.iter().enumerate() {
+            let slot = if inst.opcode == 31
+            /* alloca */
+            {
+                inst.def.as_ref().filter(|d| ty.contains_key(d))
+            } else if let Some((slot, access)) = accessed(b, i) {
+                match access {
+                    Access::Load => {
+                        let value = current
+                            .get(slot)
+                            .cloned()
+                            .unwrap_or_else(|| undef(ty[slot]));
+                        if let Some(def) = &inst.def {
+                            replaced.insert(def.clone(), value);
+                        }
+                    }
+                    Access::Store => {
+                        let mut operand = inst.uses[0].clone();
+                        let value = match operand.name.as_ref().and_then(|n| replaced.get(n)) {
+                            Some(value) => value.clone(),
+                            None => {
+                                let text = stored(&inst.string).unwrap_or("undef").to_string();
+                                if operand.constant {
+                                    operand.value.get_or_insert_with(|| text.clone());
+                                }
+                                (operand, text)
+                            }
+                        };
+                        current.insert(slot.clone(), value);
+                    }
+                    // the contents of a slot are undefined outside its lifetime
+                    Access::Start | Access::End => {
+                        current.insert(slot.clone(), undef(ty[slot]));
+                    }
+                }
+                Some(slot)
+            } else if crate::callgraph::callee(&inst.string)
+                .is_some_and(|c| c.starts_with("llvm.dbg."))
+            {
+                inst.uses
+                    .iter()
+                    .filter_map(|o| o.name.as_ref())
+                    .find(|n| ty.contains_key(n))
+            } else {
+                None
+            };
+            if slot.is_some() {
+                kept[b][i] = false;
+            }
+        }
+        for s in succs(b) {
+            for (k, slot) in phis[s].iter().enumerate() {
+                let value = current
+                    .get(*slot)
+                    .cloned()
+                    .unwrap_or_else(|| undef(ty[slot]));
+                incoming[s][k].push((value, f.basic_blocks[b].name.clone()));
+            }
+        }
+        todo.extend(children[b].iter().rev().map(|&c| Visit::Block(c)));
+    }
+
+    let rewrite = |uses: &[Operand], string: &str| {
+        let mut string = string.to_string();
+        let uses = uses
+            .iter()
+            .map(|o| match o.name.as_ref().and_then(|n| replaced.get(n)) {
+                Some((value, text)) => {
+                    string = rename(&string, o.name.as_ref().unwrap(), text);
+                    value.clone()
+                }
+                None => o.clone(),
+            })
+            .collect();
+        (uses, string)
+    };
+    let basic_blocks = f
+        .basic_blocks
+        .iter()
+        .enumerate()
+        .map(|(b, block)| {
+            let phis = phi_names[b]
+                .iter()
+                .zip(std::mem::take(&mut incoming[b]))
+                .map(|(name, incoming)| {
+                    let incoming = incoming.into_iter().map(|((o, _), b)| (o, b)).collect();
+                    builder::phi(name.clone(), incoming)
+                });
+            let insts = block
+                .insts
+                .iter()
+                .zip(&kept[b])
+                .filter(|(_, &kept)| kept)
+                .map(|(inst, _)| {
+                    let (uses, string) = rewrite(&inst.uses, &inst.string);
+                    Instruction {
+                        uses,
+                        string,
+                        ..inst.clone()
+                    }
+                });
+            let (uses, string) = rewrite(&block.term.uses, &block.term.string);
+            BasicBlock {
+                name: block.name.clone(),
+                insts: phis.chain(insts).collect(),
+                term: ir::Terminator {
+                    uses,
+                    string,
+                    ..block.term.clone()
+                },
+            }
+        })
+        .collect();
+
+    Ok(Function {
+        basic_blocks,
+        ..f.clone()
+    })
+}
+
+/// The CFG of `f` with the values live into and out of every block.
+pub fn cfg(f: &Function) -> String {
+    let lives = ir::owned_lva(f);
+    let blocks = ir::block_lives(f, &lives);
+    let node = |b: usize, _: &BasicBlock| {
+        let (r#in, out) = &blocks[b];
+        crate::dot::Node {
+            before: Some(format!(
+                "in: {}",
+                crate::debug::labels(r#in, &HashMap::new())
+            )),
+            after: Some(format!(
+                "out: {}",
+                crate::debug::labels(out, &HashMap::new())
+            )),
+            attrs: String::new(),
+        }
+    };
+    crate::dot::cfg(f, node, |_, _| String::new())
+}
+
+#[test]
+fn test_promote() {
+    use crate::builder::Builder;
+
+    assert_eq!(
+        rename("%1 %11 %1.0, %1)", &Name::Number(1), "0"),
+        "0 %11 %1.0, 0)"
+    );
+    assert_eq!(stored("  store i32 %5, ptr %2, align 4"), Some("%5"));
+    assert_eq!(stored("  store ptr null, ptr %2"), Some("null"));
+
+    let mut b = Builder::new("f");
+    let c = b.param("i1");
+    let (then, otherwise, join) = (b.block(), b.block(), b.block());
+    let slot = b.alloca("i32");
+    b.store(&constant("i32", 0), &slot);
+    b.cond_br(&c, then, otherwise);
+
+    b.position(then);
+    b.store(&constant("i32", 1), &slot);
+    b.br(join);
+
+    b.position(otherwise);
+    b.br(join);
+
+    b.position(join);
+    let x = b.load("i32", &slot);
+    b.ret(Some(&x));
+    let f = b.build();
+
+    let promoted = promote(&f).unwrap();
+    assert_eq!(
+        ir::strings(&promoted),
+        vec![
+            "  br i1 %0, label %3, label %4",
+            "  br label %5",
+            "  br label %5",
+            r#"  %"2.0" = phi i32 [ 1, %3 ], [ 0, %4 ]"#,
+            r#"  ret i32 %"2.0""#,
+        ],
+    );
+    let phi = &promoted.basic_blocks[3].insts[0];
+    assert_eq!(phi.blocks, Some(vec![Name::Number(3), Name::Number(4)]));
+    assert_eq!(
+        promoted.basic_blocks[3].term.uses[0].name,
+        Some(Name::Name("2.0".to_string())),
+    );
+    assert!(stack::analyze(&promoted).slots.is_empty());
+    // the blocks after the removed alloca are renumbered
+    assert_eq!(
+        promoted.to_string(),
+        r#"define i32 @f(i1 %0) {
+  br i1 %0, label %2, label %3
+
+2:
+  br label %4
+
+3:
+  br label %4
+
+4:
+  %"2.0" = phi i32 [ 1, %2 ], [ 0, %3 ]
+  ret i32 %"2.0"
+}
+"#,
+    );
+    assert!(cfg(&promoted).contains(r#"label="{%5:|in: \{\}|%\"2.0\" = phi"#));
+    // the phi is live out of neither predecessor, its values are constants
+    let lives = ir::lva(&promoted);
+    assert!(lives
+        .iter()
+        .all(|(r#in, _, _)| !r#in.contains(&Name::Number(2))));
+    assert_eq!(lives[4].0, [Name::Name("2.0".to_string())].iter().collect(),);
+
+    // both edges of a branch to one block get an entry in its phi
+    let mut b = Builder::new("g");
+    let c = b.param("i1");
+    let (twice, join) = (b.block(), b.block());
+    let slot = b.alloca("i32");
+    b.store(&constant("i32", 0), &slot);
+    b.cond_br(&c, join, twice);
+
+    b.position(twice);
+    b.store(&constant("i32", 1), &slot);
+    b.cond_br(&c, join, join);
+
+    b.position(join);
+    let x = b.load("i32", &slot);
+    b.ret(Some(&x));
+    let f = b.build();
+
+    let promoted = promote(&f).unwrap();
+    let phi = &promoted.basic_blocks[2].insts[0];
+    assert_eq!(
+        phi.string,
+        r#"  %"2.0" = phi i32 [ 0, %1 ], [ 1, %3 ], [ 1, %3 ]"#
+    );
+    assert_eq!(
+        phi.blocks,
+        Some(vec![Name::Number(1), Name::Number(3), Name::Number(3)])
+    );
+}
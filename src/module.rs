@@ -0,0 +1,376 @@
+#![allow(non_snake_case)]
+
+use std::collections::{BTreeMap, HashMap};
+
+use dioxus::prelude::*;
+
+const LINKAGES: &[&str] = &[
+    "private",
+    "internal",
+    "available_externally",
+    "linkonce",
+    "weak",
+    "common",
+    "appending",
+    "extern_weak",
+    "linkonce_odr",
+    "weak_odr",
+    "external",
+];
+
+/// A global variable like `@.str = private constant [3 x i8] c"%d\00"`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Global {
+    pub name: String,
+    pub linkage: String,
+    pub constant: bool,
+    pub ty: String,
+    /// None for globals defined elsewhere.
+    pub initializer: Option<String>,
+}
+
+/// A function declared but not defined, like `declare i32 @printf(ptr, ...)`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Declaration {
+    pub name: String,
+    pub ret: String,
+    pub params: Vec<String>,
+    /// The attribute group of the function, like 1 for `#1`.
+    pub attributes: Option<usize>,
+}
+
+/// An entry of `!llvm.module.flags`, like `!{i32 1, !"wchar_size", i32 4}`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Flag {
+    pub behavior: u32,
+    pub key: String,
+    pub value: String,
+}
+
+/// Everything in the module text besides function bodies.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Info {
+    pub source_filename: Option<String>,
+    pub datalayout: Option<String>,
+    pub triple: Option<String>,
+    pub globals: Vec<Global>,
+    pub declarations: Vec<Declaration>,
+    /// The attributes of every attribute group, like `#0`.
+    pub attributes: BTreeMap<usize, Vec<String>>,
+    pub flags: Vec<Flag>,
+}
+
+/// Splits `s` at `sep` outside of strings and brackets.
+pub fn split(s: &str, sep: char) -> Vec<&str> {
+    let (mut parts, mut start, mut depth, mut string) = (vec![], 0, 0, false);
+    for (i, c) in s.char_indices() {
+        match c {
+            '"' => string = !string,
+            '(' | '[' | '{' | '<' if !string => depth += 1,
+            ')' | ']' | '}' | '>' if !string => depth -= 1,
+            c if c == sep && !string && depth == 0 => {
+                parts.push(&s[start..i]);
+                start = i + c.len_utf8();
+            }
+            _ => {}
+        }
+    }
+    parts.push(&s[start..]);
+    parts
+        .into_iter()
+        .map(str::trim)
+        .filter(|p| !p.is_empty())
+        .collect()
+}
+
+fn unquote(s: &str) -> String {
+    s.trim().trim_matches('"').to_string()
+}
+
+/// The first type in `s` and the rest of it.
+pub fn ty(s: &str) -> (&str, &str) {
+    let s = s.trim_start();
+    let end = match split(s, ' ').first() {
+        // aggregates like [3 x i8] and { i32, ptr } stay in one piece
+        Some(first) => first.trim_end_matches(',').len(),
+        None => 0,
+    };
+    let end = if s[end..].trim_start().starts_with('(') {
+        // function types like i32 (ptr, ...)
+        s[end..].find(')').map_or(end, |i| end + i + 1)
+    } else {
+        end
+    };
+    (&s[..end], &s[end..])
+}
+
+fn global(line: &str) -> Option<Global> {
+    let (name, rest) = line.strip_prefix('@')?.split_once(" = ")?;
+    let words: Vec<_> = rest.split_whitespace().collect();
+    let kind = words
+        .iter()
+        .position(|w| matches!(*w, "global" | "constant"))?;
+    let linkage = words[..kind]
+        .iter()
+        .find(|w| LINKAGES.contains(w))
+        .unwrap_or(&"external");
+    let after = rest
+        .match_indices(words[kind])
+        .map(|(i, _)| i + words[kind].len())
+        .find(|&i| rest[i..].starts_with(' '))?;
+    let (ty, rest) = ty(&rest[after..]);
+    let initializer = split(rest, ',')
+        .first()
+        .map(|i| i.to_string())
+        .filter(|_| !rest.trim_start().starts_with(','));
+    Some(Global {
+        name: unquote(name),
+        linkage: linkage.to_string(),
+        constant: words[kind] == "constant",
+        ty: ty.to_string(),
+        initializer,
+    })
+}
+
+fn declaration(line: &str) -> Option<Declaration> {
+    let rest = line.strip_prefix("declare ")?;
+    let at = rest.find('@')?;
+    let (prefix, rest) = (&rest[..at], &rest[at + 1..]);
+    let open = rest.find('(')?;
+    let name = unquote(&rest[..open]);
+    let close = open + split(&rest[open..], ' ').first()?.len();
+    let params = split(&rest[open + 1..close - 1], ',')
+        .into_iter()
+        .map(str::to_string)
+        .collect();
+    let attributes = rest[close..]
+        .split_whitespace()
+        .find_map(|w| w.strip_prefix('#')?.parse().ok());
+    // the return type is the last type before the name, after any
+    // linkage, attributes and the like
+    let ret = split(prefix, ' ')
+        .into_iter()
+        .rev()
+        .find(|w| !w.starts_with('"'))
+        .unwrap_or("void")
+        .to_string();
+    Some(Declaration {
+        name,
+        ret,
+        params,
+        attributes,
+    })
+}
+
+/// Reads everything but the function bodies of the module in `text`.
+pub fn parse(text: &str) -> Info {
+    let mut info = Info::default();
+    let mut tuples = HashMap::new();
+    let mut flags = vec![];
+    for line in text.lines() {
+        let line = line.trim_end();
+        if let Some(v) = line.strip_prefix("source_filename = ") {
+            info.source_filename = Some(unquote(v));
+        } else if let Some(v) = line.strip_prefix("target datalayout = ") {
+            info.datalayout = Some(unquote(v));
+        } else if let Some(v) = line.strip_prefix("target triple = ") {
+            info.triple = Some(unquote(v));
+        } else if line.starts_with('@') {
+            info.globals.extend(global(line));
+        } else if line.starts_with("declare ") {
+            info.declarations.extend(declaration(line));
+        } else if let Some(rest) = line.strip_prefix("attributes #") {
+            let Some((id, attrs)) = rest.split_once(" = ") else {
+                continue;
+            };
+            let attrs = attrs.trim().trim_start_matches('{').trim_end_matches('}');
+            if let Ok(id) = id.parse() {
+                let attrs = split(attrs, ' ').into_iter().map(str::to_string).collect();
+                info.attributes.insert(id, attrs);
+            }
+        } else if let Some(rest) = line.strip_prefix("!llvm.module.flags = !{") {
+            flags = split(rest.trim_end_matches('}'), ',');
+        } else if let Some((id, rest)) = line.split_once(" = !{") {
+            tuples.insert(id, split(rest.trim_end_matches('}'), ','));
+        }
+    }
+    info.flags = flags
+        .into_iter()
+        .filter_map(|id| match &tuples.get(id)?[..] {
+            [behavior, key, value] => Some(Flag {
+                behavior: behavior.strip_prefix("i32 ")?.parse().ok()?,
+                key: unquote(key.strip_prefix('!')?),
+                value: value.to_string(),
+            }),
+            _ => None,
+        })
+        .collect();
+    info
+}
+
+/// A summary of the module: its target, globals, declarations, attribute
+/// groups and flags, along with the names of the functions it defines.
+#[component]
+pub fn Summary(info: Info, definitions: Vec<String>) -> Element {
+    let row = "flex border-b border-zinc-200 dark:border-zinc-700";
+    let target = [
+        ("Source", &info.source_filename),
+        ("Triple", &info.triple),
+        ("Data layout", &info.datalayout),
+    ]
+    .into_iter()
+    .filter_map(|(k, v)| Some((k, v.clone()?)))
+    .collect::<Vec<_>>();
+
+    rsx! {
+        div { class: "ml-1 font-mono text-sm",
+            for (k , v) in target {
+                div { class: "flex",
+                    span { class: "flex-none w-32 text-zinc-500", "{k}" }
+                    span { class: "break-all", "{v}" }
+                }
+            }
+            h2 { class: "mt-2 font-bold", "Globals" }
+            for g in info.globals {
+                div { class: row,
+                    span { class: "flex-none w-1/4 truncate", title: "{g.name}", "@{g.name}" }
+                    span { class: "flex-none w-1/6 text-zinc-500",
+                        "{g.linkage} "
+                        if g.constant {
+                            "constant"
+                        } else {
+                            "global"
+                        }
+                    }
+                    span { class: "flex-none w-1/6", "{g.ty}" }
+                    span { class: "flex-1 truncate", title: g.initializer.clone().unwrap_or_default(),
+                        {g.initializer.clone().unwrap_or_default()}
+                    }
+                }
+            }
+            h2 { class: "mt-2 font-bold", "Definitions" }
+            for name in definitions {
+                div { class: row, "@{name}" }
+            }
+            h2 { class: "mt-2 font-bold", "Declarations" }
+            for d in info.declarations {
+                div { class: row,
+                    "{d.ret} @{d.name}("
+                    {d.params.join(", ")}
+                    ")"
+                    if let Some(a) = d.attributes {
+                        span { class: "ml-1 text-zinc-500", "#{a}" }
+                    }
+                }
+            }
+            h2 { class: "mt-2 font-bold", "Attribute groups" }
+            for (id , attrs) in info.attributes {
+                div { class: row,
+                    span { class: "flex-none w-12", "#{id}" }
+                    span { class: "break-all", {attrs.join(" ")} }
+                }
+            }
+            h2 { class: "mt-2 font-bold", "Module flags" }
+            for f in info.flags {
+                div { class: row,
+                    span { class: "flex-none w-1/3", "{f.key}" }
+                    span { class: "flex-none w-1/3", "{f.value}" }
+                    span { class: "flex-none w-1/3 text-zinc-500", "behavior {f.behavior}" }
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_parse() {
+    let info = parse(include_str!("../examples/ll/brainfuck.ll"));
+    assert_eq!(
+        info.source_filename.as_deref(),
+        Some("examples/ll/brainfuck.c")
+    );
+    assert_eq!(info.triple.as_deref(), Some("arm64-apple-macosx14.0.0"));
+    assert_eq!(
+        info.datalayout.as_deref(),
+        Some("e-m:o-i64:64-i128:128-n32:64-S128")
+    );
+
+    assert_eq!(info.globals.len(), 4);
+    assert_eq!(
+        info.globals[0],
+        Global {
+            name: ".str".to_string(),
+            linkage: "private".to_string(),
+            constant: true,
+            ty: "[3 x i8]".to_string(),
+            initializer: Some(r#"c"%c\00""#.to_string()),
+        },
+    );
+    assert_eq!(
+        info.globals[2],
+        Global {
+            name: "__stderrp".to_string(),
+            linkage: "external".to_string(),
+            constant: false,
+            ty: "ptr".to_string(),
+            initializer: None,
+        },
+    );
+
+    assert_eq!(
+        info.declarations
+            .iter()
+            .map(|d| &d.name[..])
+            .collect::<Vec<_>>(),
+        vec!["llvm.memset.p0.i64", "printf", "fprintf"],
+    );
+    assert_eq!(
+        info.declarations[1],
+        Declaration {
+            name: "printf".to_string(),
+            ret: "i32".to_string(),
+            params: vec!["ptr noundef".to_string(), "...".to_string()],
+            attributes: Some(2),
+        },
+    );
+    assert_eq!(
+        info.declarations[0].params,
+        vec!["ptr nocapture writeonly", "i8", "i64", "i1 immarg"],
+    );
+
+    assert_eq!(info.attributes.keys().collect::<Vec<_>>(), vec![&0, &1, &2]);
+    assert_eq!(
+        info.attributes[&1],
+        vec![
+            "nocallback",
+            "nofree",
+            "nounwind",
+            "willreturn",
+            "memory(argmem: write)"
+        ],
+    );
+    assert_eq!(info.flags.len(), 4);
+    assert_eq!(
+        info.flags[0],
+        Flag {
+            behavior: 1,
+            key: "wchar_size".to_string(),
+            value: "i32 4".to_string(),
+        },
+    );
+
+    assert_eq!(
+        global("@g = dso_local global { i32, ptr } { i32 1, ptr null }, align 8"),
+        Some(Global {
+            name: "g".to_string(),
+            linkage: "external".to_string(),
+            constant: false,
+            ty: "{ i32, ptr }".to_string(),
+            initializer: Some("{ i32 1, ptr null }".to_string()),
+        }),
+    );
+    assert_eq!(
+        declaration("declare noalias ptr @malloc(i64 noundef)").map(|d| d.ret),
+        Some("ptr".to_string()),
+    );
+}
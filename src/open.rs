@@ -0,0 +1,71 @@
+#![allow(non_snake_case)]
+
+use std::sync::Arc;
+
+use dioxus::html::{FileEngine, HasFileData};
+use dioxus::prelude::*;
+
+/// The name and contents of every file in `files`.
+pub async fn read(files: Arc<dyn FileEngine>) -> Vec<(String, Vec<u8>)> {
+    let mut read = vec![];
+    for name in files.files() {
+        match files.read_file(&name).await {
+            Some(bytes) => read.push((name, bytes)),
+            None => tracing::warn!("failed to read {name}"),
+        }
+    }
+    read
+}
+
+#[component]
+pub fn Open(onopen: EventHandler<(String, Vec<u8>)>) -> Element {
+    rsx! {
+        label {
+            class: "bg-zinc-100 dark:bg-zinc-800 cursor-pointer flex h-12 items-center px-2",
+            title: "Open .ll, .bc or .c files",
+            "Open"
+            input {
+                r#type: "file",
+                class: "hidden",
+                accept: ".ll,.bc,.c",
+                multiple: true,
+                onchange: move |e: Event<FormData>| async move {
+                    if let Some(files) = e.files() {
+                        for file in read(files).await {
+                            onopen.call(file);
+                        }
+                    }
+                },
+            }
+        }
+    }
+}
+
+/// Calls `onopen` with the files dropped onto `children`.
+#[component]
+pub fn Drop(onopen: EventHandler<(String, Vec<u8>)>, children: Element) -> Element {
+    let mut hovered = use_signal(|| false);
+    let class = if hovered() {
+        "h-full outline-dashed outline-2 outline-blue-500"
+    } else {
+        "h-full"
+    };
+
+    rsx! {
+        div {
+            class: "{class}",
+            prevent_default: "ondragover ondrop",
+            ondragover: move |_| hovered.set(true),
+            ondragleave: move |_| hovered.set(false),
+            ondrop: move |e: DragEvent| async move {
+                hovered.set(false);
+                if let Some(files) = e.files() {
+                    for file in read(files).await {
+                        onopen.call(file);
+                    }
+                }
+            },
+            {children}
+        }
+    }
+}
@@ -0,0 +1,71 @@
+use base64::Engine;
+use wasm_bindgen::prelude::*;
+
+/// Everything needed to restore a view: the module text and where in the
+/// analysis of it the user was.
+#[derive(Clone, Debug, Default, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct State {
+    pub text: String,
+    /// Index of the selected function.
+    pub function: usize,
+    /// Index of the active view tab.
+    pub tab: usize,
+    /// Number of steps taken by the liveness stepper of `function`.
+    pub step: usize,
+}
+
+/// Compresses `state` into a string that is safe to put in a URL fragment.
+pub fn encode(state: &State) -> String {
+    let json = serde_json::to_vec(state).unwrap();
+    let deflated = miniz_oxide::deflate::compress_to_vec(&json, 9);
+    base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(deflated)
+}
+
+pub fn decode(s: &str) -> Result<State, String> {
+    let deflated = base64::engine::general_purpose::URL_SAFE_NO_PAD
+        .decode(s)
+        .map_err(|e| e.to_string())?;
+    // a fragment of a few kB should never inflate past this
+    let json = miniz_oxide::inflate::decompress_to_vec_with_limit(&deflated, 1 << 24)
+        .map_err(|e| format!("{e:?}"))?;
+    serde_json::from_slice(&json).map_err(|e| e.to_string())
+}
+
+/// Restores the state in the fragment of the current URL, if any.
+pub fn read() -> Option<State> {
+    let hash = web_sys::window()?.location().hash().ok()?;
+    let hash = hash.strip_prefix('#').unwrap_or(&hash);
+    if hash.is_empty() {
+        return None;
+    }
+    decode(hash)
+        .map_err(|e| tracing::warn!("invalid permalink: {e}"))
+        .ok()
+}
+
+/// Replaces the fragment of the current URL without adding to the history.
+pub fn write(state: &State) -> Result<(), JsValue> {
+    let window = web_sys::window().ok_or_else(|| JsValue::from_str("no window"))?;
+    window.history()?.replace_state_with_url(
+        &JsValue::NULL,
+        "",
+        Some(&format!("#{}", encode(state))),
+    )
+}
+
+#[test]
+fn test_encode() {
+    let state = State {
+        text: include_str!("../examples/ll/for1.ll").to_string(),
+        function: 0,
+        tab: 1,
+        step: 37,
+    };
+    let encoded = encode(&state);
+    assert!(encoded
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'));
+    assert!(encoded.len() < state.text.len());
+    assert_eq!(decode(&encoded), Ok(state));
+    assert!(decode("not base64!").is_err());
+}
@@ -0,0 +1,201 @@
+#![allow(non_snake_case)]
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use dioxus::prelude::*;
+
+use crate::ir::{Function, Name, Type};
+
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum Class {
+    Int,
+    Float,
+    Vector,
+}
+
+impl Class {
+    pub fn of(ty: &Type) -> Self {
+        match ty.id {
+            // half, bfloat, float, double, x86_fp80, fp128, ppc_fp128
+            0..=6 => Class::Float,
+            // fixed and scalable vectors
+            18 | 19 => Class::Vector,
+            _ => Class::Int,
+        }
+    }
+}
+
+/// The type of every value in `f`, taken from params and from the operands
+/// that use each value. Values that are never used have no type.
+pub fn types(f: &Function) -> HashMap<&Name, &Type> {
+    f.params
+        .iter()
+        .map(|p| (&p.name, &p.ty))
+        .chain(f.basic_blocks.iter().flat_map(|b| {
+            b.insts
+                .iter()
+                .flat_map(|i| i.uses.iter())
+                .chain(b.term.uses.iter())
+                .filter(|o| !o.constant && o.ty.id != 8)
+                .filter_map(|o| Some((o.name.as_ref()?, &o.ty)))
+        }))
+        .collect()
+}
+
+pub type Counts = BTreeMap<Class, usize>;
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Pressure {
+    /// Live-in and live-out counts of every instruction, in `ir::lva` order.
+    pub insts: Vec<(Counts, Counts)>,
+    /// Maximum number of values live at once in each block.
+    pub blocks: Vec<(Name, usize)>,
+    /// Maximum number of values of each class live at once in the function.
+    pub classes: Counts,
+    /// Maximum number of values live at once in the whole function.
+    pub max: usize,
+}
+
+fn total(c: &Counts) -> usize {
+    c.values().sum()
+}
+
+impl Pressure {
+    /// Number of values live at any point of every instruction.
+    pub fn totals(&self) -> Vec<usize> {
+        self.insts
+            .iter()
+            .map(|(i, o)| total(i).max(total(o)))
+            .collect()
+    }
+}
+
+pub fn pressure(f: &Function, lives: &[(HashSet<&Name>, HashSet<&Name>, &str)]) -> Pressure {
+    tracing::trace!("pressure {}", f.name);
+
+    let types = types(f);
+    let count = |s: &HashSet<&Name>| {
+        let mut c = Counts::new();
+        for n in s {
+            *c.entry(types.get(n).map_or(Class::Int, |t| Class::of(t)))
+                .or_default() += 1;
+        }
+        c
+    };
+    let insts: Vec<_> = lives.iter().map(|(i, o, _)| (count(i), count(o))).collect();
+
+    let mut blocks = vec![];
+    let mut j = 0;
+    for b in &f.basic_blocks {
+        let n = b.insts.len() + 1;
+        let max = insts[j..j + n]
+            .iter()
+            .map(|(i, o)| total(i).max(total(o)))
+            .max()
+            .unwrap_or_default();
+        blocks.push((b.name.clone(), max));
+        j += n;
+    }
+    let max = blocks.iter().map(|(_, m)| *m).max().unwrap_or_default();
+
+    let mut classes = Counts::new();
+    for (i, o) in &insts {
+        for (&class, &n) in i.iter().chain(o.iter()) {
+            let c = classes.entry(class).or_default();
+            *c = (*c).max(n);
+        }
+    }
+
+    Pressure {
+        insts,
+        blocks,
+        classes,
+        max,
+    }
+}
+
+/// A fill color for Graphviz going from green at no pressure to red once
+/// `pressure` reaches `k`.
+pub fn heat(pressure: usize, k: usize) -> String {
+    let ratio = if k == 0 {
+        1.0
+    } else {
+        (pressure as f64 / k as f64).min(1.0)
+    };
+    format!("{:.3} 0.400 1.000", (1.0 - ratio) / 3.0)
+}
+
+#[component]
+pub fn Sparkline(values: Vec<usize>, k: usize) -> Element {
+    let width = values.len().max(1) * 8;
+    let height = values.iter().copied().max().unwrap_or_default().max(k) * 4 + 2;
+    let points = values
+        .iter()
+        .enumerate()
+        .map(|(j, v)| format!("{},{}", j * 8 + 4, height - v * 4 - 1))
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    rsx! {
+        svg {
+            class: "ml-1",
+            width: "{width}",
+            height: "{height}",
+            line {
+                x1: "0",
+                y1: "{height - k * 4 - 1}",
+                x2: "{width}",
+                y2: "{height - k * 4 - 1}",
+                stroke: "red",
+                stroke_dasharray: "2",
+            }
+            polyline {
+                points: "{points}",
+                fill: "none",
+                stroke: "currentColor",
+            }
+        }
+    }
+}
+
+#[test]
+fn test_pressure() {
+    use crate::builder::Builder;
+
+    // define double @main(i32 %0, double %1) {
+    //   %3 = sitofp i32 %0 to double
+    //   %4 = fadd double %3, %1
+    //   ret double %4
+    // }
+    let mut b = Builder::new("main");
+    let (n, x) = (b.param("i32"), b.param("double"));
+    let n = b.cast("sitofp", &n, "double");
+    let sum = b.binary("fadd", &n, &x);
+    b.ret(Some(&sum));
+    let f = b.build();
+
+    let p = pressure(&f, &crate::ir::lva(&f));
+    assert_eq!(
+        p.insts,
+        vec![
+            (
+                Counts::from([(Class::Int, 1), (Class::Float, 1)]),
+                Counts::from([(Class::Float, 2)]),
+            ),
+            (
+                Counts::from([(Class::Float, 2)]),
+                Counts::from([(Class::Float, 1)]),
+            ),
+            (Counts::from([(Class::Float, 1)]), Counts::new()),
+        ],
+    );
+    assert_eq!(p.totals(), vec![2, 2, 1]);
+    assert_eq!(p.blocks, vec![(Name::Number(2), 2)]);
+    assert_eq!(
+        p.classes,
+        Counts::from([(Class::Int, 1), (Class::Float, 2)]),
+    );
+    assert_eq!(p.max, 2);
+    assert_eq!(heat(0, 4), "0.333 0.400 1.000");
+    assert_eq!(heat(4, 4), "0.000 0.400 1.000");
+}
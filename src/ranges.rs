@@ -0,0 +1,155 @@
+#![allow(non_snake_case)]
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use dioxus::prelude::*;
+
+use crate::ir::{self, Function, Name};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Cell {
+    pub live: bool,
+    pub def: bool,
+    pub r#use: bool,
+}
+
+/// One row per value, one cell per instruction in `ir::lva` order. Rows are
+/// ordered by the first instruction that mentions the value.
+pub fn rows(
+    lives: &[(HashSet<Name>, HashSet<Name>, String)],
+    defs: &[HashSet<&Name>],
+    uses: &[HashSet<&Name>],
+) -> Vec<(Name, Vec<Cell>)> {
+    let names: BTreeSet<&Name> = lives
+        .iter()
+        .flat_map(|(r#in, out, _)| r#in.iter().chain(out.iter()))
+        .chain(defs.iter().chain(uses.iter()).flatten().copied())
+        .collect();
+    let mut rows: Vec<_> = names
+        .into_iter()
+        .map(|n| {
+            let cells: Vec<_> = lives
+                .iter()
+                .enumerate()
+                .map(|(j, (r#in, out, _))| Cell {
+                    live: r#in.contains(n) || out.contains(n),
+                    def: defs.get(j).is_some_and(|d| d.contains(n)),
+                    r#use: uses.get(j).is_some_and(|u| u.contains(n)),
+                })
+                .collect();
+            (n.clone(), cells)
+        })
+        .collect();
+    rows.sort_by_key(|(_, cells)| cells.iter().position(|c| c.live || c.def || c.r#use));
+    rows
+}
+
+/// Indices (in `ir::lva` order) of the first instruction of every block.
+pub fn block_starts(f: &Function) -> Vec<usize> {
+    f.basic_blocks
+        .iter()
+        .scan(0, |j, b| {
+            let start = *j;
+            *j += b.insts.len() + 1;
+            Some(start)
+        })
+        .collect()
+}
+
+#[component]
+pub fn Ranges(
+    f: Function,
+    lives: Vec<(HashSet<Name>, HashSet<Name>, String)>,
+    variables: HashMap<Name, String>,
+) -> Element {
+    let rows = rows(&lives, &ir::def(&f), &ir::r#use(&f));
+    let starts = block_starts(&f);
+    let strings: Vec<_> = ir::strings(&f)
+        .iter()
+        .map(|s| s.trim().to_string())
+        .collect();
+
+    rsx! {
+        div { class: "font-mono text-xs ml-1 mt-2",
+            for (name , cells) in rows {
+                div { class: "flex h-4",
+                    span { class: "flex-none w-24 text-right pr-1 truncate",
+                        {crate::debug::label(&name, &variables)}
+                    }
+                    for (j , cell) in cells.into_iter().enumerate() {
+                        span {
+                            class: format!(
+                                "flex-none w-3 h-4 text-center leading-4 {} {}",
+                                if starts.contains(&j) { "border-l border-zinc-400" } else { "" },
+                                if cell.live { "bg-blue-300 dark:bg-blue-800" } else { "" },
+                            ),
+                            title: "{strings[j]}",
+                            if cell.def && cell.r#use {
+                                "x"
+                            } else if cell.def {
+                                "d"
+                            } else if cell.r#use {
+                                "u"
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_rows() {
+    let lives = vec![
+        (
+            HashSet::from([Name::Number(0)]),
+            HashSet::from([Name::Number(0), Name::Number(2)]),
+            "  %2 = add i32 %0, 1".to_string(),
+        ),
+        (
+            HashSet::from([Name::Number(0), Name::Number(2)]),
+            HashSet::new(),
+            "  %3 = add i32 %2, %0".to_string(),
+        ),
+        (HashSet::new(), HashSet::new(), "  ret void".to_string()),
+    ];
+    let (n0, n2, n3) = (Name::Number(0), Name::Number(2), Name::Number(3));
+    let defs = vec![HashSet::from([&n2]), HashSet::from([&n3]), HashSet::new()];
+    let uses = vec![
+        HashSet::from([&n0]),
+        HashSet::from([&n0, &n2]),
+        HashSet::new(),
+    ];
+
+    let cell = |live, def, r#use| Cell { live, def, r#use };
+    assert_eq!(
+        rows(&lives, &defs, &uses),
+        vec![
+            (
+                Name::Number(0),
+                vec![
+                    cell(true, false, true),
+                    cell(true, false, true),
+                    cell(false, false, false),
+                ],
+            ),
+            (
+                Name::Number(2),
+                vec![
+                    cell(true, true, false),
+                    cell(true, false, true),
+                    cell(false, false, false),
+                ],
+            ),
+            (
+                Name::Number(3),
+                vec![
+                    cell(false, false, false),
+                    cell(false, true, false),
+                    cell(false, false, false),
+                ],
+            ),
+        ],
+    );
+}
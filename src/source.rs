@@ -0,0 +1,130 @@
+use std::collections::HashMap;
+
+/// Where a function is found in the module text. All lines are 0-based.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Lines {
+    /// The line of the `define`.
+    pub define: usize,
+    /// The first line of every block: its label, or the `define` for an
+    /// unlabelled entry block.
+    pub blocks: Vec<usize>,
+    /// The first line of every instruction, in `ir::lva` order.
+    pub insts: Vec<usize>,
+    /// The last line of every instruction, in `ir::lva` order.
+    pub ends: Vec<usize>,
+}
+
+impl Lines {
+    /// The first and last line of the `row`th instruction.
+    pub fn inst(&self, row: usize) -> Option<(usize, usize)> {
+        Some((*self.insts.get(row)?, *self.ends.get(row)?))
+    }
+
+    /// The row of the instruction on `line`. Labels, comments and the
+    /// `define` belong to the instruction after them.
+    pub fn row(&self, line: usize) -> Option<usize> {
+        if line < self.define {
+            return None;
+        }
+        self.ends.iter().position(|&end| end >= line)
+    }
+}
+
+pub fn function_name(line: &str) -> Option<String> {
+    let at = line.find('@')?;
+    let rest = &line[at + 1..];
+    if let Some(rest) = rest.strip_prefix('"') {
+        return Some(rest[..rest.find('"')?].to_string());
+    }
+    Some(rest[..rest.find('(')?].to_string())
+}
+
+fn is_label(line: &str) -> bool {
+    let line = line.split(';').next().unwrap_or_default().trim_end();
+    !line.starts_with(char::is_whitespace) && line.ends_with(':')
+}
+
+/// Maps every function defined in `text` to the lines it spans.
+pub fn functions(text: &str) -> HashMap<String, Lines> {
+    let mut functions = HashMap::new();
+    let mut current: Option<(String, Lines)> = None;
+    // instructions like switch may span several lines
+    let mut depth = 0i32;
+
+    for (n, line) in text.lines().enumerate() {
+        if line.starts_with("define") {
+            current = function_name(line).map(|name| {
+                (
+                    name,
+                    Lines {
+                        define: n,
+                        ..Default::default()
+                    },
+                )
+            });
+            depth = 0;
+            continue;
+        }
+        let Some((_, lines)) = current.as_mut() else {
+            continue;
+        };
+        let code = line.split(';').next().unwrap_or_default();
+        if line.starts_with('}') {
+            functions.extend(current.take());
+        } else if is_label(line) {
+            lines.blocks.push(n);
+        } else if !code.trim().is_empty() {
+            if depth == 0 {
+                if lines.blocks.is_empty() {
+                    lines.blocks.push(lines.define);
+                }
+                lines.insts.push(n);
+                lines.ends.push(n);
+            } else if let Some(end) = lines.ends.last_mut() {
+                *end = n;
+            }
+            depth += code.matches('[').count() as i32 - code.matches(']').count() as i32;
+        }
+    }
+
+    functions
+}
+
+#[test]
+fn test_functions() {
+    let text = include_str!("../examples/ll/for1.ll");
+    assert_eq!(
+        functions(text),
+        HashMap::from([(
+            "main".to_string(),
+            Lines {
+                define: 8,
+                blocks: vec![8, 12, 15],
+                insts: vec![9, 10, 13, 16, 17, 18, 19, 20],
+                ends: vec![9, 10, 13, 16, 17, 18, 19, 20],
+            },
+        )]),
+    );
+
+    let text = "define void @\"a b\"() {\nentry:\n  switch i32 0, label %x [\n    i32 1, label %x\n  ]\nx:\n  ret void\n}\n";
+    assert_eq!(
+        functions(text),
+        HashMap::from([(
+            "a b".to_string(),
+            Lines {
+                define: 0,
+                blocks: vec![1, 5],
+                insts: vec![2, 6],
+                ends: vec![4, 6],
+            },
+        )]),
+    );
+
+    let lines = &functions(text)["a b"];
+    assert_eq!(lines.inst(0), Some((2, 4)));
+    // the label belongs to the instruction after it
+    assert_eq!(
+        [0, 1, 3, 5, 6, 7].map(|l| lines.row(l)),
+        [Some(0), Some(0), Some(0), Some(1), Some(1), None],
+    );
+}
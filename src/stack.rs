@@ -0,0 +1,449 @@
+#![allow(non_snake_case)]
+
+use std::collections::{HashMap, HashSet};
+
+use dioxus::prelude::*;
+
+use crate::callgraph::callee;
+use crate::ir::{self, Function, Name, Operand};
+use crate::ranges::block_starts;
+
+/// A stack slot, i.e. the result of an `alloca`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Slot {
+    pub name: Name,
+    /// The allocated type, like `i32` or `[1001 x i8]`.
+    pub ty: String,
+    /// The row of the `alloca`, in `ir::lva` order.
+    pub row: usize,
+    /// The row of the first use of the slot that is not a load from or a
+    /// store to it of its allocated type, None if it could be promoted to a
+    /// register. For an alloca of more than one element, this is its own row.
+    pub escape: Option<usize>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Stack {
+    pub slots: Vec<Slot>,
+    /// The slots live into and out of every instruction, in `ir::lva` order.
+    pub lives: Vec<ir::OwnedInstLive>,
+    /// Slots that are never live at once and could share a stack location.
+    pub shared: Vec<Vec<Name>>,
+}
+
+/// How an instruction accesses a slot.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Access {
+    Load,
+    Store,
+    Start,
+    End,
+}
+
+/// The operand of the instruction with `opcode` and `uses` that it loads
+/// from, stores to or marks the lifetime of, if that is all it does.
+pub fn access(opcode: usize, uses: &[Operand], string: &str) -> Option<(usize, Access)> {
+    if string.contains(" volatile ") {
+        return None;
+    }
+    let access = match (opcode, callee(string)) {
+        (32 /* load */, _) => (0, Access::Load),
+        // operands of a store are stored as [value, ptr]
+        (33 /* store */, _) => (1, Access::Store),
+        (56 /* call */, Some(c)) if c.starts_with("llvm.lifetime.start") => (1, Access::Start),
+        (56 /* call */, Some(c)) if c.starts_with("llvm.lifetime.end") => (1, Access::End),
+        _ => return None,
+    };
+    uses.get(access.0)?.name.as_ref()?;
+    Some(access)
+}
+
+/// The opcode, def, uses, string and type of an instruction, where the type
+/// is the allocated type of an alloca and the loaded type of a load.
+type Row<'a> = (
+    usize,
+    Option<&'a Name>,
+    &'a [Operand],
+    &'a str,
+    Option<&'a str>,
+);
+
+/// Finds the stack slots of `f`, which of them can be promoted to registers,
+/// when they are live and which of them could share a location.
+///
+/// A promotable slot is live from a store to it until the last load of the
+/// value stored, and dead after `llvm.lifetime.end` or before
+/// `llvm.lifetime.start`. A slot that escapes may be read through another
+/// pointer at any time, so it is live for all of its lifetime, i.e. between
+/// its lifetime markers or in the whole function if it has none.
+pub fn analyze(f: &Function) -> Stack {
+    let rows: Vec<Row> = f
+        .basic_blocks
+        .iter()
+        .flat_map(|b| {
+            b.insts
+                .iter()
+                .map(|i| {
+                    (
+                        i.opcode,
+                        i.def.as_ref(),
+                        &i.uses[..],
+                        &i.string[..],
+                        i.ty.as_deref(),
+                    )
+                })
+                .chain([(
+                    b.term.opcode,
+                    b.term.def.as_ref(),
+                    &b.term.uses[..],
+                    &b.term.string[..],
+                    None,
+                )])
+        })
+        .collect();
+    let starts = block_starts(f);
+    let index: HashMap<&Name, usize> = f
+        .basic_blocks
+        .iter()
+        .map(|b| &b.name)
+        .zip(starts.iter().copied())
+        .collect();
+    let succs: Vec<Vec<usize>> = f
+        .basic_blocks
+        .iter()
+        .zip(&starts)
+        .flat_map(|(b, &start)| {
+            let term = b
+                .term
+                .uses
+                .iter()
+                .filter(|o| o.ty.id == 8)
+                .filter_map(|o| index.get(o.name.as_ref()?).copied())
+                .collect();
+            (start + 1..start + b.insts.len() + 1)
+                .map(|r| vec![r])
+                .chain([term])
+        })
+        .collect();
+    let mut preds = vec![vec![]; rows.len()];
+    for (r, succs) in succs.iter().enumerate() {
+        for &s in succs {
+            preds[s].push(r);
+        }
+    }
+
+    let accesses: Vec<Option<(&Name, Access)>> = rows
+        .iter()
+        .map(|&(opcode, _, uses, string, _)| {
+            let (k, access) = access(opcode, uses, string)?;
+            Some((uses[k].name.as_ref()?, access))
+        })
+        .collect();
+    let slots: Vec<Slot> = rows
+        .iter()
+        .enumerate()
+        .filter(|(_, (opcode, ..))| *opcode == 31 /* alloca */)
+        .filter_map(|(row, &(_, def, count, string, _))| {
+            let name = def?;
+            let (_, ty) = string.split_once("alloca ")?;
+            let ty = crate::module::ty(ty).0;
+            // like LLVM's isAllocaPromotable, a slot escapes if it is used as
+            // anything but the pointer that is accessed, e.g. stored or passed
+            // to a call, or if it is accessed as another type than allocated
+            let escape = rows.iter().position(|&(opcode, _, uses, string, loaded)| {
+                let access = access(opcode, uses, string);
+                let mistyped = match access {
+                    Some((k, Access::Load)) if uses[k].name.as_ref() == Some(name) => {
+                        loaded != Some(ty)
+                    }
+                    Some((k, Access::Store)) if uses[k].name.as_ref() == Some(name) => {
+                        uses[0].ty.name != ty
+                    }
+                    _ => false,
+                };
+                let access = access.map(|(k, _)| k);
+                mistyped
+                    || !callee(string).is_some_and(|c| c.starts_with("llvm.dbg."))
+                        && uses
+                            .iter()
+                            .enumerate()
+                            .any(|(k, o)| o.name.as_ref() == Some(name) && access != Some(k))
+            });
+            // only a single element can be promoted
+            let single = matches!(count, [c] if c.constant && c.value.as_deref() == Some("1"));
+            Some(Slot {
+                name: name.clone(),
+                ty: ty.to_string(),
+                row,
+                escape: if single { escape } else { Some(row) },
+            })
+        })
+        .collect();
+    let promotable: HashSet<&Name> = slots
+        .iter()
+        .filter(|s| s.escape.is_none())
+        .map(|s| &s.name)
+        .collect();
+
+    // promotable slots are live from stores back from loads:
+    // in[r] = load[r] U (out[r] - (store[r] U start[r] U end[r]))
+    let mut lives = vec![(HashSet::<Name>::new(), HashSet::<Name>::new()); rows.len()];
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for r in (0..rows.len()).rev() {
+            let out: HashSet<Name> = succs[r]
+                .iter()
+                .flat_map(|&s| lives[s].0.iter().cloned())
+                .collect();
+            let mut r#in = out.clone();
+            match accesses[r] {
+                Some((n, Access::Load)) if promotable.contains(n) => {
+                    r#in.insert(n.clone());
+                }
+                Some((n, _)) => {
+                    r#in.remove(n);
+                }
+                None => {}
+            }
+            if (&r#in, &out) != (&lives[r].0, &lives[r].1) {
+                lives[r] = (r#in, out);
+                changed = true;
+            }
+        }
+    }
+
+    // escaping slots are live forwards from their lifetime.start, or from
+    // the entry if they have none, until their lifetime.end
+    let escaping: Vec<&Name> = slots
+        .iter()
+        .filter(|s| s.escape.is_some())
+        .map(|s| &s.name)
+        .collect();
+    let entry: HashSet<&Name> = escaping
+        .iter()
+        .filter(|&&n| !accesses.contains(&Some((n, Access::Start))))
+        .copied()
+        .collect();
+    let mut started = vec![(HashSet::<&Name>::new(), HashSet::<&Name>::new()); rows.len()];
+    let mut changed = !escaping.is_empty();
+    while changed {
+        changed = false;
+        for r in 0..rows.len() {
+            let mut r#in: HashSet<&Name> = preds[r]
+                .iter()
+                .flat_map(|&p| started[p].1.iter().copied())
+                .collect();
+            if r == 0 {
+                r#in.extend(&entry);
+            }
+            let mut out = r#in.clone();
+            match accesses[r] {
+                Some((n, Access::Start)) if escaping.contains(&n) => {
+                    out.insert(n);
+                }
+                Some((n, Access::End)) => {
+                    out.remove(n);
+                }
+                _ => {}
+            }
+            if (&r#in, &out) != (&started[r].0, &started[r].1) {
+                started[r] = (r#in, out);
+                changed = true;
+            }
+        }
+    }
+    for (l, (r#in, out)) in lives.iter_mut().zip(started) {
+        l.0.extend(r#in.into_iter().cloned());
+        l.1.extend(out.into_iter().cloned());
+    }
+
+    // two slots interfere if both are live at once, or if one is written
+    // while the other is live
+    let interfere = |a: &Name, b: &Name| {
+        lives.iter().zip(&accesses).any(|((r#in, out), access)| {
+            let written = |n| matches!(access, Some((w, Access::Store | Access::Start)) if *w == n);
+            (r#in.contains(a) && r#in.contains(b))
+                || (out.contains(a) && out.contains(b))
+                || (written(a) && out.contains(b))
+                || (written(b) && out.contains(a))
+        })
+    };
+    let mut shared: Vec<Vec<Name>> = vec![];
+    for slot in &slots {
+        match shared
+            .iter_mut()
+            .find(|g| g.iter().all(|n| !interfere(n, &slot.name)))
+        {
+            Some(g) => g.push(slot.name.clone()),
+            None => shared.push(vec![slot.name.clone()]),
+        }
+    }
+
+    Stack {
+        slots,
+        lives: lives
+            .into_iter()
+            .zip(ir::strings(f))
+            .map(|((r#in, out), s)| (r#in, out, s.to_string()))
+            .collect(),
+        shared,
+    }
+}
+
+/// The stack slots of a function, whether they can be promoted to registers
+/// and the slots that could share a location.
+#[component]
+pub fn Slots(stack: Stack, variables: HashMap<Name, String>) -> Element {
+    let strings: Vec<_> = stack
+        .lives
+        .iter()
+        .map(|(_, _, s)| s.trim().to_string())
+        .collect();
+    let shared: Vec<_> = stack
+        .shared
+        .iter()
+        .filter(|g| g.len() > 1)
+        .map(|g| crate::debug::labels(&g.iter().cloned().collect(), &variables))
+        .collect();
+
+    rsx! {
+        div { class: "ml-1 mt-2 font-mono text-sm",
+            h2 { class: "font-bold", "Stack slots" }
+            for slot in stack.slots {
+                div { class: "flex border-b border-zinc-200 dark:border-zinc-700",
+                    span { class: "flex-none w-1/4 truncate", {crate::debug::label(&slot.name, &variables)} }
+                    span { class: "flex-none w-1/6", "{slot.ty}" }
+                    match slot.escape {
+                        None => rsx! {
+                            span { class: "flex-1 text-green-600", "promotable" }
+                        },
+                        Some(row) => rsx! {
+                            span { class: "flex-1 truncate text-zinc-500", title: "{strings[row]}",
+                                "escapes: {strings[row]}"
+                            }
+                        },
+                    }
+                }
+            }
+            for group in shared {
+                div { "Could share a location: {group}" }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_analyze() {
+    use crate::builder::{constant, global, Builder};
+
+    let mut b = Builder::new("f");
+    let slots: Vec<_> = (0..4).map(|_| b.alloca("i32")).collect();
+    let exit = b.block();
+    b.store(&constant("i32", 0), &slots[0]);
+    let x = b.load("i32", &slots[0]);
+    b.store(&x, &slots[1]);
+    let size = constant("i64", 4);
+    b.call(
+        "void",
+        &global("llvm.lifetime.start.p0"),
+        &[&size, &slots[2]],
+    );
+    b.call("void", &global("g"), &[&slots[2]]);
+    b.call("void", &global("llvm.lifetime.end.p0"), &[&size, &slots[2]]);
+    b.store(&constant("i32", 1), &slots[3]);
+    b.load("i32", &slots[3]);
+    b.br(exit);
+
+    b.position(exit);
+    let x = b.load("i32", &slots[1]);
+    b.ret(Some(&x));
+    let f = b.build();
+
+    let stack = analyze(&f);
+    assert_eq!(
+        stack
+            .slots
+            .iter()
+            .map(|s| (&s.name, &s.ty[..], s.row, s.escape))
+            .collect::<Vec<_>>(),
+        vec![
+            (&Name::Number(1), "i32", 0, None),
+            (&Name::Number(2), "i32", 1, None),
+            (&Name::Number(3), "i32", 2, Some(8)),
+            (&Name::Number(4), "i32", 3, None),
+        ],
+    );
+
+    let sorted = |s: &HashSet<Name>| {
+        let mut s: Vec<_> = s
+            .iter()
+            .map(|n| match n {
+                Name::Number(n) => *n,
+                Name::Name(_) => unreachable!(),
+            })
+            .collect();
+        s.sort();
+        s
+    };
+    assert_eq!(
+        stack
+            .lives
+            .iter()
+            .map(|(r#in, out, _)| (sorted(r#in), sorted(out)))
+            .collect::<Vec<_>>(),
+        vec![
+            (vec![], vec![]),
+            (vec![], vec![]),
+            (vec![], vec![]),
+            (vec![], vec![]),
+            // store i32 0, ptr %1
+            (vec![], vec![1]),
+            // %5 = load i32, ptr %1
+            (vec![1], vec![]),
+            // store i32 %5, ptr %2
+            (vec![], vec![2]),
+            // lifetime.start %3
+            (vec![2], vec![2, 3]),
+            (vec![2, 3], vec![2, 3]),
+            // lifetime.end %3
+            (vec![2, 3], vec![2]),
+            // store i32 1, ptr %4
+            (vec![2], vec![2, 4]),
+            (vec![2, 4], vec![2]),
+            (vec![2], vec![2]),
+            // %8 = load i32, ptr %2
+            (vec![2], vec![]),
+            (vec![], vec![]),
+        ],
+    );
+    assert_eq!(
+        stack.shared,
+        vec![
+            vec![Name::Number(1), Name::Number(2)],
+            vec![Name::Number(3), Name::Number(4)],
+        ],
+    );
+
+    // slots accessed as another type, or of more than one element, are not
+    // promotable
+    let mut b = Builder::new("g");
+    let (wide, narrow) = (b.alloca("i32"), b.alloca("i32"));
+    b.array_alloca("i32", &constant("i32", 2));
+    b.store(&constant("i64", 0), &wide);
+    b.load("i16", &narrow);
+    b.ret(None);
+    let f = b.build();
+    assert_eq!(
+        analyze(&f)
+            .slots
+            .iter()
+            .map(|s| (&s.name, s.escape))
+            .collect::<Vec<_>>(),
+        vec![
+            (&Name::Number(1), Some(3)),
+            (&Name::Number(2), Some(4)),
+            (&Name::Number(3), Some(2)),
+        ],
+    );
+}
@@ -3,8 +3,9 @@
 use dioxus::prelude::*;
 
 #[component]
-pub fn Tabs(tabs: Vec<(String, Element)>) -> Element {
-    let mut active = use_signal(|| 0);
+pub fn Tabs(tabs: Vec<(String, Element)>, active: Option<Signal<usize>>) -> Element {
+    let local = use_signal(|| 0);
+    let mut active = active.unwrap_or(local);
     rsx! {
         div { class: "flex flex-col h-full",
             nav { class: "flex-none",
@@ -22,7 +23,7 @@ pub fn Tabs(tabs: Vec<(String, Element)>) -> Element {
                     }
                 }
             }
-            div { class: "bg-white dark:bg-zinc-900 flex-1 overflow-scroll", {&tabs[*active.read()].1} }
+            div { class: "bg-white dark:bg-zinc-900 flex-1 overflow-scroll", {tabs.get(*active.read()).map(|(_, t)| t.clone())} }
         }
     }
 }
@@ -0,0 +1,1041 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::ir::{BasicBlock, Function, Instruction, Name, Operand, Terminator};
+use crate::module::{split, ty};
+
+/// The keyword of every opcode of LLVM 17 that has one.
+pub fn opcode(opcode: usize) -> Option<&'static str> {
+    const NAMES: [&str; 67] = [
+        "ret",
+        "br",
+        "switch",
+        "indirectbr",
+        "invoke",
+        "resume",
+        "unreachable",
+        "cleanupret",
+        "catchret",
+        "catchswitch",
+        "callbr",
+        "fneg",
+        "add",
+        "fadd",
+        "sub",
+        "fsub",
+        "mul",
+        "fmul",
+        "udiv",
+        "sdiv",
+        "fdiv",
+        "urem",
+        "srem",
+        "frem",
+        "shl",
+        "lshr",
+        "ashr",
+        "and",
+        "or",
+        "xor",
+        "alloca",
+        "load",
+        "store",
+        "getelementptr",
+        "fence",
+        "cmpxchg",
+        "atomicrmw",
+        "trunc",
+        "zext",
+        "sext",
+        "fptoui",
+        "fptosi",
+        "uitofp",
+        "sitofp",
+        "fptrunc",
+        "fpext",
+        "ptrtoint",
+        "inttoptr",
+        "bitcast",
+        "addrspacecast",
+        "cleanuppad",
+        "catchpad",
+        "icmp",
+        "fcmp",
+        "phi",
+        "call",
+        "select",
+        "",
+        "",
+        "va_arg",
+        "extractelement",
+        "insertelement",
+        "shufflevector",
+        "extractvalue",
+        "insertvalue",
+        "landingpad",
+        "freeze",
+    ];
+    NAMES
+        .get(opcode.checked_sub(1)?)
+        .copied()
+        .filter(|n| !n.is_empty())
+}
+
+/// Words between an opcode and its operands that the analyses ignore.
+const FLAGS: &[&str] = &[
+    "tail",
+    "musttail",
+    "notail",
+    "nuw",
+    "nsw",
+    "exact",
+    "disjoint",
+    "nneg",
+    "inbounds",
+    "volatile",
+    "atomic",
+    "fast",
+    "nnan",
+    "ninf",
+    "nsz",
+    "arcp",
+    "contract",
+    "afn",
+    "reassoc",
+    "fastcc",
+    "coldcc",
+    "ccc",
+    "swiftcc",
+    "swifttailcc",
+    "tailcc",
+];
+
+/// Words besides the `FLAGS` and `ATTRIBUTES` that change what an
+/// instruction does but have no field, like an alignment or a numbered
+/// calling convention, so an instruction with any of them is printed as its
+/// `string`.
+const KEPT: &[&str] = &["align", "cc"];
+
+/// Whether the instruction in `string` has any of the words in `FLAGS`,
+/// `ATTRIBUTES` or `KEPT`, or metadata attached like `!dbg !7`.
+fn kept(string: &str) -> bool {
+    let code = string.split(';').next().unwrap_or_default();
+    code.split(|c: char| c.is_whitespace() || c == ',')
+        .any(|w| {
+            FLAGS.contains(&w)
+                || KEPT.contains(&w)
+                || ATTRIBUTES
+                    .iter()
+                    .any(|a| w == *a || a.ends_with('(') && w.starts_with(a))
+                || w.strip_prefix('!')
+                    .is_some_and(|m| m.starts_with(|c: char| c.is_ascii_lowercase()))
+        })
+}
+
+/// Parameter and return attributes, some of which take an argument.
+const ATTRIBUTES: &[&str] = &[
+    "noundef",
+    "nonnull",
+    "noalias",
+    "nocapture",
+    "readonly",
+    "readnone",
+    "writeonly",
+    "signext",
+    "zeroext",
+    "inreg",
+    "returned",
+    "immarg",
+    "nofree",
+    "nest",
+    "swiftself",
+    "swifterror",
+    "dereferenceable(",
+    "dereferenceable_or_null(",
+    "byval(",
+    "sret(",
+    "inalloca(",
+    "preallocated(",
+    "elementtype(",
+    "align(",
+    "align ",
+];
+
+/// `s` without the attributes it starts with.
+fn skip_attributes(mut s: &str) -> &str {
+    loop {
+        s = s.trim_start();
+        let Some(a) = ATTRIBUTES.iter().find(|a| {
+            s.starts_with(*a) && (a.ends_with(['(', ' ']) || s[a.len()..].starts_with(' '))
+        }) else {
+            return s;
+        };
+        s = &s[a.len()..];
+        if a.ends_with('(') {
+            s = s.find(')').map_or("", |i| &s[i + 1..]);
+        } else if *a == "align " {
+            s = s.trim_start().split_once(' ').map_or("", |(_, s)| s);
+        }
+    }
+}
+
+/// The value of a typed operand like `ptr noundef @.str`.
+fn value(operand: &str) -> String {
+    let (_, rest) = ty(operand);
+    skip_attributes(rest).trim().to_string()
+}
+
+/// `s` without the flags it starts with.
+fn skip_flags(mut s: &str) -> &str {
+    while let Some((word, rest)) = s.trim_start().split_once(' ') {
+        if !FLAGS.contains(&word) {
+            break;
+        }
+        s = rest;
+    }
+    s.trim_start()
+}
+
+/// The type and value of an operand, like `i32` and `0`.
+type Typed = (String, String);
+
+/// The types and values of the operands in `string`, in the same order as
+/// the uses of an instruction with `opcode`, the type it is about and its
+/// predicate.
+fn operands(opcode: usize, string: &str) -> Option<(Vec<Typed>, Option<String>, Option<String>)> {
+    let string = string.split(';').next()?.trim();
+    let string = match string.split_once(" = ") {
+        Some((def, rest)) if def.starts_with('%') && !def.contains(' ') => rest,
+        _ => string,
+    };
+    let string = skip_flags(string);
+    let (keyword, rest) = string
+        .split_once(char::is_whitespace)
+        .unwrap_or((string, ""));
+    if Some(keyword) != self::opcode(opcode) {
+        return None;
+    }
+    let rest = skip_flags(rest);
+    // metadata and alignment are not operands
+    let pieces: Vec<&str> = split(rest, ',')
+        .into_iter()
+        .filter(|p| !p.starts_with('!') && !p.starts_with("align "))
+        .collect();
+    let typed = |p: &str| (ty(p).0.to_string(), value(p));
+    let all = |pieces: &[&str]| pieces.iter().map(|p| typed(p)).collect::<Vec<_>>();
+    let label = |l: &str| ("label".to_string(), l.to_string());
+
+    Some(match opcode {
+        1 if rest.trim() == "void" => (vec![], None, None),
+        1 | 12 | 67 => (all(&pieces[..1.min(pieces.len())]), None, None),
+        2 => match &pieces[..] {
+            [l] => (vec![typed(l)], None, None),
+            // operands of a conditional br are stored as [cond, false, true]
+            [c, t, f] => (vec![typed(c), typed(f), typed(t)], None, None),
+            _ => return None,
+        },
+        3 => {
+            let (cond, rest) = rest.split_once(',')?;
+            let (default, cases) = rest.split_once('[')?;
+            let cases = cases.trim().trim_end_matches(']').replace(',', " ");
+            let words: Vec<_> = cases.split_whitespace().collect();
+            let mut values = vec![typed(cond), typed(default)];
+            for case in words.chunks(4) {
+                let [ty, v, _, dest] = case else {
+                    return None;
+                };
+                values.extend([(ty.to_string(), v.to_string()), label(dest)]);
+            }
+            (values, None, None)
+        }
+        7 => (vec![], None, None),
+        13..=30 => match &pieces[..] {
+            [a, b] => {
+                let (ty, a) = typed(a);
+                (vec![(ty.clone(), a), (ty, b.to_string())], None, None)
+            }
+            _ => return None,
+        },
+        31 => {
+            let (ty, _) = ty(pieces.first()?);
+            let count = match pieces.get(1) {
+                Some(p) if !p.starts_with("addrspace(") => typed(p),
+                _ => ("i32".to_string(), "1".to_string()),
+            };
+            (vec![count], Some(ty.to_string()), None)
+        }
+        32 | 34 => {
+            let (ty, _) = ty(pieces.first()?);
+            (
+                atomic(string, all(&pieces[1..])),
+                Some(ty.to_string()),
+                None,
+            )
+        }
+        33 => (atomic(string, all(&pieces)), None, None),
+        57 => (all(&pieces), None, None),
+        38..=50 => {
+            let (operand, to) = rest.rsplit_once(" to ")?;
+            let (to, _) = ty(to.split(',').next()?);
+            (vec![typed(operand)], Some(to.to_string()), None)
+        }
+        53 | 54 => {
+            let (predicate, rest) = rest.trim().split_once(' ')?;
+            match &split(rest, ',')[..] {
+                [a, b, ..] => {
+                    let (ty, a) = typed(a);
+                    (
+                        vec![(ty.clone(), a), (ty, b.to_string())],
+                        None,
+                        Some(predicate.to_string()),
+                    )
+                }
+                _ => return None,
+            }
+        }
+        55 => {
+            let (ty, incoming) = ty(rest);
+            let values = split(incoming, ',')
+                .into_iter()
+                .filter(|p| p.starts_with('['))
+                .map(|p| {
+                    let p = p.trim_start_matches('[').trim_end_matches(']');
+                    Some((ty.to_string(), split(p, ',').first()?.to_string()))
+                })
+                .collect::<Option<_>>()?;
+            (values, None, None)
+        }
+        56 => {
+            // the arguments are in the first parens right after a name, as
+            // opposed to those of a function type like i32 (ptr, ...)
+            let (mut depth, mut string, mut open, mut close) = (0, false, None, None);
+            for (i, c) in rest.char_indices() {
+                match c {
+                    '"' => string = !string,
+                    '(' if !string
+                        && depth == 0
+                        && open.is_none()
+                        && !rest[..i].ends_with(char::is_whitespace) =>
+                    {
+                        open = Some(i);
+                        depth += 1;
+                    }
+                    '(' | '[' | '{' | '<' if !string => depth += 1,
+                    ')' | ']' | '}' | '>' if !string => {
+                        depth -= 1;
+                        if depth == 0 && open.is_some() {
+                            close = Some(i);
+                            break;
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            let (open, close) = (open?, close?);
+            let before = &rest[..open];
+            let start = if let Some(quoted) = before.strip_suffix('"') {
+                quoted.rfind('"')?.checked_sub(1)?
+            } else {
+                before.rfind(char::is_whitespace)? + 1
+            };
+            let mut values: Vec<Typed> = split(&rest[open + 1..close], ',')
+                .iter()
+                .map(|a| typed(a))
+                .collect();
+            values.push(("ptr".to_string(), rest[start..open].to_string()));
+            let ty = skip_attributes(&rest[..start]).trim().to_string();
+            (values, Some(ty), None)
+        }
+        _ => return None,
+    })
+}
+
+/// `values` without the ordering that follows the pointer of an atomic load
+/// or store, like `unordered` or `syncscope("agent") seq_cst`.
+fn atomic(string: &str, mut values: Vec<Typed>) -> Vec<Typed> {
+    if string.split_whitespace().any(|w| w == "atomic") {
+        if let Some((_, ptr)) = values.last_mut() {
+            *ptr = ptr
+                .split_whitespace()
+                .next()
+                .unwrap_or_default()
+                .to_string();
+        }
+    }
+    values
+}
+
+/// Sets the values of the constants in `uses` and returns the type and
+/// predicate of the instruction, if `string` has as many operands.
+fn annotate(
+    opcode: usize,
+    string: &str,
+    uses: &mut [Operand],
+) -> Option<(Option<String>, Option<String>)> {
+    let (values, ty, predicate) = operands(opcode, string)?;
+    if values.len() != uses.len() {
+        return None;
+    }
+    for (o, (_, v)) in uses.iter_mut().zip(values) {
+        if o.constant {
+            o.value = Some(v);
+        }
+    }
+    Some((ty, predicate))
+}
+
+/// Reads what the JSON leaves out of every instruction of `f` from its
+/// `string`: the values of constants, the types of allocas, loads, casts,
+/// calls and getelementptrs, and the predicates of comparisons.
+pub fn read(f: &mut Function) {
+    for b in &mut f.basic_blocks {
+        for i in &mut b.insts {
+            if let Some((ty, predicate)) = annotate(i.opcode, &i.string, &mut i.uses) {
+                (i.ty, i.predicate) = (ty, predicate);
+            }
+        }
+        annotate(b.term.opcode, &b.term.string, &mut b.term.uses);
+    }
+}
+
+/// `name` as an identifier, quoted if it is not one as is, like `"a b"` or
+/// `"2.0"`.
+pub fn identifier(name: &str) -> String {
+    let valid = |c: char| c.is_ascii_alphanumeric() || matches!(c, '-' | '$' | '.' | '_');
+    if name.starts_with(|c: char| valid(c) && !c.is_ascii_digit()) && name.chars().all(valid) {
+        name.to_string()
+    } else {
+        format!("{name:?}")
+    }
+}
+
+/// The local `name` as it is written in LLVM IR, like `%1` or `%"2.0"`.
+pub fn local(name: &Name) -> String {
+    match name {
+        Name::Name(n) => format!("%{}", identifier(n)),
+        Name::Number(n) => format!("%{n}"),
+    }
+}
+
+/// The text of the value of `o`, None for constants whose value is unknown.
+fn val(o: &Operand) -> Option<String> {
+    match &o.name {
+        Some(name) => Some(local(name)),
+        None => o.value.clone(),
+    }
+}
+
+fn typed(o: &Operand) -> Option<String> {
+    Some(format!("{} {}", o.ty.name, val(o)?))
+}
+
+fn list(operands: &[Operand]) -> Option<String> {
+    Some(
+        operands
+            .iter()
+            .map(typed)
+            .collect::<Option<Vec<_>>>()?
+            .join(", "),
+    )
+}
+
+impl Instruction {
+    fn print(&self) -> Option<String> {
+        if kept(&self.string) {
+            return None;
+        }
+        let op = opcode(self.opcode)?;
+        let u = &self.uses;
+        let ty = || self.ty.as_deref();
+        let body = match self.opcode {
+            12 | 67 => format!("{op} {}", typed(u.first()?)?),
+            13..=30 => format!("{op} {}, {}", typed(u.first()?)?, val(u.get(1)?)?),
+            31 => match u.first() {
+                Some(count) if count.value.as_deref() != Some("1") => {
+                    format!("{op} {}, {}", ty()?, typed(count)?)
+                }
+                _ => format!("{op} {}", ty()?),
+            },
+            32 | 34 => format!("{op} {}, {}", ty()?, list(u)?),
+            33 | 57 => format!("{op} {}", list(u)?),
+            38..=50 => format!("{op} {} to {}", typed(u.first()?)?, ty()?),
+            53 | 54 => format!(
+                "{op} {} {}, {}",
+                self.predicate.as_deref()?,
+                typed(u.first()?)?,
+                val(u.get(1)?)?,
+            ),
+            55 => {
+                let incoming = u
+                    .iter()
+                    .zip(self.blocks.as_ref()?)
+                    .map(|(o, b)| Some(format!("[ {}, {} ]", val(o)?, local(b))))
+                    .collect::<Option<Vec<_>>>()?;
+                format!("{op} {} {}", u.first()?.ty.name, incoming.join(", "))
+            }
+            56 => {
+                let (callee, args) = u.split_last()?;
+                format!("{op} {} {}({})", ty()?, val(callee)?, list(args)?)
+            }
+            _ => return None,
+        };
+        Some(match &self.def {
+            Some(def) => format!("{} = {body}", local(def)),
+            None => body,
+        })
+    }
+}
+
+impl Terminator {
+    fn print(&self) -> Option<String> {
+        if kept(&self.string) {
+            return None;
+        }
+        Some(match (self.opcode, &self.uses[..]) {
+            (1, []) => "ret void".to_string(),
+            (1, [o]) => format!("ret {}", typed(o)?),
+            (2, [l]) => format!("br {}", typed(l)?),
+            (2, [c, f, t]) => format!("br {}, {}, {}", typed(c)?, typed(t)?, typed(f)?),
+            (3, [c, default, cases @ ..]) => {
+                let cases = cases
+                    .chunks(2)
+                    .map(|c| Some(format!("{}, {}", typed(&c[0])?, typed(c.get(1)?)?)))
+                    .collect::<Option<Vec<_>>>()?;
+                format!(
+                    "switch {}, {} [ {} ]",
+                    typed(c)?,
+                    typed(default)?,
+                    cases.join(" ")
+                )
+            }
+            (7, []) => "unreachable".to_string(),
+            _ => return None,
+        })
+    }
+}
+
+/// Prints the instruction from its fields, or as its `string` if it has
+/// fields that are not known, e.g. for opcodes like invoke or landingpad, or
+/// parts that have none, like `nsw`, an alignment or attached metadata.
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.print() {
+            Some(text) => write!(f, "{text}"),
+            None => write!(f, "{}", self.string.trim()),
+        }
+    }
+}
+
+impl fmt::Display for Terminator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.print() {
+            Some(text) => write!(f, "{text}"),
+            None => write!(f, "{}", self.string.trim()),
+        }
+    }
+}
+
+impl fmt::Display for BasicBlock {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{}:", &local(&self.name)[1..])?;
+        for i in &self.insts {
+            writeln!(f, "  {i}")?;
+        }
+        writeln!(f, "  {}", self.term)
+    }
+}
+
+/// `string` with every `%n` in `numbers` renumbered.
+fn renumber_string(string: &str, numbers: &HashMap<usize, usize>) -> String {
+    let mut out = String::new();
+    let mut rest = string;
+    while let Some(i) = rest.find('%') {
+        out.push_str(&rest[..=i]);
+        rest = &rest[i + 1..];
+        let digits = rest
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        // like %2.0, which is a name
+        let named = rest[digits..]
+            .starts_with(|c: char| c.is_ascii_alphanumeric() || matches!(c, '-' | '$' | '.' | '_'));
+        match rest[..digits].parse().ok().filter(|_| !named) {
+            Some(n) if numbers.contains_key(&n) => out.push_str(&numbers[&n].to_string()),
+            _ => out.push_str(&rest[..digits]),
+        }
+        rest = &rest[digits..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Numbers the unnamed values and blocks of `f` in order from 0, as LLVM
+/// requires after some are removed, in its fields and in the `string` of
+/// every instruction.
+fn renumber(f: &mut Function) {
+    let mut numbers = HashMap::new();
+    let names = f
+        .params
+        .iter()
+        .map(|p| &p.name)
+        .chain(f.basic_blocks.iter().flat_map(|b| {
+            [&b.name]
+                .into_iter()
+                .chain(b.insts.iter().filter_map(|i| i.def.as_ref()))
+                .chain(&b.term.def)
+        }));
+    for name in names {
+        if let Name::Number(n) = name {
+            let next = numbers.len();
+            numbers.entry(*n).or_insert(next);
+        }
+    }
+
+    let rename = |name: &mut Name| {
+        if let Name::Number(n) = name {
+            *n = numbers.get(n).copied().unwrap_or(*n);
+        }
+    };
+    for p in &mut f.params {
+        rename(&mut p.name);
+    }
+    for b in &mut f.basic_blocks {
+        rename(&mut b.name);
+        for i in &mut b.insts {
+            i.def.iter_mut().for_each(rename);
+            i.uses
+                .iter_mut()
+                .filter_map(|o| o.name.as_mut())
+                .for_each(rename);
+            i.blocks.iter_mut().flatten().for_each(rename);
+            i.string = renumber_string(&i.string, &numbers);
+        }
+        b.term.def.iter_mut().for_each(rename);
+        b.term
+            .uses
+            .iter_mut()
+            .filter_map(|o| o.name.as_mut())
+            .for_each(rename);
+        b.term.string = renumber_string(&b.term.string, &numbers);
+    }
+}
+
+/// Prints the definition of the function with its unnamed values and blocks
+/// renumbered. Its return type is that of its `ret` instructions, as the JSON
+/// does not have it, and a numbered entry block is left unlabeled like LLVM
+/// does.
+impl fmt::Display for Function {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut renumbered = self.clone();
+        renumber(&mut renumbered);
+        let ret = renumbered
+            .basic_blocks
+            .iter()
+            .filter(|b| b.term.opcode == 1)
+            .find_map(|b| b.term.uses.first())
+            .map_or("void", |o| &o.ty.name);
+        let params: Vec<_> = renumbered
+            .params
+            .iter()
+            .map(|p| format!("{} {}", p.ty.name, local(&p.name)))
+            .collect();
+        writeln!(
+            f,
+            "define {ret} @{}({}) {{",
+            identifier(&renumbered.name),
+            params.join(", ")
+        )?;
+        for (i, b) in renumbered.basic_blocks.iter().enumerate() {
+            match (i, &b.name) {
+                (0, Name::Number(_)) => {
+                    let b = b.to_string();
+                    write!(f, "{}", b.split_once('\n').map_or("", |(_, insts)| insts))?;
+                }
+                (0, _) => write!(f, "{b}")?,
+                _ => write!(f, "\n{b}")?,
+            }
+        }
+        writeln!(f, "}}")
+    }
+}
+
+#[test]
+fn test_print() {
+    use crate::builder::{constant, Builder};
+
+    // what the JSON leaves out of the operands of an instruction
+    let forget = |uses: &mut Vec<Operand>| {
+        for o in uses.iter_mut().filter(|o| o.constant) {
+            o.value = None;
+        }
+    };
+
+    // for1.ll, as the JSON has it
+    let mut f = crate::ir::for1();
+    let mut strings = [
+        "  %3 = icmp sgt i32 %0, 0",
+        "  br i1 %3, label %5, label %4",
+        "  ret i32 0",
+        "  %6 = phi i32 [ %8, %5 ], [ 0, %2 ]",
+        "  %7 = tail call i32 (ptr, ...) @printf(ptr noundef nonnull dereferenceable(1) @.str, i32 noundef %6)",
+        "  %8 = add nuw nsw i32 %6, 1",
+        "  %9 = icmp eq i32 %8, %0",
+        "  br i1 %9, label %4, label %5, !llvm.loop !5",
+    ]
+    .into_iter();
+    for b in &mut f.basic_blocks {
+        for i in &mut b.insts {
+            i.string = strings.next().unwrap().to_string();
+            (i.ty, i.predicate) = (None, None);
+            forget(&mut i.uses);
+        }
+        b.term.string = strings.next().unwrap().to_string();
+        forget(&mut b.term.uses);
+    }
+    // unknown values are printed as the original text
+    assert_eq!(
+        f.basic_blocks[2].insts[1].to_string(),
+        f.basic_blocks[2].insts[1].string.trim()
+    );
+    read(&mut f);
+    assert_eq!(
+        f.to_string(),
+        "define i32 @main(i32 %0, ptr %1) {
+  %3 = icmp sgt i32 %0, 0
+  br i1 %3, label %5, label %4
+
+4:
+  ret i32 0
+
+5:
+  %6 = phi i32 [ %8, %5 ], [ 0, %2 ]
+  %7 = tail call i32 (ptr, ...) @printf(ptr noundef nonnull dereferenceable(1) @.str, i32 noundef %6)
+  %8 = add nuw nsw i32 %6, 1
+  %9 = icmp eq i32 %8, %0
+  br i1 %9, label %4, label %5, !llvm.loop !5
+}
+",
+    );
+
+    // the parameters are renumbered with the body
+    let mut b = Builder::new("f");
+    let p = b.param("i32");
+    b.ret(Some(&p));
+    let mut f = b.build();
+    f.params[0].name = Name::Number(7);
+    f.basic_blocks[0].term.uses[0].name = Some(Name::Number(7));
+    f.basic_blocks[0].term.string = "  ret i32 %7".to_string();
+    assert_eq!(f.to_string(), "define i32 @f(i32 %0) {\n  ret i32 %0\n}\n");
+
+    let numbers = HashMap::from([(2, 1), (5, 4), (6, 5), (8, 7)]);
+    assert_eq!(
+        renumber_string(
+            "  %6 = phi i32 [ %8, %5 ], [ %2.0, %2 ], [ %9, %x ]",
+            &numbers
+        ),
+        "  %5 = phi i32 [ %7, %4 ], [ %2.0, %1 ], [ %9, %x ]",
+    );
+
+    assert_eq!(identifier("a b"), r#""a b""#);
+    assert_eq!(identifier("2.0"), r#""2.0""#);
+    assert_eq!(local(&Name::Name("x.2".to_string())), "%x.2");
+
+    // brainfuck.ll
+    let values = |opcode, s| {
+        let (operands, ty, predicate) = operands(opcode, s).unwrap();
+        let values: Vec<_> = operands.into_iter().map(|(_, v)| v).collect();
+        (values, ty, predicate)
+    };
+    let types = |opcode, s| {
+        let (operands, _, _) = operands(opcode, s).unwrap();
+        operands.into_iter().map(|(t, _)| t).collect::<Vec<_>>()
+    };
+    assert_eq!(
+        values(
+            3,
+            "  switch i8 %15, label %41 [\n    i8 43, label %16\n    i8 45, label %20\n  ]"
+        ),
+        (
+            ["%15", "%41", "43", "%16", "45", "%20"]
+                .map(String::from)
+                .to_vec(),
+            None,
+            None,
+        ),
+    );
+    assert_eq!(
+        values(
+            34,
+            "  %14 = getelementptr inbounds [1001 x i8], ptr %7, i64 0, i64 %13"
+        ),
+        (
+            ["%7", "0", "%13"].map(String::from).to_vec(),
+            Some("[1001 x i8]".to_string()),
+            None,
+        ),
+    );
+    assert_eq!(
+        types(
+            3,
+            "  switch i8 %15, label %41 [\n    i8 43, label %16\n    i8 45, label %20\n  ]"
+        ),
+        ["i8", "label", "i8", "label", "i8", "label"],
+    );
+    assert_eq!(
+        types(
+            56,
+            "  %7 = tail call i32 (ptr, ...) @printf(ptr noundef nonnull dereferenceable(1) @.str, i32 noundef %6)"
+        ),
+        ["ptr", "i32", "ptr"],
+    );
+    assert_eq!(types(13, "  %8 = add nuw nsw i32 %6, 1"), ["i32", "i32"]);
+    assert_eq!(
+        values(40, "  %13 = sext i32 %12 to i64"),
+        (vec!["%12".to_string()], Some("i64".to_string()), None),
+    );
+    assert_eq!(
+        values(31, "  %7 = alloca [1001 x i8], align 16"),
+        (vec!["1".to_string()], Some("[1001 x i8]".to_string()), None),
+    );
+    assert_eq!(
+        values(33, "  store volatile i32 0, ptr %1, align 4, !tbaa !5"),
+        (["0", "%1"].map(String::from).to_vec(), None, None),
+    );
+    assert_eq!(
+        values(32, "  %12 = load i32, ptr %6, align 4"),
+        (vec!["%6".to_string()], Some("i32".to_string()), None),
+    );
+    assert_eq!(operands(32, "  %12 = add i32 %6, 1"), None);
+    assert_eq!(
+        values(
+            32,
+            "  %2 = load atomic i32, ptr %1 syncscope(\"agent\") seq_cst, align 4"
+        ),
+        (vec!["%1".to_string()], Some("i32".to_string()), None),
+    );
+    assert_eq!(
+        values(33, "  store atomic i32 1, ptr %1 unordered, align 4"),
+        (["1", "%1"].map(String::from).to_vec(), None, None),
+    );
+
+    // what changes the meaning of an instruction but has no field is kept
+    for string in [
+        "  store volatile i32 0, ptr %1, align 4",
+        "  %2 = load atomic i32, ptr %1 unordered, align 4",
+        "  %2 = musttail call i32 @f(i32 %0)",
+        "  %2 = call fastcc i32 @f(i32 %0)",
+        "  call void @f(ptr sret(%struct.s) align 4 %1)",
+        "  call void @f(i8 signext %0)",
+        "  %2 = call i32 @f(i32 noundef %0)",
+        "  %8 = add nuw nsw i32 %6, 1",
+        "  %7 = alloca [1001 x i8], align 16",
+        "  %16 = load i32, ptr %2, align 4, !dbg !35",
+        "  br i1 %9, label %4, label %5, !llvm.loop !5",
+    ] {
+        assert!(kept(string), "{string}");
+    }
+    assert!(!kept("  %2 = call i32 @f(i32 %0) ; volatile"));
+    assert!(!kept(
+        "  call void @llvm.dbg.value(metadata i32 %0, metadata !14, metadata !DIExpression())"
+    ));
+    let mut b = Builder::new("f");
+    let p = b.param("ptr");
+    b.store(&constant("i32", 0), &p);
+    b.ret(None);
+    let mut store = b.build().basic_blocks[0].insts[0].clone();
+    store.string = "  store volatile i32 0, ptr %0, align 4".to_string();
+    forget(&mut store.uses);
+    let string = store.string.clone();
+    annotate(store.opcode, &string, &mut store.uses);
+    assert_eq!(store.uses[0].value.as_deref(), Some("0"));
+    assert_eq!(store.to_string(), "store volatile i32 0, ptr %0, align 4");
+}
+
+#[test]
+fn test_examples() {
+    use crate::builder::type_of;
+    use crate::ir::Param;
+
+    // the functions of `text` with the fields the JSON has, read natively
+    let parse = |text: &str| -> Vec<Function> {
+        let lines: Vec<_> = text.lines().collect();
+        let mut functions: Vec<_> = crate::source::functions(text).into_iter().collect();
+        functions.sort_by_key(|(_, l)| l.define);
+        let operand = |(ty, value): Typed| Operand {
+            constant: !value.starts_with('%'),
+            name: value.starts_with('%').then(|| value.parse().unwrap()),
+            ty: type_of(&ty),
+            value: None,
+        };
+        functions
+            .into_iter()
+            .map(|(name, l)| {
+                let define = lines[l.define];
+                let open = define.find(&format!("{}(", identifier(&name))).unwrap()
+                    + identifier(&name).len();
+                let close = open + define[open..].find(") ").unwrap();
+                let params: Vec<_> = split(&define[open + 1..close], ',')
+                    .into_iter()
+                    .filter(|p| !p.is_empty())
+                    .map(|p| Param {
+                        name: p.split_whitespace().last().unwrap().parse().unwrap(),
+                        ty: type_of(ty(p).0),
+                    })
+                    .collect();
+
+                let mut basic_blocks: Vec<BasicBlock> = vec![];
+                for (k, &start) in l.insts.iter().enumerate() {
+                    let (_, end) = l.inst(k).unwrap();
+                    let string = lines[start..=end].join("\n");
+                    let code = string.trim();
+                    let (def, code) = match code.split_once(" = ") {
+                        Some((def, code)) if def.starts_with('%') => {
+                            (Some(def.parse::<Name>().unwrap()), code)
+                        }
+                        _ => (None, code),
+                    };
+                    let keyword = skip_flags(code).split_whitespace().next().unwrap();
+                    let opcode = (1..=67).find(|&o| opcode(o) == Some(keyword)).unwrap();
+                    let (operands, _, _) = operands(opcode, &string).unwrap();
+                    let uses: Vec<_> = operands.into_iter().map(operand).collect();
+
+                    let block = l.blocks.iter().rposition(|&b| b <= start).unwrap();
+                    if block == basic_blocks.len() {
+                        let label = lines[l.blocks[block]].split(';').next().unwrap();
+                        let name = match label.trim_end().strip_suffix(':') {
+                            Some(label) => label.parse().unwrap(),
+                            // the unlabeled entry block is numbered after the
+                            // parameters
+                            None => Name::Number(params.len()),
+                        };
+                        basic_blocks.push(BasicBlock {
+                            name,
+                            insts: vec![],
+                            term: Terminator {
+                                opcode: 0,
+                                def: None,
+                                uses: vec![],
+                                string: String::new(),
+                            },
+                        });
+                    }
+                    let b = basic_blocks.last_mut().unwrap();
+                    if opcode <= 11 {
+                        b.term = Terminator {
+                            opcode,
+                            def,
+                            uses,
+                            string,
+                        };
+                    } else {
+                        let blocks = (opcode == 55).then(|| {
+                            code.split('[')
+                                .skip(1)
+                                .map(|p| {
+                                    let (_, block) =
+                                        p.split(']').next().unwrap().rsplit_once(',').unwrap();
+                                    block.trim().parse().unwrap()
+                                })
+                                .collect()
+                        });
+                        b.insts.push(Instruction {
+                            opcode,
+                            def,
+                            uses,
+                            blocks,
+                            string,
+                            ty: None,
+                            predicate: None,
+                        });
+                    }
+                }
+
+                let mut f = Function {
+                    name,
+                    params,
+                    basic_blocks,
+                };
+                read(&mut f);
+                f
+            })
+            .collect()
+    };
+    let uses = |uses: &[Operand]| {
+        uses.iter()
+            .map(|o| (o.name.clone(), o.value.clone(), o.ty.name.clone()))
+            .collect::<Vec<_>>()
+    };
+    let fields = |f: &Function| {
+        let params: Vec<_> = f
+            .params
+            .iter()
+            .map(|p| (p.name.clone(), p.ty.name.clone()))
+            .collect();
+        let blocks: Vec<_> = f
+            .basic_blocks
+            .iter()
+            .map(|b| {
+                let insts: Vec<_> = b
+                    .insts
+                    .iter()
+                    .map(|i| {
+                        (
+                            i.opcode,
+                            i.def.clone(),
+                            uses(&i.uses),
+                            i.blocks.clone(),
+                            i.ty.clone(),
+                            i.predicate.clone(),
+                        )
+                    })
+                    .collect();
+                (b.name.clone(), insts, b.term.opcode, uses(&b.term.uses))
+            })
+            .collect();
+        (params, blocks)
+    };
+
+    // llvm::parse needs the wasm of llvm-json, so the examples are read
+    // natively into the fields the JSON has. Every example prints as its
+    // .printed text, which was checked once against LLVM: with its functions
+    // in place of those of the example, llvm-as and llvm-dis give the same
+    // module as for the example. The printed text also reads back the same.
+    for (text, expected) in [
+        (
+            include_str!("../examples/ll/brainfuck.ll"),
+            include_str!("../examples/ll/brainfuck.printed"),
+        ),
+        (
+            include_str!("../examples/ll/fib.ll"),
+            include_str!("../examples/ll/fib.printed"),
+        ),
+        (
+            include_str!("../examples/ll/fib.g.ll"),
+            include_str!("../examples/ll/fib.g.printed"),
+        ),
+        (
+            include_str!("../examples/ll/for0.ll"),
+            include_str!("../examples/ll/for0.printed"),
+        ),
+        (
+            include_str!("../examples/ll/for1.ll"),
+            include_str!("../examples/ll/for1.printed"),
+        ),
+        (
+            include_str!("../examples/ll/min.ll"),
+            include_str!("../examples/ll/min.printed"),
+        ),
+        (
+            include_str!("../examples/ll/ret.ll"),
+            include_str!("../examples/ll/ret.printed"),
+        ),
+    ] {
+        let functions = parse(text);
+        let printed: Vec<_> = functions.iter().map(|f| f.to_string()).collect();
+        assert_eq!(printed.join("\n"), expected);
+        for (f, printed) in functions.iter().zip(&printed) {
+            let read = parse(printed);
+            assert_eq!(read.len(), 1, "{printed}");
+            assert_eq!(fields(&read[0]), fields(f), "{printed}");
+        }
+    }
+}
@@ -0,0 +1,220 @@
+#![allow(non_snake_case)]
+
+use std::collections::BTreeMap;
+
+use dioxus::prelude::*;
+use wasm_bindgen::prelude::*;
+
+use crate::graphviz;
+
+const KEY: &str = "ullvm-workspace";
+
+/// The analysis settings a document was last viewed with.
+#[derive(Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct Settings {
+    pub k: usize,
+    pub engine: graphviz::Engine,
+    /// The register assignment of every function, by name.
+    pub assignments: BTreeMap<String, String>,
+    pub tab: usize,
+    pub function: usize,
+    /// Whether to parse again shortly after every edit.
+    pub auto: bool,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            k: 4,
+            engine: graphviz::Engine::default(),
+            assignments: BTreeMap::new(),
+            tab: 0,
+            function: 0,
+            auto: false,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct Document {
+    pub name: String,
+    pub text: String,
+    /// The C source the module was compiled from, if known.
+    #[serde(default)]
+    pub source: Option<String>,
+    #[serde(default)]
+    pub settings: Settings,
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct Workspace {
+    pub documents: Vec<Document>,
+    pub current: usize,
+}
+
+impl Workspace {
+    pub fn new(text: String) -> Self {
+        Workspace {
+            documents: vec![Document {
+                name: "untitled".to_string(),
+                text,
+                source: None,
+                settings: Settings::default(),
+            }],
+            current: 0,
+        }
+    }
+
+    pub fn current(&self) -> &Document {
+        &self.documents[self.current]
+    }
+
+    pub fn current_mut(&mut self) -> &mut Document {
+        &mut self.documents[self.current]
+    }
+
+    /// Adds a document named `name`, or a numbered variant of it if the name
+    /// is taken, and returns its index.
+    pub fn add(&mut self, name: &str, text: String) -> usize {
+        let mut unique = name.to_string();
+        let mut n = 1;
+        while self.documents.iter().any(|d| d.name == unique) {
+            n += 1;
+            unique = format!("{name} {n}");
+        }
+        self.documents.push(Document {
+            name: unique,
+            text,
+            source: None,
+            settings: Settings::default(),
+        });
+        self.documents.len() - 1
+    }
+
+    /// Removes the document at `i` unless it is the last one, and returns the
+    /// index of the document to show instead.
+    pub fn remove(&mut self, i: usize) -> usize {
+        if self.documents.len() > 1 && i < self.documents.len() {
+            self.documents.remove(i);
+        }
+        i.min(self.documents.len() - 1)
+    }
+}
+
+fn storage() -> Result<web_sys::Storage, JsValue> {
+    web_sys::window()
+        .ok_or_else(|| JsValue::from_str("no window"))?
+        .local_storage()?
+        .ok_or_else(|| JsValue::from_str("no localStorage"))
+}
+
+/// The workspace saved in localStorage, if any.
+pub fn load() -> Option<Workspace> {
+    let json = storage().ok()?.get_item(KEY).ok()??;
+    serde_json::from_str::<Workspace>(&json)
+        .map_err(|e| tracing::warn!("invalid workspace: {e}"))
+        .ok()
+        .filter(|w| w.current < w.documents.len())
+}
+
+pub fn save(workspace: &Workspace) -> Result<(), JsValue> {
+    storage()?.set_item(KEY, &serde_json::to_string(workspace).unwrap())
+}
+
+fn prompt(message: &str, default: &str) -> Option<String> {
+    web_sys::window()?
+        .prompt_with_message_and_default(message, default)
+        .ok()?
+        .filter(|s| !s.trim().is_empty())
+}
+
+#[component]
+pub fn Documents(workspace: Signal<Workspace>, onswitch: EventHandler<usize>) -> Element {
+    let current = workspace.read().current;
+    let names: Vec<_> = workspace
+        .read()
+        .documents
+        .iter()
+        .map(|d| d.name.clone())
+        .collect();
+    let single = names.len() == 1;
+
+    rsx! {
+        div { class: "flex h-12",
+            select {
+                class: "bg-zinc-100 dark:bg-zinc-800 flex-1",
+                onchange: move |e: Event<FormData>| {
+                    if let Ok(i) = e.data.value().parse() {
+                        onswitch.call(i);
+                    }
+                },
+                for (i , n) in names.iter().enumerate() {
+                    option { key: "{i}", value: "{i}", selected: i == current, "{n}" }
+                }
+            }
+            button {
+                class: "bg-zinc-100 dark:bg-zinc-800 px-2",
+                title: "New document",
+                onclick: move |_| {
+                    if let Some(name) = prompt("Name of the new document", "untitled") {
+                        let i = workspace.write().add(&name, String::new());
+                        onswitch.call(i);
+                    }
+                },
+                "+"
+            }
+            button {
+                class: "bg-zinc-100 dark:bg-zinc-800 px-2",
+                title: "Rename document",
+                onclick: move |_| {
+                    let name = workspace.read().current().name.clone();
+                    if let Some(name) = prompt("New name of the document", &name) {
+                        workspace.write().current_mut().name = name;
+                    }
+                },
+                "✎"
+            }
+            button {
+                class: "bg-zinc-100 dark:bg-zinc-800 px-2",
+                title: "Delete document",
+                disabled: single,
+                onclick: move |_| {
+                    let i = workspace.write().remove(current);
+                    onswitch.call(i);
+                },
+                "×"
+            }
+        }
+    }
+}
+
+#[test]
+fn test_workspace() {
+    let mut w = Workspace::new("define void @main() {}".to_string());
+    assert_eq!(w.add("untitled", String::new()), 1);
+    assert_eq!(w.add("untitled", String::new()), 2);
+    assert_eq!(w.add("fib", String::new()), 3);
+    assert_eq!(
+        w.documents.iter().map(|d| &d.name[..]).collect::<Vec<_>>(),
+        vec!["untitled", "untitled 2", "untitled 3", "fib"],
+    );
+
+    assert_eq!(w.remove(3), 2);
+    assert_eq!(w.remove(0), 0);
+    assert_eq!(w.remove(0), 0);
+    assert_eq!(w.remove(0), 0);
+    assert_eq!(w.documents.len(), 1);
+    assert_eq!(w.current().name, "untitled 3");
+
+    w.current_mut().settings.engine = graphviz::Engine::Circo;
+    let json = serde_json::to_string(&w).unwrap();
+    assert!(json.contains(r#""engine":"circo""#));
+    assert_eq!(serde_json::from_str::<Workspace>(&json).unwrap(), w);
+
+    // documents saved before settings existed
+    let w: Workspace =
+        serde_json::from_str(r#"{"documents":[{"name":"a","text":""}],"current":0}"#).unwrap();
+    assert_eq!(w.current().settings, Settings::default());
+    assert_eq!(w.current().source, None);
+}